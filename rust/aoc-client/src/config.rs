@@ -0,0 +1,105 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use serde::Deserialize;
+use crate::Error;
+
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+pub const CONFIG_VAR: &str = "AOC_CONFIG";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// How to reach Advent of Code, read from `~/.config/aoc/config.toml`:
+///
+/// ```toml
+/// session = "53616c7465645f5f..."
+/// base_url = "http://localhost:8080"
+/// history = "/home/me/aoc-history.toml"
+/// ```
+///
+/// `AOC_SESSION` and `AOC_BASE_URL` take precedence over the file, and `AOC_CONFIG`
+/// names another file to read.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Config {
+    pub session: Option<String>,
+    #[serde(default = "default_base_url")]
+    pub base_url: String,
+    /// Where submitted answers are kept, `history.toml` next to the config file by default.
+    pub history: Option<PathBuf>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config { session: None, base_url: default_base_url(), history: None }
+    }
+}
+
+fn default_base_url() -> String {
+    String::from(DEFAULT_BASE_URL)
+}
+
+impl Config {
+    pub fn load() -> Result<Self, Error> {
+        let mut config = match Config::path() {
+            Some(path) => Config::read(&path)?,
+            None => Config::default(),
+        };
+        if let Some(session) = var(SESSION_VAR) {
+            config.session = Some(session);
+        }
+        if let Some(base_url) = var(BASE_URL_VAR) {
+            config.base_url = base_url;
+        }
+        Ok(config)
+    }
+
+    /// Reads the config in `path`, a missing file is an empty config.
+    pub fn read(path: &PathBuf) -> Result<Self, Error> {
+        match fs::read_to_string(path) {
+            Ok(text) => toml::from_str(&text)
+                .map_err(|e| Error::Config(format!("{}: {}", path.display(), e.message()))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(Error::Config(format!("{}: {}", path.display(), e))),
+        }
+    }
+
+    pub fn history_path(&self) -> Option<PathBuf> {
+        self.history.clone().or_else(|| Some(Config::path()?.with_file_name("history.toml")))
+    }
+
+    fn path() -> Option<PathBuf> {
+        if let Some(path) = var(CONFIG_VAR) {
+            return Some(PathBuf::from(path));
+        }
+        let config_dir = var("XDG_CONFIG_HOME").map(PathBuf::from)
+            .or_else(|| var("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        Some(config_dir.join("aoc").join("config.toml"))
+    }
+}
+
+fn var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.trim().is_empty()).map(|value| value.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readConfigTest() {
+        let dir = env::temp_dir().join("aoc-client-config");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        fs::write(&path, "session = \"abc\"\n").unwrap();
+        assert_eq!(Config { session: Some(String::from("abc")), ..Config::default() }, Config::read(&path).unwrap());
+        fs::write(&path, "base_url = \"http://localhost:8080\"\n").unwrap();
+        assert_eq!("http://localhost:8080", Config::read(&path).unwrap().base_url);
+        fs::write(&path, "history = \"/tmp/history.toml\"\n").unwrap();
+        assert_eq!(Some(PathBuf::from("/tmp/history.toml")), Config::read(&path).unwrap().history_path());
+        fs::write(&path, "session = 5\n").unwrap();
+        assert!(Config::read(&path).is_err());
+        assert_eq!(Config::default(), Config::read(&dir.join("missing.toml")).unwrap());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
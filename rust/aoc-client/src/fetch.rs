@@ -0,0 +1,97 @@
+use std::fs;
+use std::path::PathBuf;
+use aoc_core::Inputs;
+use crate::{Client, Error};
+
+/// Where a fetched input is, and whether it had to be downloaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+impl Fetched {
+    pub fn path(&self) -> &PathBuf {
+        match self {
+            Fetched::Cached(path) | Fetched::Downloaded(path) => path,
+        }
+    }
+}
+
+impl Client {
+    /// Makes sure the input of `day` is among `inputs`, downloading it only when there is
+    /// no input file for the day yet, whichever way it is named, or only an empty one.
+    pub fn fetch(&self, year: u16, inputs: &Inputs, day: u8) -> Result<Fetched, Error> {
+        let path = inputs.path(day);
+        if path.is_file() && fs::metadata(&path)?.len() > 0 {
+            return Ok(Fetched::Cached(path));
+        }
+        let input = self.input(year, day)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, input)?;
+        Ok(Fetched::Downloaded(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use aoc_core::Naming;
+    use crate::mock::Server;
+    use crate::Config;
+    use super::*;
+
+    fn client(server: &Server) -> Client {
+        Client::new(&Config { session: Some(String::from("cafe")), base_url: server.url.clone(), ..Config::default() }).unwrap()
+    }
+
+    #[test]
+    fn fetchTest() {
+        let dir = env::temp_dir().join("aoc-client-fetch");
+        let _ = fs::remove_dir_all(&dir);
+        let server = Server::start(vec![(200, "1000\n2000\n")]);
+        let inputs = Inputs::new(&dir, Naming::Spelled);
+
+        let fetched = client(&server).fetch(2020, &inputs, 1).unwrap();
+        assert_eq!(Fetched::Downloaded(dir.join("dayOne.txt")), fetched);
+        assert_eq!("1000\n2000\n", fs::read_to_string(fetched.path()).unwrap());
+        let requests = server.requests();
+        assert!(requests[0].starts_with("GET /2020/day/1/input HTTP/1.1"));
+        assert!(requests[0].contains("session=cafe"));
+
+        // The server only answers once, so this has to come from the cache.
+        assert_eq!(Fetched::Cached(dir.join("dayOne.txt")), client(&server).fetch(2020, &inputs, 1).unwrap());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn emptyInputTest() {
+        let dir = env::temp_dir().join("aoc-client-empty");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day1.txt"), "").unwrap();
+        let server = Server::start(vec![(200, "1721\n979\n")]);
+
+        let fetched = client(&server).fetch(2020, &Inputs::new(&dir, Naming::Numeric), 1).unwrap();
+        assert_eq!(Fetched::Downloaded(dir.join("day1.txt")), fetched);
+        assert_eq!("1721\n979\n", fs::read_to_string(fetched.path()).unwrap());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn notUnlockedTest() {
+        let dir = env::temp_dir().join("aoc-client-not-unlocked");
+        let _ = fs::remove_dir_all(&dir);
+        let server = Server::start(vec![(404, "Please don't repeatedly request this endpoint before it unlocks!")]);
+        let error = client(&server).fetch(2022, &Inputs::new(&dir, Naming::Numeric), 25).unwrap_err();
+        assert!(matches!(error, Error::Status { code: 404, .. }));
+        assert!(!dir.join("day25.txt").exists());
+    }
+
+    #[test]
+    fn noSessionTest() {
+        assert!(matches!(Client::new(&Config::default()), Err(Error::NoSession)));
+    }
+}
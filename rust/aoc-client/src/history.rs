@@ -0,0 +1,155 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use crate::Outcome;
+
+/// One submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// When it was submitted, in seconds since the Unix epoch.
+    pub at: u64,
+    /// The seconds to wait before the next answer to the puzzle.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wait: Option<u64>,
+}
+
+impl Attempt {
+    fn is_for(&self, year: u16, day: u8, part: u8) -> bool {
+        self.year == year && self.day == day && self.part == part
+    }
+}
+
+/// Every answer submitted so far, kept in a TOML file with an `[[attempt]]` per answer.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History {
+    #[serde(default, rename = "attempt")]
+    attempts: Vec<Attempt>,
+}
+
+/// Why an answer is not worth submitting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    Solved { answer: String },
+    Tried { outcome: Outcome, at: u64 },
+    /// At least as high as an answer that was too high, or as low as one that was too low.
+    OutOfBounds { outcome: Outcome, answer: String },
+    CoolDown { seconds: u64 },
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Refusal::Solved { answer } => write!(f, "the part is already solved, the answer was {}", answer),
+            Refusal::Tried { outcome, .. } => write!(f, "this answer was already submitted, it was {}", outcome),
+            Refusal::OutOfBounds { outcome, answer } => write!(f, "{} was already {}", answer, outcome),
+            Refusal::CoolDown { seconds } => write!(f, "wait {}s before submitting another answer", seconds),
+        }
+    }
+}
+
+impl History {
+    /// Reads the history in `path`, a missing file has no attempts yet.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => toml::from_str(&text).map_err(|e| io::Error::new(
+                io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e.message()))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text = toml::to_string(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, text)
+    }
+
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    pub fn record(&mut self, attempt: Attempt) {
+        self.attempts.push(attempt);
+    }
+
+    /// Whether `answer` is worth submitting at `now`, judging by the earlier attempts.
+    pub fn check(&self, year: u16, day: u8, part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+        let attempts: Vec<&Attempt> = self.attempts.iter().filter(|a| a.is_for(year, day, part)).collect();
+        if let Some(solved) = attempts.iter().find(|a| a.outcome == Outcome::Correct) {
+            return Err(Refusal::Solved { answer: solved.answer.clone() });
+        }
+        let judged = |a: &&&Attempt| !matches!(a.outcome, Outcome::TooSoon | Outcome::WrongLevel | Outcome::Unknown);
+        if let Some(tried) = attempts.iter().filter(judged).find(|a| a.answer == answer) {
+            return Err(Refusal::Tried { outcome: tried.outcome, at: tried.at });
+        }
+        if let Ok(value) = answer.parse::<i128>() {
+            let out_of_bounds = attempts.iter().find(|a| match (a.outcome, a.answer.parse::<i128>()) {
+                (Outcome::TooHigh, Ok(high)) => value >= high,
+                (Outcome::TooLow, Ok(low)) => value <= low,
+                _ => false,
+            });
+            if let Some(bound) = out_of_bounds {
+                return Err(Refusal::OutOfBounds { outcome: bound.outcome, answer: bound.answer.clone() });
+            }
+        }
+        let ready_at = self.attempts.iter()
+            .filter(|a| a.year == year && a.day == day)
+            .filter_map(|a| a.wait.map(|wait| a.at + wait))
+            .max();
+        match ready_at {
+            Some(ready_at) if ready_at > now => Err(Refusal::CoolDown { seconds: ready_at - now }),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attempt(part: u8, answer: &str, outcome: Outcome, at: u64, wait: Option<u64>) -> Attempt {
+        Attempt { year: 2022, day: 11, part, answer: answer.to_string(), outcome, at, wait }
+    }
+
+    #[test]
+    fn checkTest() {
+        let mut history = History::default();
+        assert_eq!(Ok(()), history.check(2022, 11, 1, "100", 1000));
+        history.record(attempt(1, "100", Outcome::TooHigh, 1000, Some(60)));
+        assert_eq!(Err(Refusal::CoolDown { seconds: 30 }), history.check(2022, 11, 1, "90", 1030));
+        assert_eq!(Ok(()), history.check(2022, 11, 1, "90", 1060));
+        assert_eq!(Err(Refusal::Tried { outcome: Outcome::TooHigh, at: 1000 }), history.check(2022, 11, 1, "100", 2000));
+        assert!(matches!(history.check(2022, 11, 1, "150", 2000), Err(Refusal::OutOfBounds { .. })));
+        assert_eq!(Ok(()), history.check(2022, 12, 1, "100", 1030));
+
+        history.record(attempt(1, "90", Outcome::TooSoon, 1070, Some(20)));
+        assert_eq!(Ok(()), history.check(2022, 11, 1, "90", 2000));
+        history.record(attempt(1, "90", Outcome::Correct, 2000, None));
+        assert_eq!(Err(Refusal::Solved { answer: String::from("90") }), history.check(2022, 11, 1, "80", 3000));
+        assert_eq!(Ok(()), history.check(2022, 11, 2, "80", 3000));
+    }
+
+    #[test]
+    fn saveHistoryTest() {
+        let path = std::env::temp_dir().join("aoc-client-history").join("history.toml");
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+        let mut history = History::default();
+        history.record(attempt(1, "100", Outcome::TooHigh, 1000, Some(60)));
+        history.record(attempt(2, "TLFGBZHCN", Outcome::Correct, 1100, None));
+        history.save(&path).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        assert!(text.starts_with("[[attempt]]\nyear = 2022\nday = 11\npart = 1\nanswer = \"100\"\noutcome = \"too-high\"\n"));
+        assert_eq!(history, History::load(&path).unwrap());
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+        assert_eq!(History::default(), History::load(&path).unwrap());
+    }
+}
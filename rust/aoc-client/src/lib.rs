@@ -0,0 +1,95 @@
+#![allow(non_snake_case)]
+
+use std::fmt;
+use std::io;
+use std::time::Duration;
+
+pub mod config;
+pub mod fetch;
+pub mod history;
+#[cfg(test)]
+mod mock;
+pub mod submit;
+
+pub use config::Config;
+pub use fetch::Fetched;
+pub use history::{Attempt, History, Refusal};
+pub use submit::{Outcome, Response};
+
+const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"), " (", env!("CARGO_PKG_AUTHORS"), ")");
+
+/// Why Advent of Code could not be reached, or did not give what was asked for.
+#[derive(Debug)]
+pub enum Error {
+    /// There is no session token in `AOC_SESSION` or the config file.
+    NoSession,
+    Config(String),
+    /// The server answered with an error status, like 404 for a puzzle that is not unlocked yet.
+    Status { code: u16, message: String },
+    /// The server could not be reached.
+    Transport(String),
+    Io(io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::NoSession => write!(f, "no session token, set {} or session in the config file", config::SESSION_VAR),
+            Error::Config(message) => write!(f, "bad config: {}", message),
+            Error::Status { code: 404, .. } => write!(f, "not found (404), is the puzzle unlocked yet?"),
+            Error::Status { code: 400, .. } => write!(f, "the session token was not accepted (400)"),
+            Error::Status { code, message } => write!(f, "the server answered {}: {}", code, message),
+            Error::Transport(message) => write!(f, "could not reach the server: {}", message),
+            Error::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+/// Talks to Advent of Code, or whatever server `Config::base_url` names, as the user of the session.
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(config: &Config) -> Result<Self, Error> {
+        let session = config.session.clone().ok_or(Error::NoSession)?;
+        let agent = ureq::AgentBuilder::new().timeout(Duration::from_secs(30)).user_agent(USER_AGENT).build();
+        Ok(Client { base_url: config.base_url.trim_end_matches('/').to_string(), session, agent })
+    }
+
+    /// The input of a puzzle, as it was downloaded.
+    pub fn input(&self, year: u16, day: u8) -> Result<String, Error> {
+        read(self.get(&format!("/{}/day/{}/input", year, day)).call())
+    }
+
+    fn get(&self, path: &str) -> ureq::Request {
+        self.agent.get(&format!("{}{}", self.base_url, path))
+            .set("Cookie", &format!("session={}", self.session))
+    }
+
+    fn post(&self, path: &str) -> ureq::Request {
+        self.agent.post(&format!("{}{}", self.base_url, path))
+            .set("Cookie", &format!("session={}", self.session))
+    }
+}
+
+pub(crate) fn read(response: Result<ureq::Response, ureq::Error>) -> Result<String, Error> {
+    match response {
+        Ok(response) => Ok(response.into_string()?),
+        Err(ureq::Error::Status(code, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            Err(Error::Status { code, message: body.lines().next().unwrap_or_default().trim().to_string() })
+        }
+        Err(ureq::Error::Transport(e)) => Err(Error::Transport(e.to_string())),
+    }
+}
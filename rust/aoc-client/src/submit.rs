@@ -0,0 +1,179 @@
+use std::fmt;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use aoc_core::Part;
+use crate::{read, Client, Error};
+
+/// What the server made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Outcome {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// The answer was not looked at, an earlier one was given too recently.
+    TooSoon,
+    /// The part is already solved, or part one is not solved yet.
+    WrongLevel,
+    Unknown,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "the right answer"),
+            Outcome::Wrong => write!(f, "not the right answer"),
+            Outcome::TooHigh => write!(f, "too high"),
+            Outcome::TooLow => write!(f, "too low"),
+            Outcome::TooSoon => write!(f, "given too soon after the previous answer"),
+            Outcome::WrongLevel => write!(f, "not for the level being solved, is the part already solved?"),
+            Outcome::Unknown => write!(f, "not understood"),
+        }
+    }
+}
+
+/// The server's reply to an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub outcome: Outcome,
+    /// How long to wait before the next answer.
+    pub wait: Option<Duration>,
+    /// The text of the reply.
+    pub message: String,
+}
+
+impl Response {
+    /// Reads the reply page, only its `<article>` says anything about the answer.
+    pub fn parse(page: &str) -> Self {
+        let message = article_text(page);
+        let outcome = if message.contains("That's the right answer") {
+            Outcome::Correct
+        } else if message.contains("That's not the right answer") {
+            if message.contains("too high") {
+                Outcome::TooHigh
+            } else if message.contains("too low") {
+                Outcome::TooLow
+            } else {
+                Outcome::Wrong
+            }
+        } else if message.contains("You gave an answer too recently") {
+            Outcome::TooSoon
+        } else if message.contains("You don't seem to be solving the right level") {
+            Outcome::WrongLevel
+        } else {
+            Outcome::Unknown
+        };
+        Response { outcome, wait: wait(&message), message }
+    }
+}
+
+/// The text of the page's article, without its markup.
+fn article_text(page: &str) -> String {
+    let article = match (page.find("<article"), page.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &page[start..end],
+        _ => page,
+    };
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// The wait in "You have 1m 24s left to wait" or "please wait 5 minutes before trying again".
+fn wait(message: &str) -> Option<Duration> {
+    if let Some(start) = message.find("You have ") {
+        let rest = &message[start + "You have ".len()..];
+        let left = &rest[..rest.find(" left to wait")?];
+        let mut seconds = 0;
+        for amount in left.split_whitespace() {
+            let (number, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+            let number: u64 = number.parse().ok()?;
+            seconds += match unit {
+                "h" => number * 3600,
+                "m" => number * 60,
+                "s" => number,
+                _ => return None,
+            };
+        }
+        return Some(Duration::from_secs(seconds));
+    }
+    let message = message.to_lowercase();
+    let start = message.find("please wait ")?;
+    let mut words = message[start + "please wait ".len()..].split_whitespace();
+    let number = words.next()?;
+    let number = match number {
+        "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "ten" => 10,
+        number => number.parse().ok()?,
+    };
+    match words.next()? {
+        unit if unit.starts_with("minute") => Some(Duration::from_secs(number * 60)),
+        unit if unit.starts_with("second") => Some(Duration::from_secs(number)),
+        _ => None,
+    }
+}
+
+impl Client {
+    /// Posts the answer to one part and reads what the server made of it.
+    pub fn submit(&self, year: u16, day: u8, part: Part, answer: &str) -> Result<Response, Error> {
+        let level = part.to_string();
+        let page = read(self.post(&format!("/{}/day/{}/answer", year, day))
+            .send_form(&[("level", level.as_str()), ("answer", answer)]))?;
+        Ok(Response::parse(&page))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::Server;
+    use crate::Config;
+
+    const WRONG: &str = "<main>\n<article><p>That's not the right answer; your answer is too high.  If you're stuck, \
+        make sure you're using the full input data; there are also some general tips on the \
+        <a href=\"/2022/about\">about page</a>.  Please wait one minute before trying again. \
+        <a href=\"/2022/day/5\">[Return to Day 5]</a></p></article>\n</main>";
+
+    #[test]
+    fn parseResponseTest() {
+        let response = Response::parse(WRONG);
+        assert_eq!(Outcome::TooHigh, response.outcome);
+        assert_eq!(Some(Duration::from_secs(60)), response.wait);
+        assert!(response.message.starts_with("That's not the right answer; your answer is too high."));
+
+        let response = Response::parse("<article><p>You gave an answer too recently; you have to wait after submitting \
+            an answer before trying again.  You have 1m 24s left to wait. <a href=\"/2022/day/5\">[Return to Day 5]</a></p></article>");
+        assert_eq!(Outcome::TooSoon, response.outcome);
+        assert_eq!(Some(Duration::from_secs(84)), response.wait);
+
+        let response = Response::parse("<article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.</p></article>");
+        assert_eq!((Outcome::Correct, None), (response.outcome, response.wait));
+        assert_eq!(Outcome::Wrong, Response::parse("<article><p>That's not the right answer.  please wait 5 minutes before trying again.</p></article>").outcome);
+        assert_eq!(Outcome::WrongLevel, Response::parse("<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>").outcome);
+        assert_eq!(Outcome::Unknown, Response::parse("<html>Bad gateway</html>").outcome);
+    }
+
+    #[test]
+    fn submitTest() {
+        let server = Server::start(vec![(200, WRONG)]);
+        let client = Client::new(&Config { session: Some(String::from("cafe")), base_url: server.url.clone(), ..Config::default() }).unwrap();
+        let response = client.submit(2022, 5, Part::Two, "MCD").unwrap();
+        assert_eq!(Outcome::TooHigh, response.outcome);
+        let request = &server.requests()[0];
+        assert!(request.starts_with("POST /2022/day/5/answer HTTP/1.1"));
+        assert!(request.contains("session=cafe"));
+        assert!(request.ends_with("level=2&answer=MCD"));
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use toml::{Table, Value};
+use crate::{Error, Part};
+
+/// The known answers of one year, kept in an `answers.toml` next to its inputs:
+///
+/// ```toml
+/// [day5]
+/// part1 = "TLFGBZHCN"
+/// part2 = "QRQFHFWCL"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    answers: BTreeMap<(u8, Part), String>,
+}
+
+impl Answers {
+    /// Reads the answers in `path`, a missing file has no answers yet.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => text.parse().map_err(|e| io::Error::new(
+                io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.answers.get(&(day, part)).map(|a| a.as_str())
+    }
+
+    pub fn insert(&mut self, day: u8, part: Part, answer: impl Into<String>) {
+        self.answers.insert((day, part), answer.into());
+    }
+
+    pub fn len(&self) -> usize {
+        self.answers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+}
+
+impl FromStr for Answers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let table: Table = s.parse().map_err(|e: toml::de::Error| e.message().to_string())?;
+        let mut answers = Answers::default();
+        for (key, parts) in table {
+            let day: u8 = key.strip_prefix("day")
+                .and_then(|day| day.parse().ok())
+                .ok_or_else(|| format!("expected a table named like [day5], found [{}]", key))?;
+            let parts = match parts {
+                Value::Table(parts) => parts,
+                _ => return Err(format!("{} should be a table", key)),
+            };
+            for (key, answer) in parts {
+                let part: Part = key.strip_prefix("part")
+                    .and_then(|part| part.parse().ok())
+                    .ok_or_else(|| format!("expected part1 or part2 in [day{}], found {}", day, key))?;
+                let answer = match answer {
+                    Value::String(answer) => answer,
+                    Value::Integer(answer) => answer.to_string(),
+                    _ => return Err(format!("day{}.{} should be a string or an integer", day, key)),
+                };
+                answers.insert(day, part, answer);
+            }
+        }
+        Ok(answers)
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut previous = None;
+        for ((day, part), answer) in &self.answers {
+            if previous != Some(*day) {
+                if previous.is_some() {
+                    writeln!(f)?;
+                }
+                writeln!(f, "[day{}]", day)?;
+                previous = Some(*day);
+            }
+            writeln!(f, "part{} = {}", part, Value::String(answer.clone()))?;
+        }
+        Ok(())
+    }
+}
+
+/// How an answer compares to the known answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong { expected: String },
+    /// There is a known answer, but the part has no solution or failed.
+    Missing { expected: String },
+    /// The part found an answer that is not known yet.
+    New,
+    /// The part has no solution and no known answer.
+    Unknown,
+}
+
+/// Whether an answer looks like the `0` or empty string of a solver that was never written,
+/// which is not worth recording as a known answer.
+pub fn is_placeholder(answer: &str) -> bool {
+    matches!(answer.trim(), "" | "0")
+}
+
+pub fn check(expected: Option<&str>, answer: Result<Option<&str>, &Error>) -> Verdict {
+    match (expected, answer) {
+        (Some(expected), Ok(Some(answer))) if expected == answer => Verdict::Correct,
+        (Some(expected), Ok(Some(_))) => Verdict::Wrong { expected: expected.to_string() },
+        (Some(expected), _) => Verdict::Missing { expected: expected.to_string() },
+        (None, Ok(Some(_))) => Verdict::New,
+        (None, _) => Verdict::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = "[day2]
+part1 = 11906
+part2 = \"11186\"
+
+[day10]
+part1 = \"13920\"
+";
+
+    #[test]
+    fn parseAnswersTest() {
+        let answers: Answers = ANSWERS.parse().unwrap();
+        assert_eq!(3, answers.len());
+        assert_eq!(Some("11906"), answers.get(2, Part::One));
+        assert_eq!(Some("11186"), answers.get(2, Part::Two));
+        assert_eq!(None, answers.get(10, Part::Two));
+        assert!("[dayfive]\npart1 = \"1\"".parse::<Answers>().is_err());
+        assert!("[day5]\npart3 = \"1\"".parse::<Answers>().is_err());
+        assert!("[day5\n".parse::<Answers>().is_err());
+    }
+
+    #[test]
+    fn writeAnswersTest() {
+        let mut answers = Answers::default();
+        answers.insert(10, Part::One, "13920");
+        answers.insert(2, Part::Two, "11186");
+        answers.insert(2, Part::One, "11906");
+        let written = answers.to_string();
+        assert_eq!(ANSWERS.replace("11906", "\"11906\""), written);
+        assert_eq!(Ok(answers), written.parse());
+    }
+
+    #[test]
+    fn checkTest() {
+        let failed = Error::Panic(String::from("not yet implemented"));
+        assert_eq!(Verdict::Correct, check(Some("42"), Ok(Some("42"))));
+        assert_eq!(Verdict::Wrong { expected: String::from("42") }, check(Some("42"), Ok(Some("41"))));
+        assert_eq!(Verdict::Missing { expected: String::from("42") }, check(Some("42"), Ok(None)));
+        assert_eq!(Verdict::Missing { expected: String::from("42") }, check(Some("42"), Err(&failed)));
+        assert_eq!(Verdict::New, check(None, Ok(Some("42"))));
+        assert_eq!(Verdict::Unknown, check(None, Err(&failed)));
+        assert!(is_placeholder("0") && is_placeholder(" "));
+        assert!(!is_placeholder("10"));
+    }
+}
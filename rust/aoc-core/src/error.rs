@@ -0,0 +1,72 @@
+use std::any::Any;
+use std::fmt;
+use std::io;
+use std::time::Duration;
+use crate::timing::Elapsed;
+
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Why a day, or one part of it, could not be solved.
+#[derive(Debug)]
+pub enum Error {
+    /// The input could not be read.
+    Input(io::Error),
+    /// The solution rejected its input.
+    Parse(BoxError),
+    /// The input was fine, but the part has no answer for it.
+    Solve(BoxError),
+    /// The solution panicked.
+    Panic(String),
+    /// The day took longer than it was given, it is left running in the background.
+    Timeout(Duration),
+}
+
+impl Error {
+    /// Turns the payload of a caught panic into an error, keeping its message when it has one.
+    pub fn panic(payload: Box<dyn Any + Send>) -> Self {
+        let message = match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => match payload.downcast::<&str>() {
+                Ok(message) => message.to_string(),
+                Err(_) => String::from("no message"),
+            },
+        };
+        Error::Panic(message)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Input(e) => write!(f, "failed to read input: {}", e),
+            Error::Parse(e) => write!(f, "bad input: {}", e),
+            Error::Solve(e) => write!(f, "{}", e),
+            Error::Panic(message) => write!(f, "panicked: {}", message),
+            Error::Timeout(limit) => write!(f, "timed out after {}", Elapsed(*limit)),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Input(e) => Some(e),
+            Error::Parse(e) | Error::Solve(e) => Some(e.as_ref()),
+            Error::Panic(_) | Error::Timeout(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic;
+
+    #[test]
+    fn panicMessageTest() {
+        let payload = panic::catch_unwind(|| panic!("no beacon in row {}", 10)).unwrap_err();
+        assert_eq!("panicked: no beacon in row 10", Error::panic(payload).to_string());
+        let payload = panic::catch_unwind(|| panic!("not yet implemented")).unwrap_err();
+        assert_eq!("panicked: not yet implemented", Error::panic(payload).to_string());
+    }
+}
@@ -0,0 +1,174 @@
+use std::fmt::{self, Display};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::{registry, Part, Solution};
+
+/// The input of an example, written out in the test or kept in a file, see `example`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Example {
+    Text(String),
+    File(u8),
+}
+
+/// The `k`th example file of the day, `resource/examples/dayN_k.txt`.
+pub fn example(k: u8) -> Example {
+    Example::File(k)
+}
+
+impl From<&str> for Example {
+    fn from(text: &str) -> Self {
+        Example::Text(text.to_string())
+    }
+}
+
+impl From<String> for Example {
+    fn from(text: String) -> Self {
+        Example::Text(text)
+    }
+}
+
+impl Display for Example {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Example::Text(text) => write!(f, "example {:?}", text.lines().next().unwrap_or_default()),
+            Example::File(k) => write!(f, "example file {}", k),
+        }
+    }
+}
+
+/// The example files of the crate in `manifest_dir`, in `resource/examples` or `resources/examples`.
+pub fn dir(manifest_dir: &Path) -> PathBuf {
+    ["resource", "resources"].iter()
+        .map(|resource| manifest_dir.join(resource).join("examples"))
+        .find(|dir| dir.is_dir())
+        .unwrap_or_else(|| manifest_dir.join("resource").join("examples"))
+}
+
+/// Checks the answers a registered day gives for its examples, used by `aoc_examples!`.
+pub struct Examples {
+    solution: Solution,
+    dir: PathBuf,
+}
+
+impl Examples {
+    /// The examples of a registered day, with the example files in `dir(manifest_dir)`.
+    pub fn new(year: u16, day: u8, manifest_dir: &str) -> Self {
+        let solution = registry::solutions(year).into_iter()
+            .find(|s| s.day() == day)
+            .unwrap_or_else(|| panic!("there is no registered solution for {} day {}", year, day));
+        Examples { solution, dir: dir(Path::new(manifest_dir)) }
+    }
+
+    pub fn path(&self, k: u8) -> PathBuf {
+        self.dir.join(format!("day{}_{}.txt", self.solution.day(), k))
+    }
+
+    /// Panics unless `part`, `part_one` or `part_two`, answers `expected` for the example.
+    pub fn check(&self, part: &str, example: impl Into<Example>, expected: impl Display) {
+        let example = example.into();
+        let part = match part {
+            "part_one" => Part::One,
+            "part_two" => Part::Two,
+            other => panic!("expected part_one or part_two, found {}", other),
+        };
+        let input = match &example {
+            Example::Text(text) => text.clone(),
+            Example::File(k) => fs::read_to_string(self.path(*k))
+                .unwrap_or_else(|e| panic!("{}: {}", self.path(*k).display(), e)),
+        };
+        let answer = self.solution.load(input)
+            .and_then(|puzzle| puzzle.solve(part))
+            .unwrap_or_else(|e| panic!("part {} failed on {}: {}", part, example, e));
+        assert_eq!(Some(expected.to_string()), answer, "part {} of day {} on {}", part, self.solution.day(), example);
+    }
+}
+
+/// Turns a day's examples into tests, with one `(input, expected)` pair per example:
+///
+/// ```ignore
+/// aoc_examples!(DayFive,
+///     part_one: [(EXAMPLE, "CMZ"), (example(2), "DCM")],
+///     part_two: [(example(1), "MCD")]);
+/// ```
+///
+/// The day is a type implementing `Day` or `TryDay`, or the day number for days written
+/// some other way, and has to be registered for `crate::YEAR`. An input is the example
+/// itself, or `example(k)` for the file `resource/examples/dayN_k.txt`.
+///
+/// Each part with examples gets its test, `examples::part_one` or `examples::part_two`,
+/// which is how `aoc status` tells the parts with examples from those without.
+#[macro_export]
+#[allow(clippy::crate_in_macro_def)] // the YEAR of the crate the examples are in
+macro_rules! aoc_examples {
+    (@tests $year:expr $(,)?) => {};
+    (@tests $year:expr, $part:ident: [] $(, $($rest:tt)*)?) => {
+        $crate::aoc_examples!(@tests $year $(, $($rest)*)?);
+    };
+    (@tests $year:expr, $part:ident: [$(($input:expr, $expected:expr)),+ $(,)?] $(, $($rest:tt)*)?) => {
+        #[test]
+        fn $part() {
+            #[allow(unused_imports)]
+            use $crate::examples::example;
+            let examples = $crate::examples::Examples::new($year, day(), env!("CARGO_MANIFEST_DIR"));
+            $(examples.check(stringify!($part), $input, $expected);)+
+        }
+        $crate::aoc_examples!(@tests $year $(, $($rest)*)?);
+    };
+    (@test $year:expr, $day:expr, $($parts:tt)+) => {
+        mod examples {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #[allow(dead_code)] // when no part has examples
+            fn day() -> u8 {
+                $day
+            }
+
+            $crate::aoc_examples!(@tests $year, $($parts)+);
+        }
+    };
+    ($day:literal, $($parts:tt)+) => {
+        $crate::aoc_examples!(@test crate::YEAR, $day, $($parts)+);
+    };
+    ($day:ident, $($parts:tt)+) => {
+        $crate::aoc_examples!(@test crate::YEAR, {
+            #[allow(unused_imports)]
+            use $crate::{Day as _, TryDay as _};
+            <$day>::day().parse().expect("Day::day() should be the day number")
+        }, $($parts)+);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const YEAR: u16 = 1997;
+
+    fn length(input: &str) -> usize {
+        input.trim().len()
+    }
+
+    fn words(input: &str) -> usize {
+        input.split_whitespace().count()
+    }
+
+    crate::register!(Solution::functions(YEAR, 6, length, words));
+
+    crate::aoc_examples!(@test YEAR, 6,
+        part_one: [("abc", 3), (String::from("ab"), "2"), (example(1), 11)],
+        part_two: [(example(1), 2)]);
+
+    #[test]
+    fn examplePathTest() {
+        let examples = Examples::new(YEAR, 6, env!("CARGO_MANIFEST_DIR"));
+        assert!(examples.path(1).ends_with("resource/examples/day6_1.txt"));
+        assert_eq!("example \"abc\"", Example::from("abc\ndef").to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "part 1 of day 6 on example \"abcd\"")]
+    fn wrongAnswerTest() {
+        Examples::new(YEAR, 6, env!("CARGO_MANIFEST_DIR")).check("part_one", "abcd", 3);
+    }
+}
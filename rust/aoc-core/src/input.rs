@@ -0,0 +1,136 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+const SPELLED: [&str; 25] = [
+    "One", "Two", "Three", "Four", "Five", "Six", "Seven", "Eight", "Nine", "Ten",
+    "Eleven", "Twelve", "Thirteen", "Fourteen", "Fifteen", "Sixteen", "Seventeen", "Eighteen",
+    "Nineteen", "Twenty", "TwentyOne", "TwentyTwo", "TwentyThree", "TwentyFour", "TwentyFive",
+];
+
+/// Names a directory of inputs to use instead of the ones in the repository.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// How a year names its input files, `day15.txt` or `dayFifteen.txt`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Naming {
+    Numeric,
+    Spelled,
+}
+
+impl Naming {
+    pub fn name(&self, day: u8) -> String {
+        match self {
+            Naming::Numeric => day.to_string(),
+            Naming::Spelled => spell(day).to_string(),
+        }
+    }
+}
+
+/// Spells out a day number the way the 2020 and 2021 inputs are named, e.g. `21` => `TwentyOne`.
+pub fn spell(day: u8) -> &'static str {
+    SPELLED[day as usize - 1]
+}
+
+/// Where a year's inputs are. Any of `day5.txt`, `day05.txt` and `dayFive.txt` is found,
+/// whichever way the year itself names them.
+#[derive(Debug, Clone)]
+pub struct Inputs {
+    dirs: Vec<PathBuf>,
+    /// The year's own directory, which keeps its answers.
+    home: PathBuf,
+    naming: Naming,
+}
+
+impl Inputs {
+    pub fn new(dir: impl Into<PathBuf>, naming: Naming) -> Self {
+        let dir = dir.into();
+        Inputs { dirs: vec![dir.clone()], home: dir, naming }
+    }
+
+    /// The inputs of `year` in the directory named by `AOC_INPUT_DIR` when it is set,
+    /// then in `dir`.
+    pub fn locate(year: u16, dir: impl Into<PathBuf>, naming: Naming) -> Self {
+        let inputs = Inputs::new(dir, naming);
+        match env::var_os(INPUT_DIR_VAR) {
+            Some(dir) if !dir.is_empty() => inputs.within(dir, year),
+            _ => inputs,
+        }
+    }
+
+    /// The same inputs looked for first in a subdirectory of `dir` named after the year, then
+    /// in `dir` and then where they were before.
+    pub fn within(&self, dir: impl Into<PathBuf>, year: u16) -> Self {
+        let dir = dir.into();
+        let mut dirs = vec![dir.join(year.to_string()), dir];
+        dirs.extend(self.dirs.iter().cloned());
+        Inputs { dirs, home: self.home.clone(), naming: self.naming }
+    }
+
+    pub fn naming(&self) -> Naming {
+        self.naming
+    }
+
+    /// The input file of `day`. When there is none, where it would be expected.
+    pub fn path(&self, day: u8) -> PathBuf {
+        let names = [self.naming.name(day), format!("{:02}", day), spell(day).to_string(), day.to_string()];
+        self.dirs.iter()
+            .flat_map(|dir| names.iter().map(move |name| dir.join(format!("day{}.txt", name))))
+            .find(|path| path.is_file())
+            .unwrap_or_else(|| self.dir().join(format!("day{}.txt", self.naming.name(day))))
+    }
+
+    pub fn load(&self, day: u8) -> io::Result<String> {
+        let path = self.path(day);
+        fs::read_to_string(&path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+    }
+
+    /// Where the year's known answers are kept, see `Answers`. They stay in the year's own
+    /// directory whichever directory the inputs come from.
+    pub fn answers_path(&self) -> PathBuf {
+        self.home.join("answers.toml")
+    }
+
+    /// The first of the directories that exists.
+    fn dir(&self) -> &PathBuf {
+        self.dirs.iter().find(|dir| dir.is_dir()).unwrap_or(&self.dirs[0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn namingTest() {
+        let inputs = Inputs::new("resource", Naming::Numeric);
+        assert_eq!(PathBuf::from("resource/day15.txt"), inputs.path(15));
+        assert_eq!(PathBuf::from("resource/answers.toml"), inputs.answers_path());
+        let inputs = Inputs::new("resources", Naming::Spelled);
+        assert_eq!(PathBuf::from("resources/dayTwentyOne.txt"), inputs.path(21));
+        assert_eq!(PathBuf::from("resources/dayOne.txt"), inputs.path(1));
+    }
+
+    #[test]
+    fn otherNamingTest() {
+        let dir = env::temp_dir().join("aoc-core-other-naming");
+        fs::create_dir_all(dir.join("2020")).unwrap();
+        fs::write(dir.join("2020").join("day15.txt"), "0,3,6").unwrap();
+        fs::write(dir.join("day04.txt"), "byr:1937").unwrap();
+        let home = dir.join("resources");
+        fs::create_dir_all(&home).unwrap();
+        fs::write(home.join("dayTwo.txt"), "1-3 a: abcde").unwrap();
+
+        let inputs = Inputs::new(&home, Naming::Spelled).within(&dir, 2020);
+        assert_eq!(dir.join("2020").join("day15.txt"), inputs.path(15));
+        assert_eq!("0,3,6", inputs.load(15).unwrap());
+        assert_eq!("byr:1937", inputs.load(4).unwrap());
+        assert_eq!(dir.join("2020").join("dayOne.txt"), inputs.path(1));
+        assert!(inputs.load(1).unwrap_err().to_string().contains("dayOne.txt"));
+        assert_eq!("1-3 a: abcde", inputs.load(2).unwrap());
+        assert_eq!(home.join("answers.toml"), inputs.answers_path());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,51 @@
+#![allow(non_snake_case)]
+
+use std::fmt::Display;
+
+// Lets the code `#[derive(AocDay)]` generates name this crate from within it too.
+extern crate self as aoc_core;
+
+pub mod answers;
+pub mod error;
+pub mod examples;
+pub mod input;
+pub mod lines;
+pub mod output;
+pub mod registry;
+pub mod runner;
+pub mod solution;
+pub mod timing;
+pub mod year;
+
+pub use answers::{Answers, Verdict};
+pub use error::Error;
+pub use input::{Inputs, Naming};
+pub use lines::{parse_lines, BadLine};
+pub use output::{Format, Output, Record};
+pub use runner::{run, run_day, DayReport, PartReport};
+pub use solution::{Part, Puzzle, Solution};
+pub use timing::Timings;
+pub use year::Year;
+pub use aoc_derive::AocDay;
+
+#[doc(hidden)]
+pub use inventory;
+
+pub trait Day {
+    type R: Display;
+
+    fn day() -> String where Self: Sized;
+    fn part_one(&self) -> Self::R;
+    fn part_two(&self) -> Self::R;
+}
+
+/// A `Day` that parses its input up front, through `TryFrom<String>`, and reports
+/// bad input or a missing answer as an error instead of panicking.
+pub trait TryDay {
+    type R: Display;
+    type E: std::error::Error + Send + Sync + 'static;
+
+    fn day() -> String where Self: Sized;
+    fn part_one(&self) -> Result<Self::R, Self::E>;
+    fn part_two(&self) -> Result<Self::R, Self::E>;
+}
@@ -0,0 +1,41 @@
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+/// The `FromStr` error of a line without the expected format, `parse_lines` tells where it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BadLine;
+
+impl From<ParseIntError> for BadLine {
+    fn from(_: ParseIntError) -> Self {
+        BadLine
+    }
+}
+
+/// Parses every non-empty line of `input`, trimmed, failing on the first line that does not
+/// parse with the error `error` makes of the day, the line counting from 1 and its text.
+pub fn parse_lines<T: FromStr, E>(day: u8, input: &str, error: impl Fn(u8, usize, &str) -> E) -> Result<Vec<T>, E> {
+    input.lines()
+        .enumerate()
+        .map(|(i, l)| (i + 1, l.trim()))
+        .filter(|(_line, l)| !l.is_empty())
+        .map(|(line, l)| l.parse().map_err(|_| error(day, line, l)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn located(day: u8, line: usize, text: &str) -> (u8, usize, String) {
+        (day, line, text.to_string())
+    }
+
+    #[test]
+    fn parseLinesTest() {
+        let numbers: Result<Vec<u32>, _> = parse_lines(1, "1\n  2\n\n3", located);
+        assert_eq!(Ok(vec![1, 2, 3]), numbers);
+        let numbers: Result<Vec<u32>, _> = parse_lines(1, "1\n\nthree\n4", located);
+        assert_eq!(Err((1, 3, String::from("three"))), numbers);
+        assert_eq!(Ok(vec![]), parse_lines::<u32, _>(1, "\n  \n", located));
+    }
+}
@@ -0,0 +1,206 @@
+use std::fmt;
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+use crate::{DayReport, Part};
+
+/// How the runner writes its results.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Lines meant to be read, ending with a summary table.
+    #[default]
+    Text,
+    /// A JSON object per part, one per line.
+    Json,
+    /// A header and a line per part.
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            other => Err(format!("unknown format {}, expected text, json or csv", other)),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Format::Text => write!(f, "text"),
+            Format::Json => write!(f, "json"),
+            Format::Csv => write!(f, "csv"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Solved,
+    /// The part has no solution yet.
+    Unsolved,
+    Failed,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Solved => write!(f, "solved"),
+            Status::Unsolved => write!(f, "unsolved"),
+            Status::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+/// The outcome of one part, as written by the JSON and CSV formats.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    /// The median time taken, in nanoseconds.
+    pub duration_ns: Option<u64>,
+    pub status: Status,
+    pub error: Option<String>,
+}
+
+const CSV_HEADER: &str = "year,day,part,answer,duration_ns,status,error";
+
+impl Record {
+    /// A record for each of `parts`, all failed when the day's input could not be read or parsed.
+    pub fn of(report: &DayReport, parts: &[Part]) -> Vec<Record> {
+        parts.iter().map(|&part| {
+            let mut record = Record {
+                year: report.year,
+                day: report.day,
+                part: part.number(),
+                answer: None,
+                duration_ns: None,
+                status: Status::Unsolved,
+                error: None,
+            };
+            let solved = match &report.parts {
+                Ok(solved) => solved.iter().find(|p| p.part == part),
+                Err(e) => {
+                    record.status = Status::Failed;
+                    record.error = Some(e.to_string());
+                    return record;
+                }
+            };
+            if let Some(solved) = solved {
+                match &solved.answer {
+                    Ok(Some(answer)) => {
+                        record.answer = Some(answer.clone());
+                        record.status = Status::Solved;
+                        record.duration_ns = solved.time.median().map(|d| d.as_nanos() as u64);
+                    }
+                    Ok(None) => (),
+                    Err(e) => {
+                        record.status = Status::Failed;
+                        record.error = Some(e.to_string());
+                    }
+                }
+            }
+            record
+        }).collect()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("a record is always valid JSON")
+    }
+
+    /// Reads a line written by `to_json`.
+    pub fn from_json(line: &str) -> Result<Self, String> {
+        serde_json::from_str(line).map_err(|e| e.to_string())
+    }
+
+    pub fn to_csv(&self) -> String {
+        let optional = |value: &Option<String>| value.as_deref().map(csv_field).unwrap_or_default();
+        format!("{},{},{},{},{},{},{}",
+                self.year, self.day, self.part, optional(&self.answer),
+                self.duration_ns.map(|d| d.to_string()).unwrap_or_default(),
+                self.status, optional(&self.error))
+    }
+}
+
+/// Writes reports as they come in the chosen format. Text goes through `DayReport::print`.
+pub struct Output {
+    format: Format,
+    header: bool,
+}
+
+impl Output {
+    pub fn new(format: Format) -> Self {
+        Output { format, header: false }
+    }
+
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    pub fn write(&mut self, report: &DayReport, parts: &[Part]) {
+        match self.format {
+            Format::Text => report.print(),
+            Format::Json => Record::of(report, parts).iter().for_each(|r| println!("{}", r.to_json())),
+            Format::Csv => {
+                if !self.header {
+                    println!("{}", CSV_HEADER);
+                    self.header = true;
+                }
+                Record::of(report, parts).iter().for_each(|r| println!("{}", r.to_csv()));
+            }
+        }
+    }
+}
+
+/// Quotes a field holding a separator, a quote or a line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::solve;
+    use crate::Solution;
+
+    #[test]
+    fn recordTest() {
+        let solution = Solution::functions(2021, 3, |input: &str| input.len(), |_: &str| -> usize { panic!("no, \"answer\"") });
+        let report = solve(&solution, Ok(String::from("abc")), &Part::ALL, 1);
+        let records = Record::of(&report, &Part::ALL);
+        assert_eq!(Status::Solved, records[0].status);
+        assert_eq!(Some(String::from("3")), records[0].answer);
+        assert!(records[0].duration_ns.is_some());
+        assert_eq!(Status::Failed, records[1].status);
+        assert_eq!(Some(String::from("panicked: no, \"answer\"")), records[1].error);
+
+        let json = records[1].to_json();
+        assert!(json.starts_with("{\"year\":2021,\"day\":3,\"part\":2,\"answer\":null,"));
+        assert!(json.ends_with("\"status\":\"failed\",\"error\":\"panicked: no, \\\"answer\\\"\"}"));
+        assert_eq!("2021,3,2,,,failed,\"panicked: no, \"\"answer\"\"\"", records[1].to_csv());
+        assert_eq!(Ok(records[1].clone()), Record::from_json(&json));
+        assert!(Record::from_json("Day 3, part 2").is_err());
+
+        let report = solve(&solution, Err(std::io::Error::from(std::io::ErrorKind::NotFound)), &[Part::Two], 1);
+        let records = Record::of(&report, &[Part::Two]);
+        assert_eq!(1, records.len());
+        assert_eq!(Status::Failed, records[0].status);
+    }
+
+    #[test]
+    fn csvFieldTest() {
+        assert_eq!("TLFGBZHCN", csv_field("TLFGBZHCN"));
+        assert_eq!("\"#..#\n.##.\"", csv_field("#..#\n.##."));
+    }
+}
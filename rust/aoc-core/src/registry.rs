@@ -0,0 +1,189 @@
+use crate::{Inputs, Solution, Year};
+
+/// A solution submitted with `register!`, collected from every linked crate at startup.
+pub struct Registration {
+    solution: fn() -> Solution,
+}
+
+impl Registration {
+    pub const fn new(solution: fn() -> Solution) -> Self {
+        Registration { solution }
+    }
+}
+
+inventory::collect!(Registration);
+
+/// Where a year keeps its inputs and its code, submitted with `register_year!`.
+pub struct YearRegistration {
+    year: u16,
+    inputs: fn() -> Inputs,
+    dir: &'static str,
+}
+
+impl YearRegistration {
+    pub const fn new(year: u16, inputs: fn() -> Inputs, dir: &'static str) -> Self {
+        YearRegistration { year, inputs, dir }
+    }
+}
+
+inventory::collect!(YearRegistration);
+
+/// Every registered solution of `year`, ordered by day.
+pub fn solutions(year: u16) -> Vec<Solution> {
+    let mut solutions: Vec<Solution> = inventory::iter::<Registration>.into_iter()
+        .map(|r| (r.solution)())
+        .filter(|s| s.year() == year)
+        .collect();
+    solutions.sort_by_key(|s| s.day());
+    solutions
+}
+
+/// Every registered year with its solutions, ordered by year.
+pub fn years() -> Vec<Year> {
+    let mut years: Vec<Year> = inventory::iter::<YearRegistration>.into_iter()
+        .map(|r| Year::new(r.year, solutions(r.year), (r.inputs)(), r.dir))
+        .collect();
+    years.sort_by_key(|y| y.year());
+    years
+}
+
+/// Registers a solution with the runner. Takes either a year and a type implementing
+/// `Day + From<String>`, or any expression building a `Solution`.
+#[macro_export]
+macro_rules! register {
+    ($year:expr, $day:ty) => {
+        $crate::inventory::submit! {
+            $crate::registry::Registration::new(|| $crate::Solution::of::<$day>($year))
+        }
+    };
+    ($solution:expr) => {
+        $crate::inventory::submit! {
+            $crate::registry::Registration::new(|| $solution)
+        }
+    };
+}
+
+/// Registers the function telling the runner where `year` keeps its inputs, along with
+/// the crate the year is written in.
+#[macro_export]
+macro_rules! register_year {
+    ($year:expr, $inputs:expr) => {
+        $crate::inventory::submit! {
+            $crate::registry::YearRegistration::new($year, $inputs, env!("CARGO_MANIFEST_DIR"))
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AocDay, Day, Naming, Part, TryDay};
+
+    struct Length {
+        input: String
+    }
+
+    impl From<String> for Length {
+        fn from(input: String) -> Self {
+            Length { input }
+        }
+    }
+
+    impl Day for Length {
+        type R = usize;
+
+        fn day() -> String {
+            String::from("2")
+        }
+
+        fn part_one(&self) -> Self::R {
+            self.input.len()
+        }
+
+        fn part_two(&self) -> Self::R {
+            self.input.len() * 2
+        }
+    }
+
+    #[derive(AocDay)]
+    #[aoc(year = DERIVED, day = 3, answer = usize)]
+    struct Words {
+        input: String
+    }
+
+    impl Words {
+        fn part_one(&self) -> usize {
+            self.input.split_whitespace().count()
+        }
+
+        fn part_two(&self) -> usize {
+            self.input.split_whitespace().map(|w| w.len()).max().unwrap_or(0)
+        }
+    }
+
+    #[derive(AocDay)]
+    #[aoc(year = DERIVED, day = 4, answer = u32, error = std::num::ParseIntError, parse = Self::parse)]
+    struct Numbers {
+        numbers: Vec<u32>
+    }
+
+    impl Numbers {
+        fn parse(input: &str) -> Result<Self, std::num::ParseIntError> {
+            let numbers = input.split(',').map(|n| n.trim().parse()).collect::<Result<_, _>>()?;
+            Ok(Numbers { numbers })
+        }
+
+        fn part_one(&self) -> Result<u32, std::num::ParseIntError> {
+            Ok(self.numbers.iter().sum())
+        }
+
+        fn part_two(&self) -> Result<u32, std::num::ParseIntError> {
+            "many".parse()
+        }
+    }
+
+    fn lines(input: &str) -> usize {
+        input.lines().count()
+    }
+
+    const YEAR: u16 = 1999;
+    const DERIVED: u16 = 1998;
+
+    crate::register!(YEAR, Length);
+    crate::register!(Solution::part_one_only(YEAR, 1, lines));
+    crate::register_year!(YEAR, || Inputs::new("resource", Naming::Numeric));
+
+    #[test]
+    fn registeredSolutionsTest() {
+        let registered = solutions(YEAR);
+        assert_eq!(vec![1, 2], registered.iter().map(|s| s.day()).collect::<Vec<u8>>());
+        let puzzle = registered[1].load(String::from("abcd")).unwrap();
+        assert_eq!(Some(String::from("8")), puzzle.solve(Part::Two).unwrap());
+        assert!(solutions(2000).is_empty());
+    }
+
+    #[test]
+    fn derivedDayTest() {
+        let words = Words::from(String::from("derive the boilerplate"));
+        assert_eq!(3, Day::part_one(&words));
+        assert_eq!(11, Day::part_two(&words));
+        assert_eq!("3", <Words as Day>::day());
+        assert_eq!("4", <Numbers as TryDay>::day());
+        assert!(Numbers::try_from(String::from("1,x")).is_err());
+
+        let registered = solutions(DERIVED);
+        assert_eq!(vec![3, 4], registered.iter().map(|s| s.day()).collect::<Vec<u8>>());
+        let puzzle = registered[1].load(String::from("1, 2, 3")).unwrap();
+        assert_eq!(Some(String::from("6")), puzzle.solve(Part::One).unwrap());
+        assert!(matches!(puzzle.solve(Part::Two), Err(crate::Error::Solve(_))));
+        assert!(registered[1].load(String::from("1,x")).is_err());
+    }
+
+    #[test]
+    fn registeredYearsTest() {
+        let year = years().into_iter().find(|y| y.year() == YEAR).unwrap();
+        assert_eq!(2, year.solutions().len());
+        assert!(year.solution(2).is_some());
+        assert_eq!(std::path::Path::new(env!("CARGO_MANIFEST_DIR")), year.dir());
+    }
+}
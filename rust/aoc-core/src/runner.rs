@@ -0,0 +1,289 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::Duration;
+use crate::timing::{Elapsed, Timings};
+use crate::{Error, Inputs, Part, Solution};
+
+/// The answer to one part and how long it took to find it.
+#[derive(Debug)]
+pub struct PartReport {
+    pub part: Part,
+    /// `None` for a part that has no solution yet.
+    pub answer: Result<Option<String>, Error>,
+    pub time: Timings,
+}
+
+/// Everything that happened while solving one day.
+#[derive(Debug)]
+pub struct DayReport {
+    pub year: u16,
+    pub day: u8,
+    pub parse: Timings,
+    /// An error when the input could not be read or parsed.
+    pub parts: Result<Vec<PartReport>, Error>,
+}
+
+impl DayReport {
+    /// Whether the input was parsed and none of the parts failed.
+    pub fn solved(&self) -> bool {
+        match &self.parts {
+            Ok(parts) => parts.iter().all(|p| p.answer.is_ok()),
+            Err(_) => false,
+        }
+    }
+
+    /// The answer to `part`, `None` when it has no solution yet or was not solved.
+    pub fn answer(&self, part: Part) -> Result<Option<&str>, &Error> {
+        let parts = self.parts.as_ref()?;
+        match parts.iter().find(|p| p.part == part) {
+            Some(p) => p.answer.as_ref().map(|a| a.as_deref()),
+            None => Ok(None),
+        }
+    }
+
+    pub fn print(&self) {
+        println!("---------------------------------------");
+        let parts = match &self.parts {
+            Ok(parts) => parts,
+            Err(e) => {
+                println!("Day {} failed: {}", self.day, e);
+                return;
+            }
+        };
+        println!("Day {}, parse: {}", self.day, self.parse);
+        for part in parts {
+            match &part.answer {
+                Ok(Some(answer)) => println!("Day {}, part {}: {} ({})", self.day, part.part, answer, part.time),
+                Ok(None) => println!("Day {}, part {}: not solved yet", self.day, part.part),
+                Err(e) => println!("Day {}, part {} failed: {}", self.day, part.part, e),
+            }
+        }
+    }
+}
+
+/// Parses the input and solves the given parts `runs` times, keeping the answers of the
+/// first run. A part that fails or has no solution yet is only tried once. A panicking
+/// solution is reported like any other failure.
+pub fn solve(solution: &Solution, input: io::Result<String>, parts: &[Part], runs: usize) -> DayReport {
+    let mut report = DayReport {
+        year: solution.year(),
+        day: solution.day(),
+        parse: Timings::default(),
+        parts: Ok(vec![]),
+    };
+    let input = match input {
+        Ok(input) => input,
+        Err(e) => {
+            report.parts = Err(Error::Input(e));
+            return report;
+        }
+    };
+    let mut reports: Vec<PartReport> = parts.iter()
+        .map(|part| PartReport { part: *part, answer: Ok(None), time: Timings::default() })
+        .collect();
+    for run in 0..runs.max(1) {
+        let input = input.clone();
+        let puzzle = match report.parse.time(|| catch(|| solution.load(input))) {
+            Ok(puzzle) => puzzle,
+            Err(e) => {
+                report.parts = Err(e);
+                return report;
+            }
+        };
+        for part in reports.iter_mut().filter(|p| run == 0 || matches!(p.answer, Ok(Some(_)))) {
+            let answer = part.time.time(|| catch(|| puzzle.solve(part.part)));
+            if run == 0 {
+                part.answer = answer;
+            }
+        }
+    }
+    report.parts = Ok(reports);
+    report
+}
+
+/// Like `solve`, but gives up on a day that takes longer than `timeout`. A solution cannot
+/// be stopped, so it is left running on its own thread until the program exits.
+pub fn solve_within(solution: &Solution, input: io::Result<String>, parts: &[Part], runs: usize,
+                    timeout: Option<Duration>) -> DayReport {
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return solve(solution, input, parts, runs),
+    };
+    let (sender, receiver) = mpsc::channel();
+    let (owned, parts_owned) = (solution.clone(), parts.to_vec());
+    thread::spawn(move || sender.send(solve(&owned, input, &parts_owned, runs)));
+    receiver.recv_timeout(timeout).unwrap_or_else(|_| DayReport {
+        year: solution.year(),
+        day: solution.day(),
+        parse: Timings::default(),
+        parts: Err(Error::Timeout(timeout)),
+    })
+}
+
+/// Solves the days on up to `jobs` threads at once and hands each report to `done` in the
+/// order of `days`, as soon as it and all the days before it are solved.
+pub fn solve_all(days: Vec<(&Solution, io::Result<String>)>, parts: &[Part], runs: usize,
+                 timeout: Option<Duration>, jobs: usize, mut done: impl FnMut(DayReport)) {
+    let count = days.len();
+    let queue = Mutex::new(days.into_iter().enumerate());
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, count.max(1)) {
+            let (queue, sender) = (&queue, sender.clone());
+            scope.spawn(move || loop {
+                let next = queue.lock().unwrap().next();
+                let Some((index, (solution, input))) = next else { break };
+                let report = solve_within(solution, input, parts, runs, timeout);
+                if sender.send((index, report)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+        let mut solved = BTreeMap::new();
+        let mut next = 0;
+        for (index, report) in receiver {
+            solved.insert(index, report);
+            while let Some(report) = solved.remove(&next) {
+                done(report);
+                next += 1;
+            }
+        }
+    });
+}
+
+/// Solves every day once, reporting the days that fail and carrying on with the rest,
+/// and ends with a summary table. Returns whether every day was solved.
+pub fn run(solutions: &[Solution], inputs: &Inputs) -> bool {
+    let reports: Vec<DayReport> = solutions.iter()
+        .map(|solution| run_day(solution, inputs.load(solution.day()), &Part::ALL, 1))
+        .collect();
+    if let Some(first) = reports.first() {
+        print_summary(first.year, &reports);
+    }
+    reports.iter().all(|r| r.solved())
+}
+
+/// Solves and prints one day.
+pub fn run_day(solution: &Solution, input: io::Result<String>, parts: &[Part], runs: usize) -> DayReport {
+    let report = solve(solution, input, parts, runs);
+    report.print();
+    report
+}
+
+/// Prints the median times of every day of a year in a table.
+pub fn print_summary(year: u16, reports: &[DayReport]) {
+    println!("=======================================");
+    println!("Advent of Code {} summary", year);
+    println!("{:>5} {:>10} {:>10} {:>10} {:>10}", "Day", "Parse", "Part 1", "Part 2", "Total");
+    let mut total = Duration::ZERO;
+    for report in reports {
+        let day_total = report_total(report);
+        total += day_total;
+        let parse = match &report.parts {
+            Ok(_) => cell(report.parse.median()),
+            Err(_) => String::from("failed"),
+        };
+        println!("{:>5} {:>10} {:>10} {:>10} {:>10}",
+                 report.day, parse, part_cell(report, Part::One), part_cell(report, Part::Two), Elapsed(day_total));
+    }
+    println!("{:>5} {:>10} {:>10} {:>10} {:>10}", "Total", "", "", "", Elapsed(total));
+}
+
+fn report_total(report: &DayReport) -> Duration {
+    let parts: Duration = match &report.parts {
+        Ok(parts) => parts.iter()
+            .filter(|p| matches!(p.answer, Ok(Some(_))))
+            .filter_map(|p| p.time.median())
+            .sum(),
+        Err(_) => Duration::ZERO,
+    };
+    report.parse.median().unwrap_or_default() + parts
+}
+
+fn part_cell(report: &DayReport, part: Part) -> String {
+    let parts = match &report.parts {
+        Ok(parts) => parts,
+        Err(_) => return String::from("-"),
+    };
+    match parts.iter().find(|p| p.part == part) {
+        Some(PartReport { answer: Ok(Some(_)), time, .. }) => cell(time.median()),
+        Some(PartReport { answer: Err(_), .. }) => String::from("failed"),
+        _ => String::from("-"),
+    }
+}
+
+fn cell(duration: Option<Duration>) -> String {
+    duration.map_or(String::from("-"), |d| Elapsed(d).to_string())
+}
+
+fn catch<T>(f: impl FnOnce() -> Result<T, Error>) -> Result<T, Error> {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| Err(Error::panic(payload)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parts(report: &DayReport) -> &[PartReport] {
+        report.parts.as_ref().unwrap()
+    }
+
+    #[test]
+    fn solveTest() {
+        let solution = Solution::functions(2021, 1, |input: &str| input.len(), |_: &str| -> usize { panic!("no answer") });
+        let report = solve(&solution, Ok(String::from("abc")), &Part::ALL, 3);
+        assert_eq!(3, report.parse.runs());
+        assert_eq!(Some(String::from("3")), *parts(&report)[0].answer.as_ref().unwrap());
+        assert_eq!(3, parts(&report)[0].time.runs());
+        assert!(matches!(parts(&report)[1].answer, Err(Error::Panic(_))));
+        assert_eq!(1, parts(&report)[1].time.runs());
+        assert!(!report.solved());
+
+        assert_eq!(Some("3"), report.answer(Part::One).unwrap());
+
+        let report = solve(&solution, Ok(String::from("abc")), &[Part::One], 1);
+        assert_eq!(1, parts(&report).len());
+        assert!(report.solved());
+        assert_eq!(None, report.answer(Part::Two).unwrap());
+    }
+
+    #[test]
+    fn timeoutTest() {
+        let solution = Solution::part_one_only(2021, 9, |_: &str| -> usize { loop { thread::park() } });
+        let report = solve_within(&solution, Ok(String::new()), &Part::ALL, 1, Some(Duration::from_millis(50)));
+        assert!(matches!(report.parts, Err(Error::Timeout(_))));
+        assert_eq!(9, report.day);
+
+        let solution = Solution::part_one_only(2021, 9, |input: &str| input.len());
+        let report = solve_within(&solution, Ok(String::from("ab")), &Part::ALL, 1, Some(Duration::from_secs(5)));
+        assert_eq!(Some("2"), report.answer(Part::One).unwrap());
+    }
+
+    #[test]
+    fn solveAllInOrderTest() {
+        let slow = Solution::part_one_only(2021, 1, |input: &str| {
+            thread::sleep(Duration::from_millis(100));
+            input.len()
+        });
+        let fast = Solution::part_one_only(2021, 2, |input: &str| input.len() * 2);
+        let days = vec![(&slow, Ok(String::from("abc"))), (&fast, Ok(String::from("abc")))];
+        let mut reports = vec![];
+        solve_all(days, &[Part::One], 1, None, 4, |report| reports.push(report));
+        assert_eq!(vec![1, 2], reports.iter().map(|r| r.day).collect::<Vec<u8>>());
+        assert_eq!(Some("6"), reports[1].answer(Part::One).unwrap());
+    }
+
+    #[test]
+    fn unreadableInputTest() {
+        let solution = Solution::part_one_only(2021, 8, |input: &str| input.len());
+        let report = solve(&solution, Err(io::Error::from(io::ErrorKind::NotFound)), &Part::ALL, 1);
+        assert!(matches!(report.parts, Err(Error::Input(_))));
+        assert!(matches!(report.answer(Part::One), Err(Error::Input(_))));
+        assert_eq!(0, report.parse.runs());
+        assert_eq!("-", part_cell(&report, Part::One));
+    }
+}
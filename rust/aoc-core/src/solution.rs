@@ -0,0 +1,309 @@
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::Arc;
+use crate::{Day, Error, TryDay};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            other => Err(format!("there is no part {}, expected 1 or 2", other)),
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// A puzzle with its input loaded, ready to be solved.
+pub trait Puzzle {
+    /// Returns `None` for a part that has no solution yet.
+    fn solve(&self, part: Part) -> Result<Option<String>, Error>;
+}
+
+impl<D: Day> Puzzle for D {
+    fn solve(&self, part: Part) -> Result<Option<String>, Error> {
+        match part {
+            Part::One => Ok(Some(self.part_one().to_string())),
+            Part::Two => Ok(Some(self.part_two().to_string())),
+        }
+    }
+}
+
+/// A `TryDay` that has parsed its input.
+struct Parsed<D>(D);
+
+impl<D: TryDay> Puzzle for Parsed<D> {
+    fn solve(&self, part: Part) -> Result<Option<String>, Error> {
+        let answer = match part {
+            Part::One => self.0.part_one(),
+            Part::Two => self.0.part_two(),
+        };
+        answer.map(|answer| Some(answer.to_string())).map_err(|e| Error::Solve(Box::new(e)))
+    }
+}
+
+/// Solutions written as free `part_one(&str)`/`part_two(&str)` functions.
+struct Functions<A, B> {
+    input: String,
+    part_one: fn(&str) -> A,
+    part_two: Option<fn(&str) -> B>,
+}
+
+impl<A: Display, B: Display> Puzzle for Functions<A, B> {
+    fn solve(&self, part: Part) -> Result<Option<String>, Error> {
+        match part {
+            Part::One => Ok(Some((self.part_one)(&self.input).to_string())),
+            Part::Two => Ok(self.part_two.map(|f| f(&self.input).to_string())),
+        }
+    }
+}
+
+type TryPart<T, E> = fn(&str) -> Result<T, E>;
+
+/// Like `Functions`, for parts that can reject the input they parse.
+struct TryFunctions<A, B, E> {
+    input: String,
+    part_one: TryPart<A, E>,
+    part_two: Option<TryPart<B, E>>,
+}
+
+impl<A, B, E> Puzzle for TryFunctions<A, B, E>
+    where A: Display, B: Display, E: std::error::Error + Send + Sync + 'static {
+    fn solve(&self, part: Part) -> Result<Option<String>, Error> {
+        let answer = match part {
+            Part::One => (self.part_one)(&self.input).map(|answer| Some(answer.to_string())),
+            Part::Two => self.part_two.map_or(Ok(None), |f| f(&self.input).map(|answer| Some(answer.to_string()))),
+        };
+        answer.map_err(|e| Error::Solve(Box::new(e)))
+    }
+}
+
+type Load = dyn Fn(String) -> Result<Box<dyn Puzzle>, Error> + Send + Sync;
+
+/// One day of one year, independent of how that year's solutions are written. Cheap to
+/// clone and can be handed to another thread.
+#[derive(Clone)]
+pub struct Solution {
+    year: u16,
+    day: u8,
+    load: Arc<Load>,
+}
+
+impl Solution {
+    pub fn new<P, F>(year: u16, day: u8, load: F) -> Self
+        where P: Puzzle + 'static, F: Fn(String) -> P + Send + Sync + 'static {
+        Solution { year, day, load: Arc::new(move |input| Ok(Box::new(load(input)))) }
+    }
+
+    /// Like `new`, for a load that can reject its input.
+    pub fn try_new<P, E, F>(year: u16, day: u8, load: F) -> Self
+        where P: Puzzle + 'static, E: std::error::Error + Send + Sync + 'static,
+              F: Fn(String) -> Result<P, E> + Send + Sync + 'static {
+        let load = move |input| match load(input) {
+            Ok(puzzle) => Ok(Box::new(puzzle) as Box<dyn Puzzle>),
+            Err(e) => Err(Error::Parse(Box::new(e))),
+        };
+        Solution { year, day, load: Arc::new(load) }
+    }
+
+    pub fn of<D>(year: u16) -> Self where D: Day + From<String> + 'static {
+        Solution::new(year, day_number(&D::day()), D::from)
+    }
+
+    pub fn try_of<D>(year: u16) -> Self where D: TryDay + TryFrom<String, Error = D::E> + 'static {
+        let load = |input| match D::try_from(input) {
+            Ok(day) => Ok(Box::new(Parsed(day)) as Box<dyn Puzzle>),
+            Err(e) => Err(Error::Parse(Box::new(e))),
+        };
+        Solution { year, day: day_number(&D::day()), load: Arc::new(load) }
+    }
+
+    pub fn functions<A, B>(year: u16, day: u8, part_one: fn(&str) -> A, part_two: fn(&str) -> B) -> Self
+        where A: Display + 'static, B: Display + 'static {
+        Solution::new(year, day, move |input| Functions { input, part_one, part_two: Some(part_two) })
+    }
+
+    pub fn try_functions<A, B, E>(year: u16, day: u8, part_one: fn(&str) -> Result<A, E>, part_two: fn(&str) -> Result<B, E>) -> Self
+        where A: Display + 'static, B: Display + 'static, E: std::error::Error + Send + Sync + 'static {
+        Solution::new(year, day, move |input| TryFunctions { input, part_one, part_two: Some(part_two) })
+    }
+
+    pub fn part_one_only<A>(year: u16, day: u8, part_one: fn(&str) -> A) -> Self
+        where A: Display + 'static {
+        Solution::new(year, day, move |input| Functions::<A, A> { input, part_one, part_two: None })
+    }
+
+    pub fn try_part_one_only<A, E>(year: u16, day: u8, part_one: fn(&str) -> Result<A, E>) -> Self
+        where A: Display + 'static, E: std::error::Error + Send + Sync + 'static {
+        Solution::new(year, day, move |input| TryFunctions::<A, A, E> { input, part_one, part_two: None })
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    pub fn load(&self, input: String) -> Result<Box<dyn Puzzle>, Error> {
+        (self.load)(input)
+    }
+}
+
+fn day_number(day: &str) -> u8 {
+    day.parse().expect("Day::day() should be the day number")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::num::ParseIntError;
+
+    struct Echo {
+        input: String
+    }
+
+    impl From<String> for Echo {
+        fn from(input: String) -> Self {
+            Echo { input }
+        }
+    }
+
+    impl Day for Echo {
+        type R = usize;
+
+        fn day() -> String {
+            String::from("7")
+        }
+
+        fn part_one(&self) -> Self::R {
+            self.input.len()
+        }
+
+        fn part_two(&self) -> Self::R {
+            self.input.lines().count()
+        }
+    }
+
+    struct Numbers {
+        numbers: Vec<u32>
+    }
+
+    impl TryFrom<String> for Numbers {
+        type Error = ParseIntError;
+
+        fn try_from(input: String) -> Result<Self, Self::Error> {
+            let numbers = input.lines().map(|l| l.parse()).collect::<Result<_, _>>()?;
+            Ok(Numbers { numbers })
+        }
+    }
+
+    impl TryDay for Numbers {
+        type R = u32;
+        type E = ParseIntError;
+
+        fn day() -> String {
+            String::from("1")
+        }
+
+        fn part_one(&self) -> Result<Self::R, Self::E> {
+            Ok(self.numbers.iter().sum())
+        }
+
+        fn part_two(&self) -> Result<Self::R, Self::E> {
+            "max".parse()
+        }
+    }
+
+    fn count(input: &str) -> usize {
+        input.len()
+    }
+
+    #[test]
+    fn parsePartTest() {
+        assert_eq!(Ok(Part::One), "1".parse());
+        assert_eq!(Ok(Part::Two), "2".parse());
+        assert!("3".parse::<Part>().is_err());
+    }
+
+    #[test]
+    fn dayAdapterTest() {
+        let solution = Solution::of::<Echo>(2022);
+        assert_eq!(2022, solution.year());
+        assert_eq!(7, solution.day());
+        let puzzle = solution.load(String::from("ab\ncd")).unwrap();
+        assert_eq!(Some(String::from("5")), puzzle.solve(Part::One).unwrap());
+        assert_eq!(Some(String::from("2")), puzzle.solve(Part::Two).unwrap());
+    }
+
+    #[test]
+    fn functionsAdapterTest() {
+        let puzzle = Solution::functions(2021, 1, count, |input| input.is_empty())
+            .load(String::from("abc")).unwrap();
+        assert_eq!(Some(String::from("3")), puzzle.solve(Part::One).unwrap());
+        assert_eq!(Some(String::from("false")), puzzle.solve(Part::Two).unwrap());
+
+        let puzzle = Solution::part_one_only(2021, 8, count).load(String::from("abc")).unwrap();
+        assert_eq!(Some(String::from("3")), puzzle.solve(Part::One).unwrap());
+        assert_eq!(None, puzzle.solve(Part::Two).unwrap());
+    }
+
+    fn sum(input: &str) -> Result<u32, ParseIntError> {
+        input.lines().map(|l| l.parse::<u32>()).sum()
+    }
+
+    #[test]
+    fn tryFunctionsAdapterTest() {
+        let puzzle = Solution::try_functions(2021, 2, sum, |input| input.parse::<u32>())
+            .load(String::from("1\n2")).unwrap();
+        assert_eq!(Some(String::from("3")), puzzle.solve(Part::One).unwrap());
+        assert!(matches!(puzzle.solve(Part::Two), Err(Error::Solve(_))));
+
+        let puzzle = Solution::try_part_one_only(2021, 21, sum).load(String::from("1\n2")).unwrap();
+        assert_eq!(None, puzzle.solve(Part::Two).unwrap());
+    }
+
+    #[test]
+    fn tryNewAdapterTest() {
+        let solution = Solution::try_new(2020, 9, |input: String| sum(&input).map(|_| Echo { input }));
+        let puzzle = solution.load(String::from("1\n2")).unwrap();
+        assert_eq!(Some(String::from("3")), puzzle.solve(Part::One).unwrap());
+        assert!(matches!(solution.load(String::from("1\ntwo")), Err(Error::Parse(_))));
+    }
+
+    #[test]
+    fn tryDayAdapterTest() {
+        let solution = Solution::try_of::<Numbers>(2015);
+        assert_eq!(1, solution.day());
+        let puzzle = solution.load(String::from("1\n2\n3")).unwrap();
+        assert_eq!(Some(String::from("6")), puzzle.solve(Part::One).unwrap());
+        assert!(matches!(puzzle.solve(Part::Two), Err(Error::Solve(_))));
+        assert!(matches!(solution.load(String::from("1\ntwo")), Err(Error::Parse(_))));
+    }
+}
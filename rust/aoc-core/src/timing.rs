@@ -0,0 +1,111 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// How long something took over one or more runs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Timings {
+    runs: Vec<Duration>,
+}
+
+impl Timings {
+    /// Runs `f` and records how long it took.
+    pub fn time<T>(&mut self, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = f();
+        self.runs.push(start.elapsed());
+        result
+    }
+
+    pub fn push(&mut self, duration: Duration) {
+        self.runs.push(duration);
+    }
+
+    pub fn runs(&self) -> usize {
+        self.runs.len()
+    }
+
+    pub fn min(&self) -> Option<Duration> {
+        self.runs.iter().min().copied()
+    }
+
+    pub fn max(&self) -> Option<Duration> {
+        self.runs.iter().max().copied()
+    }
+
+    /// The middle run, or the mean of the two middle runs for an even number of runs.
+    pub fn median(&self) -> Option<Duration> {
+        let mut sorted = self.runs.clone();
+        sorted.sort();
+        let mid = sorted.len() / 2;
+        match sorted.len() {
+            0 => None,
+            n if n % 2 == 1 => Some(sorted[mid]),
+            _ => Some((sorted[mid - 1] + sorted[mid]) / 2),
+        }
+    }
+}
+
+impl fmt::Display for Timings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.min(), self.median(), self.max()) {
+            (Some(min), Some(median), Some(max)) if self.runs() > 1 => write!(
+                f, "min {}, median {}, max {} over {} runs",
+                Elapsed(min), Elapsed(median), Elapsed(max), self.runs()
+            ),
+            (_, Some(median), _) => write!(f, "{}", Elapsed(median)),
+            _ => write!(f, "-"),
+        }
+    }
+}
+
+/// A duration printed in the largest unit that keeps it above one, e.g. `1.5ms`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elapsed(pub Duration);
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nanos = self.0.as_nanos();
+        let text = if nanos < 1_000 {
+            format!("{}ns", nanos)
+        } else if nanos < 1_000_000 {
+            format!("{:.1}µs", nanos as f64 / 1e3)
+        } else if nanos < 1_000_000_000 {
+            format!("{:.1}ms", nanos as f64 / 1e6)
+        } else {
+            format!("{:.2}s", nanos as f64 / 1e9)
+        };
+        f.pad(&text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timings(millis: &[u64]) -> Timings {
+        let mut timings = Timings::default();
+        millis.iter().for_each(|m| timings.push(Duration::from_millis(*m)));
+        timings
+    }
+
+    #[test]
+    fn statisticsTest() {
+        let odd = timings(&[5, 1, 3]);
+        assert_eq!(Some(Duration::from_millis(1)), odd.min());
+        assert_eq!(Some(Duration::from_millis(3)), odd.median());
+        assert_eq!(Some(Duration::from_millis(5)), odd.max());
+        assert_eq!(Some(Duration::from_millis(3)), timings(&[4, 1, 2, 8]).median());
+        assert_eq!(None, Timings::default().median());
+    }
+
+    #[test]
+    fn displayTest() {
+        assert_eq!("512ns", Elapsed(Duration::from_nanos(512)).to_string());
+        assert_eq!("12.3µs", Elapsed(Duration::from_nanos(12_340)).to_string());
+        assert_eq!("1.5ms", Elapsed(Duration::from_micros(1_500)).to_string());
+        assert_eq!("2.25s", Elapsed(Duration::from_millis(2_250)).to_string());
+        assert_eq!("3.0ms", timings(&[3]).to_string());
+        assert_eq!("min 1.0ms, median 3.0ms, max 5.0ms over 3 runs", timings(&[5, 1, 3]).to_string());
+        assert_eq!("-", Timings::default().to_string());
+    }
+}
@@ -0,0 +1,37 @@
+use std::path::{Path, PathBuf};
+use crate::{Inputs, Solution};
+
+/// All solutions of one year together with where that year keeps its inputs.
+pub struct Year {
+    year: u16,
+    solutions: Vec<Solution>,
+    inputs: Inputs,
+    dir: PathBuf,
+}
+
+impl Year {
+    pub fn new(year: u16, solutions: Vec<Solution>, inputs: Inputs, dir: impl Into<PathBuf>) -> Self {
+        Year { year, solutions, inputs, dir: dir.into() }
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn solutions(&self) -> &[Solution] {
+        &self.solutions
+    }
+
+    pub fn solution(&self, day: u8) -> Option<&Solution> {
+        self.solutions.iter().find(|s| s.day() == day)
+    }
+
+    pub fn inputs(&self) -> &Inputs {
+        &self.inputs
+    }
+
+    /// The directory of the crate the year is written in.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
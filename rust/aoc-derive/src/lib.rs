@@ -0,0 +1,167 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Expr, Fields, Ident, LitInt, Path, Type};
+
+/// Implements `Day` for a struct holding a day's input, along with `From<String>`, and
+/// registers it with the runner:
+///
+/// ```ignore
+/// #[derive(AocDay)]
+/// #[aoc(day = 4, answer = u64)]
+/// pub struct DayFour {
+///     input: String
+/// }
+///
+/// impl DayFour {
+///     fn part_one(&self) -> u64 { ... }
+///     fn part_two(&self) -> u64 { ... }
+/// }
+/// ```
+///
+/// `Day::part_one` and `Day::part_two` call the struct's own `part_one` and `part_two`.
+///
+/// * `day`: the day number.
+/// * `answer`: the type of the answers.
+/// * `year`: the year to register the day with, `crate::YEAR` when left out.
+/// * `parse`: a function taking the input as `&str` and returning the struct, for a struct
+///   holding parsed data instead of its single input field.
+/// * `error`: implements `TryDay` and `TryFrom<String>` with this error instead. The parts,
+///   and the `parse` function, then return a `Result`.
+#[proc_macro_derive(AocDay, attributes(aoc))]
+pub fn derive_aoc_day(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+#[derive(Default)]
+struct Attributes {
+    year: Option<Expr>,
+    day: Option<LitInt>,
+    answer: Option<Type>,
+    error: Option<Type>,
+    parse: Option<Path>,
+}
+
+impl Attributes {
+    fn of(input: &DeriveInput) -> syn::Result<Self> {
+        let mut attributes = Attributes::default();
+        for attr in input.attrs.iter().filter(|a| a.path().is_ident("aoc")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("year") {
+                    attributes.year = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("day") {
+                    let day: LitInt = meta.value()?.parse()?;
+                    let number: u8 = day.base10_parse()?;
+                    if !(1..=25).contains(&number) {
+                        return Err(syn::Error::new(day.span(), "the day should be 1 to 25"));
+                    }
+                    attributes.day = Some(day);
+                } else if meta.path.is_ident("answer") {
+                    attributes.answer = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("error") {
+                    attributes.error = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("parse") {
+                    attributes.parse = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error("expected year, day, answer, error or parse"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(attributes)
+    }
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let attributes = Attributes::of(&input)?;
+    let day = attributes.day
+        .ok_or_else(|| syn::Error::new(name.span(), "missing #[aoc(day = ...)]"))?;
+    let answer = attributes.answer
+        .ok_or_else(|| syn::Error::new(name.span(), "missing #[aoc(answer = ...)]"))?;
+    let year = attributes.year.unwrap_or_else(|| syn::parse_quote!(crate::YEAR));
+    let day = day.base10_digits();
+    let build = match &attributes.parse {
+        Some(parse) => quote! { #parse(&input) },
+        None => {
+            let field = input_field(&input)?;
+            match attributes.error {
+                Some(_) => quote! { Ok(#name { #field: input }) },
+                None => quote! { #name { #field: input } },
+            }
+        }
+    };
+
+    let tokens = match attributes.error {
+        None => quote! {
+            impl ::core::convert::From<String> for #name {
+                fn from(input: String) -> Self {
+                    #build
+                }
+            }
+
+            impl aoc_core::Day for #name {
+                type R = #answer;
+
+                fn day() -> String {
+                    String::from(#day)
+                }
+
+                fn part_one(&self) -> Self::R {
+                    #name::part_one(self)
+                }
+
+                fn part_two(&self) -> Self::R {
+                    #name::part_two(self)
+                }
+            }
+
+            aoc_core::register!(#year, #name);
+        },
+        Some(error) => quote! {
+            impl ::core::convert::TryFrom<String> for #name {
+                type Error = #error;
+
+                fn try_from(input: String) -> Result<Self, Self::Error> {
+                    #build
+                }
+            }
+
+            impl aoc_core::TryDay for #name {
+                type R = #answer;
+                type E = #error;
+
+                fn day() -> String {
+                    String::from(#day)
+                }
+
+                fn part_one(&self) -> Result<Self::R, Self::E> {
+                    #name::part_one(self)
+                }
+
+                fn part_two(&self) -> Result<Self::R, Self::E> {
+                    #name::part_two(self)
+                }
+            }
+
+            aoc_core::register!(aoc_core::Solution::try_of::<#name>(#year));
+        },
+    };
+    Ok(tokens)
+}
+
+/// The one field of a struct that keeps its input as it is.
+fn input_field(input: &DeriveInput) -> syn::Result<&Ident> {
+    let message = "#[derive(AocDay)] needs a struct with a single input field, or #[aoc(parse = ...)]";
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) if fields.named.len() == 1 => Ok(fields.named[0].ident.as_ref().unwrap()),
+            _ => Err(syn::Error::new(input.ident.span(), message)),
+        },
+        _ => Err(syn::Error::new(input.ident.span(), message)),
+    }
+}
@@ -0,0 +1,204 @@
+use std::collections::{BTreeSet, HashMap};
+use std::hash::Hash;
+use crate::grid::{Grid, Pos};
+
+/// Cells that change all at once, one generation after the other, by a `step` from each
+/// generation to the next.
+pub struct Automaton<'a, S> {
+    state: S,
+    generation: usize,
+    step: Box<dyn Fn(&S) -> S + 'a>,
+}
+
+/// A generation that came back: `start` is the first generation of the cycle and every
+/// generation `length` after it is the same.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The generation of the first pass through the cycle that is the same as `generation`.
+    pub fn equivalent(&self, generation: usize) -> usize {
+        match generation < self.start {
+            true => generation,
+            false => self.start + (generation - self.start) % self.length,
+        }
+    }
+}
+
+impl<'a, S> Automaton<'a, S> {
+    pub fn new(state: S, step: impl Fn(&S) -> S + 'a) -> Self {
+        Automaton { state, generation: 0, step: Box::new(step) }
+    }
+
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn step(&mut self) -> &S {
+        self.state = (self.step)(&self.state);
+        self.generation += 1;
+        &self.state
+    }
+
+    pub fn run(&mut self, generations: usize) -> &S {
+        for _ in 0..generations {
+            self.step();
+        }
+        &self.state
+    }
+
+    /// Steps until `done` holds, the current generation included, and returns that generation.
+    pub fn run_until(&mut self, mut done: impl FnMut(&S) -> bool) -> usize {
+        while !done(&self.state) {
+            self.step();
+        }
+        self.generation
+    }
+
+    /// Steps until a generation is the same as the one before it and returns that generation.
+    pub fn run_until_stable(&mut self) -> usize where S: PartialEq {
+        loop {
+            let next = (self.step)(&self.state);
+            if next == self.state {
+                return self.generation;
+            }
+            self.state = next;
+            self.generation += 1;
+        }
+    }
+
+    /// Steps until a generation comes back, which is where the automaton is left. Never
+    /// returns when the generations keep on growing.
+    pub fn find_cycle(&mut self) -> Cycle where S: Clone + Eq + Hash {
+        let mut seen: HashMap<S, usize> = HashMap::new();
+        loop {
+            if let Some(&start) = seen.get(&self.state) {
+                return Cycle { start, length: self.generation - start };
+            }
+            seen.insert(self.state.clone(), self.generation);
+            self.step();
+        }
+    }
+}
+
+impl<'a, T: 'a> Automaton<'a, Grid<T>> {
+    /// Every cell of the grid becomes what `rule` makes of it and of the cells in its
+    /// `neighbourhood`.
+    pub fn dense<I>(grid: Grid<T>, neighbourhood: impl Fn(&Grid<T>, Pos) -> I + 'a, rule: impl Fn(&T, &[&T]) -> T + 'a) -> Self
+        where I: IntoIterator<Item = Pos> {
+        Automaton::new(grid, move |grid: &Grid<T>| Grid::from_fn(grid.rows(), grid.cols(), |pos| {
+            let neighbours: Vec<&T> = neighbourhood(grid, pos).into_iter().map(|n| &grid[n]).collect();
+            rule(&grid[pos], &neighbours)
+        }))
+    }
+}
+
+impl<'a, P: Clone + Ord + Hash + 'a> Automaton<'a, BTreeSet<P>> {
+    /// Cells anywhere, of which only the live ones are kept. `rule` tells from whether a cell
+    /// is alive and how many cells in its `neighbourhood` are, whether it lives on.
+    pub fn sparse<I>(live: impl IntoIterator<Item = P>, neighbourhood: impl Fn(&P) -> I + 'a, rule: impl Fn(bool, usize) -> bool + 'a) -> Self
+        where I: IntoIterator<Item = P> {
+        Automaton::new(live.into_iter().collect(), move |live: &BTreeSet<P>| {
+            let mut counts: HashMap<P, usize> = live.iter().map(|cell| (cell.clone(), 0)).collect();
+            for neighbour in live.iter().flat_map(&neighbourhood) {
+                *counts.entry(neighbour).or_default() += 1;
+            }
+            counts.into_iter()
+                .filter(|(cell, count)| rule(live.contains(cell), *count))
+                .map(|(cell, _)| cell)
+                .collect()
+        })
+    }
+}
+
+/// A grid in the middle of a plane that is `background` as far as it goes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Infinite<T> {
+    pub grid: Grid<T>,
+    pub background: T,
+}
+
+impl<T> Infinite<T> {
+    /// The cell at `(row, column)`, which may be outside the grid.
+    pub fn get(&self, (row, col): (isize, isize)) -> &T {
+        match (usize::try_from(row), usize::try_from(col)) {
+            (Ok(row), Ok(col)) => self.grid.get((row, col)).unwrap_or(&self.background),
+            _ => &self.background,
+        }
+    }
+}
+
+impl<'a, T: Clone + 'a> Automaton<'a, Infinite<T>> {
+    /// Every cell becomes what `rule` makes of the cells in its `window`, given as steps from
+    /// the cell. The grid grows by the reach of the window each generation, as far as the
+    /// background can be changed by the grid, and the background becomes what `rule` makes
+    /// of a window of background only.
+    pub fn infinite(grid: Grid<T>, background: T, window: Vec<(isize, isize)>, rule: impl Fn(&[&T]) -> T + 'a) -> Self {
+        let reach = window.iter().map(|&(d_row, d_col)| d_row.unsigned_abs().max(d_col.unsigned_abs())).max().unwrap_or(0);
+        Automaton::new(Infinite { grid, background }, move |plane: &Infinite<T>| {
+            let grid = Grid::from_fn(plane.grid.rows() + 2 * reach, plane.grid.cols() + 2 * reach, |(row, col)| {
+                let (row, col) = (row as isize - reach as isize, col as isize - reach as isize);
+                let cells: Vec<&T> = window.iter().map(|&(d_row, d_col)| plane.get((row + d_row, col + d_col))).collect();
+                rule(&cells)
+            });
+            let background = rule(&vec![&plane.background; window.len()]);
+            Infinite { grid, background }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn life(alive: bool, neighbours: usize) -> bool {
+        neighbours == 3 || alive && neighbours == 2
+    }
+
+    #[test]
+    fn denseTest() {
+        let blinker = Grid::parse("...\n###\n...", |c| Some(c == '#')).unwrap();
+        let mut automaton = Automaton::dense(blinker.clone(), |grid, pos| grid.neighbours8(pos).collect::<Vec<Pos>>(),
+            |&alive, neighbours| life(alive, neighbours.iter().filter(|&&&n| n).count()));
+        assert_eq!(&blinker.transpose(), automaton.step());
+        assert_eq!(Cycle { start: 1, length: 2 }, automaton.find_cycle());
+        assert_eq!(3, automaton.generation());
+        assert_eq!(1, Cycle { start: 1, length: 2 }.equivalent(1_000_000_001));
+
+        let mut counter = Automaton::dense(Grid::new(1, 3, 0), |_, _| vec![], |&n, _| (n + 1).min(5));
+        assert_eq!(5, counter.run_until_stable());
+        assert_eq!(3, Automaton::new(0, |n| n + 1).run_until(|&n| n == 3));
+    }
+
+    #[test]
+    fn sparseTest() {
+        let glider = [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)];
+        let neighbours = |&(row, col): &(i32, i32)| (-1..=1).flat_map(move |i| (-1..=1).map(move |j| (row + i, col + j)))
+            .filter(move |&n| n != (row, col));
+        let mut automaton = Automaton::sparse(glider, neighbours, life);
+        let moved: BTreeSet<(i32, i32)> = glider.iter().map(|&(row, col)| (row + 1, col + 1)).collect();
+        assert_eq!(&moved, automaton.run(4));
+        assert_eq!(5, automaton.state().len());
+    }
+
+    #[test]
+    fn infiniteTest() {
+        let window: Vec<(isize, isize)> = vec![(0, -1), (0, 0), (0, 1)];
+        // a cell is lit when no cell in its window is, so the background keeps flipping
+        let mut automaton = Automaton::infinite(Grid::parse("#", Some).unwrap(), '.', window, |cells| {
+            if cells.iter().all(|&&c| c == '.') { '#' } else { '.' }
+        });
+        assert_eq!("###\n...\n###\n", automaton.step().grid.to_string());
+        assert_eq!('#', automaton.state().background);
+        assert_eq!(".....\n.....\n..#..\n.....\n.....\n", automaton.step().grid.to_string());
+        assert_eq!('.', automaton.state().background);
+        assert_eq!(&'.', automaton.state().get((-3, 7)));
+    }
+}
@@ -0,0 +1,323 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::iter::Peekable;
+use std::vec::IntoIter;
+
+/// A piece of an expression, with the column it starts at, counting from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenKind {
+    Number(i64),
+    Name(String),
+    Operator(char),
+    Open,
+    Close,
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TokenKind::Number(n) => write!(f, "{}", n),
+            TokenKind::Name(name) => write!(f, "{}", name),
+            TokenKind::Operator(op) => write!(f, "{}", op),
+            TokenKind::Open => write!(f, "("),
+            TokenKind::Close => write!(f, ")"),
+        }
+    }
+}
+
+/// Why an expression could not be read or worked out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// A character that is no part of any token, or an operator the table does not have.
+    UnknownToken { column: usize, found: char },
+    /// The parenthesis at `column` has no partner.
+    UnbalancedParenthesis { column: usize },
+    /// A token where an operand or an operator should be.
+    Unexpected { column: usize, found: String },
+    /// The expression stops where an operand should be.
+    UnexpectedEnd,
+    UnknownVariable(String),
+    /// An operator `eval` does not know how to work out.
+    UnknownOperator(char),
+    DivisionByZero,
+    Overflow,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::UnknownToken { column, found } => write!(f, "column {}: unknown token {:?}", column, found),
+            Error::UnbalancedParenthesis { column } => write!(f, "column {}: unbalanced parenthesis", column),
+            Error::Unexpected { column, found } => write!(f, "column {}: unexpected {:?}", column, found),
+            Error::UnexpectedEnd => write!(f, "the expression ends too soon"),
+            Error::UnknownVariable(name) => write!(f, "unknown variable {}", name),
+            Error::UnknownOperator(op) => write!(f, "no way to work out {}", op),
+            Error::DivisionByZero => write!(f, "division by zero"),
+            Error::Overflow => write!(f, "the value does not fit"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Splits `input` into numbers, names, parentheses and the operators in `operators`,
+/// skipping whitespace. Operators are read only where a number or a name cannot start.
+pub fn tokenize(input: &str, operators: &Operators) -> Result<Vec<Token>, Error> {
+    let mut tokens = vec![];
+    let mut chars = input.chars().enumerate().peekable();
+    while let Some((i, c)) = chars.next() {
+        let column = i + 1;
+        let kind = match c {
+            c if c.is_whitespace() => continue,
+            '0'..='9' => {
+                let mut digits = c.to_string();
+                while let Some((_, d)) = chars.next_if(|(_, d)| d.is_ascii_digit()) {
+                    digits.push(d);
+                }
+                TokenKind::Number(digits.parse().map_err(|_| Error::Overflow)?)
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut name = c.to_string();
+                while let Some((_, d)) = chars.next_if(|(_, d)| d.is_alphanumeric() || *d == '_') {
+                    name.push(d);
+                }
+                TokenKind::Name(name)
+            }
+            '(' => TokenKind::Open,
+            ')' => TokenKind::Close,
+            c if operators.contains(c) => TokenKind::Operator(c),
+            found => return Err(Error::UnknownToken { column, found }),
+        };
+        tokens.push(Token { kind, column });
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Associativity {
+    Left,
+    Right,
+}
+
+/// Which operators an expression may use and how tightly they bind, the higher the
+/// precedence the tighter.
+#[derive(Debug, Clone, Default)]
+pub struct Operators {
+    binary: HashMap<char, (u8, Associativity)>,
+    negation: Option<u8>,
+}
+
+impl Operators {
+    /// No operators at all, add them with `binary` and `negation`.
+    pub fn new() -> Self {
+        Operators::default()
+    }
+
+    /// The usual rules: `*` and `/` before `+` and `-`, all from left to right, and a minus
+    /// sign before everything.
+    pub fn arithmetic() -> Self {
+        Operators::new()
+            .binary('+', 1, Associativity::Left)
+            .binary('-', 1, Associativity::Left)
+            .binary('*', 2, Associativity::Left)
+            .binary('/', 2, Associativity::Left)
+            .negation(3)
+    }
+
+    pub fn binary(mut self, op: char, precedence: u8, associativity: Associativity) -> Self {
+        self.binary.insert(op, (precedence, associativity));
+        self
+    }
+
+    /// Allows a `-` in front of an operand.
+    pub fn negation(mut self, precedence: u8) -> Self {
+        self.negation = Some(precedence);
+        self
+    }
+
+    /// Whether `c` is one of the operators, binary or the minus sign.
+    pub fn contains(&self, c: char) -> bool {
+        self.binary.contains_key(&c) || (c == '-' && self.negation.is_some())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Number(i64),
+    Variable(String),
+    Neg(Box<Expr>),
+    Binary(char, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// Reads `input` with the precedence and associativity of `operators`.
+    pub fn parse(input: &str, operators: &Operators) -> Result<Self, Error> {
+        let mut parser = Parser { tokens: tokenize(input, operators)?.into_iter().peekable(), operators };
+        let expr = parser.expression(0)?;
+        match parser.tokens.next() {
+            None => Ok(expr),
+            Some(Token { kind: TokenKind::Close, column }) => Err(Error::UnbalancedParenthesis { column }),
+            Some(token) => Err(unexpected(token)),
+        }
+    }
+
+    /// The value of the expression, dividing towards zero, with the variables looked up in
+    /// `variables`. Only `+ - * /` can be worked out, other operators are an error.
+    pub fn eval(&self, variables: &HashMap<String, i64>) -> Result<i64, Error> {
+        match self {
+            Expr::Number(n) => Ok(*n),
+            Expr::Variable(name) => variables.get(name).copied().ok_or_else(|| Error::UnknownVariable(name.clone())),
+            Expr::Neg(operand) => operand.eval(variables)?.checked_neg().ok_or(Error::Overflow),
+            Expr::Binary(op, lhs, rhs) => {
+                let (lhs, rhs) = (lhs.eval(variables)?, rhs.eval(variables)?);
+                match op {
+                    '+' => lhs.checked_add(rhs),
+                    '-' => lhs.checked_sub(rhs),
+                    '*' => lhs.checked_mul(rhs),
+                    '/' if rhs == 0 => return Err(Error::DivisionByZero),
+                    '/' => lhs.checked_div(rhs),
+                    _ => return Err(Error::UnknownOperator(*op)),
+                }.ok_or(Error::Overflow)
+            }
+        }
+    }
+}
+
+/// Writes every operation in parentheses, which shows how the expression was read.
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expr::Number(n) => write!(f, "{}", n),
+            Expr::Variable(name) => write!(f, "{}", name),
+            Expr::Neg(operand) => write!(f, "-{}", operand),
+            Expr::Binary(op, lhs, rhs) => write!(f, "({} {} {})", lhs, op, rhs),
+        }
+    }
+}
+
+fn unexpected(token: Token) -> Error {
+    Error::Unexpected { column: token.column, found: token.kind.to_string() }
+}
+
+/// A Pratt parser: an operand, then every operator that binds at least `min` tightly along
+/// with what comes after it. The binding powers are wider than the precedences, so that one
+/// more than `u8::MAX` still binds tighter than it.
+struct Parser<'a> {
+    tokens: Peekable<IntoIter<Token>>,
+    operators: &'a Operators,
+}
+
+impl Parser<'_> {
+    fn expression(&mut self, min: u16) -> Result<Expr, Error> {
+        let mut lhs = self.operand()?;
+        while let Some(token) = self.tokens.peek() {
+            let op = match token.kind {
+                TokenKind::Operator(op) => op,
+                TokenKind::Close => break,
+                _ => return Err(unexpected(token.clone())),
+            };
+            let (precedence, associativity) = *self.operators.binary.get(&op)
+                .ok_or(Error::UnknownToken { column: token.column, found: op })?;
+            let precedence = u16::from(precedence);
+            if precedence < min {
+                break;
+            }
+            self.tokens.next();
+            let rhs = self.expression(match associativity {
+                Associativity::Left => precedence + 1,
+                Associativity::Right => precedence,
+            })?;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn operand(&mut self) -> Result<Expr, Error> {
+        let token = self.tokens.next().ok_or(Error::UnexpectedEnd)?;
+        match (token.kind.clone(), self.operators.negation) {
+            (TokenKind::Number(n), _) => Ok(Expr::Number(n)),
+            (TokenKind::Name(name), _) => Ok(Expr::Variable(name)),
+            (TokenKind::Operator('-'), Some(precedence)) => Ok(Expr::Neg(Box::new(self.expression(u16::from(precedence))?))),
+            (TokenKind::Open, _) => {
+                let inner = self.expression(0)?;
+                match self.tokens.next() {
+                    Some(Token { kind: TokenKind::Close, .. }) => Ok(inner),
+                    _ => Err(Error::UnbalancedParenthesis { column: token.column }),
+                }
+            }
+            _ => Err(unexpected(token)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(input: &str, operators: &Operators) -> Result<i64, Error> {
+        Expr::parse(input, operators)?.eval(&HashMap::new())
+    }
+
+    #[test]
+    fn tokenizeTest() {
+        let arithmetic = Operators::arithmetic();
+        let tokens = tokenize("12 *(x1- 3)", &arithmetic).unwrap();
+        let kinds: Vec<TokenKind> = tokens.iter().map(|t| t.kind.clone()).collect();
+        assert_eq!(vec![
+            TokenKind::Number(12), TokenKind::Operator('*'), TokenKind::Open, TokenKind::Name("x1".to_string()),
+            TokenKind::Operator('-'), TokenKind::Number(3), TokenKind::Close,
+        ], kinds);
+        assert_eq!(vec![1, 4, 5, 6, 8, 10, 11], tokens.iter().map(|t| t.column).collect::<Vec<_>>());
+        assert_eq!(Err(Error::UnknownToken { column: 3, found: '%' }), tokenize("1 % 2", &arithmetic));
+        let power = Operators::new().binary('^', 1, Associativity::Right);
+        assert_eq!(TokenKind::Operator('^'), tokenize("2^3", &power).unwrap()[1].kind);
+        assert_eq!(Err(Error::UnknownToken { column: 2, found: '-' }), tokenize("2-3", &power));
+    }
+
+    #[test]
+    fn precedenceTest() {
+        let arithmetic = Operators::arithmetic();
+        assert_eq!(7, eval("1 + 2 * 3", &arithmetic).unwrap());
+        assert_eq!(3, eval("10 - 4 - 3", &arithmetic).unwrap());
+        assert_eq!(-3, eval("-(1 + 2)", &arithmetic).unwrap());
+        assert_eq!(-2, eval("-7 / 3", &arithmetic).unwrap());
+        assert_eq!("((1 + (2 * 3)) - -x)", Expr::parse("1 + 2 * 3 - -x", &arithmetic).unwrap().to_string());
+        let right = Operators::new().binary('-', 1, Associativity::Right);
+        assert_eq!(9, eval("10 - 4 - 3", &right).unwrap());
+        let flat = Operators::new().binary('+', 1, Associativity::Left).binary('*', 1, Associativity::Left);
+        assert_eq!(9, eval("1 + 2 * 3", &flat).unwrap());
+        let highest = Operators::new().binary('-', u8::MAX, Associativity::Left).binary('+', 0, Associativity::Left);
+        assert_eq!("(((10 - 4) - 3) + 1)", Expr::parse("10 - 4 - 3 + 1", &highest).unwrap().to_string());
+    }
+
+    #[test]
+    fn variableTest() {
+        let expr = Expr::parse("a * (b + 2)", &Operators::arithmetic()).unwrap();
+        let variables = HashMap::from([("a".to_string(), 3), ("b".to_string(), -4)]);
+        assert_eq!(Ok(-6), expr.eval(&variables));
+        assert_eq!(Err(Error::UnknownVariable("a".to_string())), expr.eval(&HashMap::new()));
+    }
+
+    #[test]
+    fn errorTest() {
+        let arithmetic = Operators::arithmetic();
+        assert_eq!(Err(Error::Unexpected { column: 3, found: "(".to_string() }), eval("1 (2 + 3)", &arithmetic));
+        assert_eq!(Err(Error::UnbalancedParenthesis { column: 5 }), eval("1 + (2 + 3", &arithmetic));
+        assert_eq!(Err(Error::UnbalancedParenthesis { column: 6 }), eval("1 + 2)", &arithmetic));
+        assert_eq!(Err(Error::Unexpected { column: 3, found: "2".to_string() }), eval("1 2", &arithmetic));
+        assert_eq!(Err(Error::Unexpected { column: 5, found: "*".to_string() }), eval("1 + * 2", &arithmetic));
+        assert_eq!(Err(Error::UnexpectedEnd), eval("1 +", &arithmetic));
+        assert_eq!(Err(Error::UnknownToken { column: 3, found: '/' }), eval("1 / 2", &Operators::new().binary('+', 1, Associativity::Left)));
+        assert_eq!(Err(Error::DivisionByZero), eval("1 / (2 - 2)", &arithmetic));
+        assert_eq!(Err(Error::Overflow), eval("9223372036854775807 + 1", &arithmetic));
+        let power = Operators::new().binary('^', 1, Associativity::Right);
+        assert_eq!("(2 ^ (3 ^ 2))", Expr::parse("2 ^ 3 ^ 2", &power).unwrap().to_string());
+        assert_eq!(Err(Error::UnknownOperator('^')), eval("2 ^ 3", &power));
+    }
+}
@@ -0,0 +1,301 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// A cell of a grid, as `(row, column)` counting from the top left.
+pub type Pos = (usize, usize);
+
+/// The steps to the four neighbours that share a side with a cell: up, left, right and down.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// The steps to all eight neighbours of a cell, row by row.
+pub const ADJACENT: [(isize, isize); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+
+/// Why a character map is not a grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    Empty,
+    /// A character the cells cannot be made of, the line and column count from 1.
+    Cell { line: usize, column: usize, found: char },
+    /// A row that is not as long as the first one.
+    Ragged { line: usize, expected: usize, found: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "the grid has no cells"),
+            ParseError::Cell { line, column, found } => write!(f, "line {}, column {}: unexpected {:?}", line, column, found),
+            ParseError::Ragged { line, expected, found } => write!(f, "line {}: expected {} cells, found {}", line, expected, found),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Grid<T> {
+    /// A `rows` by `cols` grid of `value`. Panics when either is zero, a grid always has a cell.
+    pub fn new(rows: usize, cols: usize, value: T) -> Self where T: Clone {
+        assert_not_empty(rows, cols);
+        Grid { cells: vec![value; rows * cols], rows, cols }
+    }
+
+    /// A `rows` by `cols` grid with `cell` at every position. Panics when either is zero.
+    pub fn from_fn(rows: usize, cols: usize, mut cell: impl FnMut(Pos) -> T) -> Self {
+        assert_not_empty(rows, cols);
+        let cells = (0..rows).flat_map(|row| (0..cols).map(move |col| (row, col))).map(&mut cell).collect();
+        Grid { cells, rows, cols }
+    }
+
+    /// Reads a character map, one row per line. Lines are trimmed and empty lines skipped,
+    /// `cell` turns a character into a cell or rejects it with `None`.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Self, ParseError> {
+        let mut cells = vec![];
+        let mut rows = 0;
+        let mut cols = 0;
+        for (i, line) in input.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+            let start = cells.len();
+            for (column, c) in line.trim().chars().enumerate() {
+                cells.push(cell(c).ok_or(ParseError::Cell { line: i + 1, column: column + 1, found: c })?);
+            }
+            let found = cells.len() - start;
+            if rows == 0 {
+                cols = found;
+            } else if found != cols {
+                return Err(ParseError::Ragged { line: i + 1, expected: cols, found });
+            }
+            rows += 1;
+        }
+        match rows {
+            0 => Err(ParseError::Empty),
+            _ => Ok(Grid { cells, rows, cols }),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn contains(&self, (row, col): Pos) -> bool {
+        row < self.rows && col < self.cols
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        match self.contains(pos) {
+            true => Some(&self.cells[pos.0 * self.cols + pos.1]),
+            false => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        match self.contains(pos) {
+            true => Some(&mut self.cells[pos.0 * self.cols + pos.1]),
+            false => None,
+        }
+    }
+
+    /// The cell at `pos` of the grid repeated endlessly in every direction.
+    pub fn get_wrapping(&self, (row, col): Pos) -> &T {
+        &self[(row % self.rows, col % self.cols)]
+    }
+
+    /// The cell one `step` away from `pos`, when that is still on the grid.
+    pub fn step(&self, (row, col): Pos, (d_row, d_col): (isize, isize)) -> Option<Pos> {
+        let pos = (row.checked_add_signed(d_row)?, col.checked_add_signed(d_col)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    /// The up to four neighbours sharing a side with `pos`.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ORTHOGONAL.iter().filter_map(move |&d| self.step(pos, d))
+    }
+
+    /// The up to eight neighbours of `pos`, diagonals included.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ADJACENT.iter().filter_map(move |&d| self.step(pos, d))
+    }
+
+    /// The cells seen looking from `pos` in `direction`, up to the edge of the grid.
+    pub fn ray(&self, pos: Pos, direction: (isize, isize)) -> impl Iterator<Item = Pos> + '_ {
+        std::iter::successors(self.step(pos, direction), move |&pos| self.step(pos, direction))
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |row| (0..cols).map(move |col| (row, col)))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(col).step_by(self.cols)
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.cols)
+    }
+
+    pub fn iter_columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.cols).map(move |col| self.column(col))
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { cells: self.cells.iter().map(&mut f).collect(), rows: self.rows, cols: self.cols }
+    }
+
+    /// The grid mirrored along its diagonal, rows become columns.
+    pub fn transpose(&self) -> Self where T: Clone {
+        Grid::from_fn(self.cols, self.rows, |(row, col)| self[(col, row)].clone())
+    }
+
+    /// The grid turned a quarter clockwise.
+    pub fn rotate_clockwise(&self) -> Self where T: Clone {
+        Grid::from_fn(self.cols, self.rows, |(row, col)| self[(self.rows - 1 - col, row)].clone())
+    }
+
+    pub fn rotate_counter_clockwise(&self) -> Self where T: Clone {
+        Grid::from_fn(self.cols, self.rows, |(row, col)| self[(col, self.cols - 1 - row)].clone())
+    }
+
+    /// The grid repeated `down` times down and `across` times across, with `tile` making the
+    /// cell of each copy from the original cell and the copy's `(row, column)` among the copies.
+    pub fn tile(&self, down: usize, across: usize, mut tile: impl FnMut(&T, Pos) -> T) -> Self {
+        Grid::from_fn(self.rows * down, self.cols * across, |(row, col)| {
+            tile(&self[(row % self.rows, col % self.cols)], (row / self.rows, col / self.cols))
+        })
+    }
+
+    /// The grid with a border of `width` cells of `value` around it.
+    pub fn pad(&self, width: usize, value: T) -> Self where T: Clone {
+        Grid::from_fn(self.rows + 2 * width, self.cols + 2 * width, |(row, col)| {
+            match (row.checked_sub(width), col.checked_sub(width)) {
+                (Some(row), Some(col)) => self.get((row, col)).unwrap_or(&value).clone(),
+                _ => value.clone(),
+            }
+        })
+    }
+}
+
+/// Rows, columns and wrapping all divide by the size of the grid, so an empty one is refused
+/// up front, like `parse` does.
+fn assert_not_empty(rows: usize, cols: usize) {
+    assert!(rows > 0 && cols > 0, "a grid needs at least one cell, not {}x{}", rows, cols);
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos).unwrap_or_else(|| panic!("{:?} is outside the {}x{} grid", pos, self.rows, self.cols))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (rows, cols) = (self.rows, self.cols);
+        self.get_mut(pos).unwrap_or_else(|| panic!("{:?} is outside the {}x{} grid", pos, rows, cols))
+    }
+}
+
+/// The cells row by row, each row on a line of its own.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.iter_rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "123
+        456";
+
+    fn digits() -> Grid<u32> {
+        Grid::parse(MAP, |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn parseTest() {
+        let grid = digits();
+        assert_eq!((2, 3), (grid.rows(), grid.cols()));
+        assert_eq!(6, grid[(1, 2)]);
+        assert_eq!(None, grid.get((2, 0)));
+        assert_eq!("123\n456\n", grid.to_string());
+        assert_eq!(Err(ParseError::Cell { line: 2, column: 2, found: 'x' }), Grid::parse("12\n3x", |c| c.to_digit(10)));
+        assert_eq!(Err(ParseError::Ragged { line: 3, expected: 2, found: 1 }), Grid::parse("12\n\n3", |c| c.to_digit(10)));
+        assert_eq!(Err(ParseError::Empty), Grid::parse("\n", Some));
+    }
+
+    #[test]
+    fn neighboursTest() {
+        let grid = digits();
+        assert_eq!(vec![(0, 1), (1, 0)], grid.neighbours4((0, 0)).collect::<Vec<Pos>>());
+        assert_eq!(vec![(0, 0), (0, 2), (1, 1)], grid.neighbours4((0, 1)).collect::<Vec<Pos>>());
+        assert_eq!(5, grid.neighbours8((1, 1)).count());
+        assert_eq!(vec![(0, 1), (0, 2)], grid.ray((0, 0), (0, 1)).collect::<Vec<Pos>>());
+        assert_eq!(0, grid.ray((0, 0), (-1, 1)).count());
+    }
+
+    #[test]
+    fn rowsAndColumnsTest() {
+        let grid = digits();
+        assert_eq!(&[4, 5, 6], grid.row(1));
+        assert_eq!(vec![3, 6], grid.column(2).copied().collect::<Vec<u32>>());
+        assert_eq!(vec![5, 7, 9], grid.iter_columns().map(|c| c.sum()).collect::<Vec<u32>>());
+        assert_eq!(vec![6, 15], grid.iter_rows().map(|r| r.iter().sum()).collect::<Vec<u32>>());
+        assert_eq!(Some(((1, 0), &4)), grid.iter().nth(3));
+        assert_eq!(&5, grid.get_wrapping((3, 7)));
+    }
+
+    #[test]
+    fn transformTest() {
+        let grid = digits();
+        assert_eq!("14\n25\n36\n", grid.transpose().to_string());
+        assert_eq!("41\n52\n63\n", grid.rotate_clockwise().to_string());
+        assert_eq!("36\n25\n14\n", grid.rotate_counter_clockwise().to_string());
+        assert_eq!(grid, grid.rotate_clockwise().rotate_counter_clockwise());
+        assert_eq!("123234\n456567\n234345\n567678\n", grid.tile(2, 2, |&v, (r, c)| v + (r + c) as u32).to_string());
+        assert_eq!("00000\n01230\n04560\n00000\n", grid.pad(1, 0).to_string());
+        assert_eq!("246\n81012\n", grid.map(|v| v * 2).to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "a grid needs at least one cell, not 3x0")]
+    fn emptyGridTest() {
+        Grid::from_fn(3, 0, |_| 0);
+    }
+}
@@ -0,0 +1,239 @@
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
+
+/// The integers an `IntervalSet` can hold.
+pub trait Discrete: Copy + Ord + Debug {
+    fn predecessor(self) -> Option<Self>;
+    fn successor(self) -> Option<Self>;
+    /// How many values there are from `start` to `end`, both included, which is more than a
+    /// `u64` holds for the full range of a 64-bit type.
+    fn count(start: Self, end: Self) -> u128;
+}
+
+macro_rules! discrete {
+    ($($t:ty),*) => {
+        $(impl Discrete for $t {
+            fn predecessor(self) -> Option<Self> {
+                self.checked_sub(1)
+            }
+
+            fn successor(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+
+            fn count(start: Self, end: Self) -> u128 {
+                (end as i128 - start as i128 + 1) as u128
+            }
+        })*
+    };
+}
+
+discrete!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// A set of integers kept as the fewest inclusive ranges that cover it, in order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T: Discrete> {
+    /// From the start of each range to its end. Ranges neither overlap nor touch.
+    ranges: BTreeMap<T, T>,
+}
+
+impl<T: Discrete> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet { ranges: BTreeMap::new() }
+    }
+
+    /// Adds every value of `range`, merging it with the ranges it overlaps or touches.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        let before = start.predecessor().unwrap_or(start);
+        if let Some((&s, &e)) = self.ranges.range(..=start).next_back() {
+            if e >= before {
+                start = s;
+                end = end.max(e);
+            }
+        }
+        let after = end.successor().unwrap_or(end);
+        let merged: Vec<(T, T)> = self.ranges.range(start..=after).map(|(&s, &e)| (s, e)).collect();
+        for (s, e) in merged {
+            self.ranges.remove(&s);
+            end = end.max(e);
+        }
+        self.ranges.insert(start, end);
+    }
+
+    /// Takes every value of `range` out, splitting the ranges it falls in.
+    pub fn remove(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        let first = self.ranges.range(..=start).next_back().map_or(start, |(&s, _)| s);
+        let overlapping: Vec<(T, T)> = self.ranges.range(first..=end).map(|(&s, &e)| (s, e)).collect();
+        for (s, e) in overlapping.into_iter().filter(|&(_, e)| e >= start) {
+            self.ranges.remove(&s);
+            if s < start {
+                self.ranges.insert(s, start.predecessor().unwrap());
+            }
+            if e > end {
+                self.ranges.insert(end.successor().unwrap(), e);
+            }
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.ranges.range(..=value).next_back().is_some_and(|(_, &end)| end >= value)
+    }
+
+    /// Whether every value of `range` is in the set.
+    pub fn contains_range(&self, range: &RangeInclusive<T>) -> bool {
+        range.is_empty() || self.ranges.range(..=*range.start()).next_back().is_some_and(|(_, end)| end >= range.end())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// How many values the set holds.
+    pub fn total_len(&self) -> u128 {
+        self.ranges.iter().map(|(&start, &end)| T::count(start, end)).sum()
+    }
+
+    /// The ranges of the set, in order.
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|(&start, &end)| start..=end)
+    }
+
+    /// The ranges missing between the first value of the set and the last.
+    pub fn gaps(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().zip(self.ranges.iter().skip(1))
+            .map(|((_, &end), (&next, _))| end.successor().unwrap()..=next.predecessor().unwrap())
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        other.iter().for_each(|range| union.insert(range));
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intersection = IntervalSet::new();
+        let (mut mine, mut theirs) = (self.iter().peekable(), other.iter().peekable());
+        while let (Some(a), Some(b)) = (mine.peek(), theirs.peek()) {
+            let start = *a.start().max(b.start());
+            let end = *a.end().min(b.end());
+            intersection.insert(start..=end);
+            match a.end() < b.end() {
+                true => mine.next(),
+                false => theirs.next(),
+            };
+        }
+        intersection
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        other.iter().for_each(|range| difference.remove(range));
+        difference
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.iter().all(|range| other.contains_range(&range))
+    }
+
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.intersection(other).is_empty()
+    }
+}
+
+impl<T: Discrete> From<RangeInclusive<T>> for IntervalSet<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let mut set = IntervalSet::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<T: Discrete> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(ranges: I) -> Self {
+        let mut set = IntervalSet::new();
+        ranges.into_iter().for_each(|range| set.insert(range));
+        set
+    }
+}
+
+impl<T: Discrete> Extend<RangeInclusive<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, ranges: I) {
+        ranges.into_iter().for_each(|range| self.insert(range));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(set: &IntervalSet<i32>) -> Vec<RangeInclusive<i32>> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn insertTest() {
+        let mut set: IntervalSet<i32> = [1..=3, 10..=12, 6..=7].into_iter().collect();
+        assert_eq!(vec![1..=3, 6..=7, 10..=12], ranges(&set));
+        set.insert(4..=5);
+        assert_eq!(vec![1..=7, 10..=12], ranges(&set));
+        set.insert(0..=11);
+        assert_eq!(vec![0..=12], ranges(&set));
+        set.insert(RangeInclusive::new(5, 2));
+        assert_eq!(13, set.total_len());
+        let mut edge = IntervalSet::from(250u8..=255);
+        edge.insert(0..=0);
+        edge.insert(255..=255);
+        assert_eq!(vec![0..=0, 250..=255], edge.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn removeTest() {
+        let mut set: IntervalSet<i32> = [-5..=5, 8..=9, 12..=20].into_iter().collect();
+        set.remove(0..=0);
+        assert_eq!(vec![-5..=-1, 1..=5, 8..=9, 12..=20], ranges(&set));
+        set.remove(4..=13);
+        assert_eq!(vec![-5..=-1, 1..=3, 14..=20], ranges(&set));
+        set.remove(-10..=-5);
+        assert_eq!(vec![-4..=-1, 1..=3, 14..=20], ranges(&set));
+        assert_eq!(vec![0..=0, 4..=13], set.gaps().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn queryTest() {
+        let set: IntervalSet<i32> = [1..=3, 6..=9].into_iter().collect();
+        assert!(set.contains(1) && set.contains(9) && !set.contains(4) && !set.contains(0));
+        assert!(set.contains_range(&(6..=8)));
+        assert!(!set.contains_range(&(3..=6)));
+        assert!(IntervalSet::from(7..=8).is_subset(&set));
+        assert!(!IntervalSet::from(2..=4).is_subset(&set));
+        assert!(IntervalSet::from(4..=5).is_disjoint(&set));
+        assert!(IntervalSet::<i32>::new().is_empty());
+    }
+
+    #[test]
+    fn setOperationsTest() {
+        let a: IntervalSet<i32> = [1..=5, 10..=15].into_iter().collect();
+        let b: IntervalSet<i32> = [4..=11, 14..=20].into_iter().collect();
+        assert_eq!(vec![1..=20], ranges(&a.union(&b)));
+        assert_eq!(vec![4..=5, 10..=11, 14..=15], ranges(&a.intersection(&b)));
+        assert_eq!(vec![1..=3, 12..=13], ranges(&a.difference(&b)));
+        assert_eq!(vec![6..=9, 16..=20], ranges(&b.difference(&a)));
+    }
+
+    #[test]
+    fn fullWidthTest() {
+        assert_eq!(1 << 64, IntervalSet::from(i64::MIN..=i64::MAX).total_len());
+        assert_eq!(1 << 64, IntervalSet::from(0..=u64::MAX).total_len());
+        let halves: IntervalSet<u64> = [0..=u64::MAX / 2, u64::MAX / 2 + 2..=u64::MAX].into_iter().collect();
+        assert_eq!(u64::MAX as u128, halves.total_len());
+    }
+}
@@ -0,0 +1,14 @@
+#![allow(non_snake_case)]
+
+//! The building blocks that keep coming back in the puzzles, shared by every year.
+
+pub mod automaton;
+pub mod expression;
+pub mod grid;
+pub mod interval;
+pub mod numtheory;
+pub mod point;
+pub mod search;
+
+pub use grid::Grid;
+pub use point::Point;
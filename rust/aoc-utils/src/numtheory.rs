@@ -0,0 +1,163 @@
+//! Divisors and remainders. The results of the modular functions are in `0..modulus`, and
+//! their products are taken without overflowing for any modulus that fits an `i128`.
+
+use std::ops::{Div, Rem};
+
+/// The integers `gcd` and `lcm` work on.
+pub trait Unsigned: Copy + Eq + Div<Output = Self> + Rem<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! unsigned {
+    ($($t:ty),*) => {
+        $(impl Unsigned for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+        })*
+    };
+}
+
+unsigned!(u8, u16, u32, u64, u128, usize);
+
+pub fn gcd<T: Unsigned>(a: T, b: T) -> T {
+    if b == T::ZERO {
+        return a;
+    }
+    gcd(b, a % b)
+}
+
+/// The least common multiple of `a` and `b`, none when it does not fit a `T`.
+pub fn lcm<T: Unsigned>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// The least common multiple of all the numbers, 1 when there are none and none when it
+/// does not fit a `T`.
+pub fn lcm_all<T: Unsigned>(numbers: impl IntoIterator<Item = T>) -> Option<T> {
+    numbers.into_iter().try_fold(T::ONE, lcm)
+}
+
+/// The greatest common divisor `g` of `a` and `b`, with `x` and `y` such that
+/// `a * x + b * y = g`, as `(g, x, y)`. `g` is never negative.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        return (a.abs(), a.signum(), 0);
+    }
+    let (g, x, y) = extended_gcd(b, a.rem_euclid(b));
+    (g, y, x - a.div_euclid(b) * y)
+}
+
+/// `a * b` modulo a positive `modulus`, falling back to doubling when the product does not fit.
+pub fn mul_mod(a: i128, b: i128, modulus: i128) -> i128 {
+    let (mut a, mut b) = (a.rem_euclid(modulus), b.rem_euclid(modulus));
+    if let Some(product) = a.checked_mul(b) {
+        return product % modulus;
+    }
+    let mut product = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            product = add_mod(product, a, modulus);
+        }
+        a = add_mod(a, a, modulus);
+        b >>= 1;
+    }
+    product
+}
+
+fn add_mod(a: i128, b: i128, modulus: i128) -> i128 {
+    match a.checked_add(b) {
+        Some(sum) => sum % modulus,
+        None => a - (modulus - b),
+    }
+}
+
+/// The `x` for which `a * x` is 1 modulo `modulus`, if `modulus` is positive and has no common
+/// divisor with `a`.
+pub fn mod_inverse(a: i128, modulus: i128) -> Option<i128> {
+    if modulus <= 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// The `x` that leaves each `remainder` when divided by its `modulus`, as `(x, m)` where every
+/// `x + k * m` does too and `m` is the least common multiple of the moduli. The moduli need
+/// not be coprime but have to be positive. `None` is when a modulus is not, when the remainders
+/// contradict each other, or when `m` or the difference to a remainder overflows.
+pub fn crt(congruences: impl IntoIterator<Item = (i128, i128)>) -> Option<(i128, i128)> {
+    congruences.into_iter().try_fold((0, 1), |(x, m), (remainder, modulus)| {
+        if modulus <= 0 {
+            return None;
+        }
+        // x + m * k = remainder (mod modulus), solved for k in steps of modulus / g
+        let (g, inverse, _) = extended_gcd(m, modulus);
+        let difference = remainder.checked_sub(x)?;
+        if difference % g != 0 {
+            return None;
+        }
+        let step = modulus / g;
+        let k = mul_mod((difference / g).rem_euclid(step), inverse, step);
+        let lcm = m.checked_mul(step)?;
+        Some((add_mod(x, mul_mod(m, k, lcm), lcm), lcm))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcdTest() {
+        assert_eq!(6, gcd(54u64, 24));
+        assert_eq!(7, gcd(0u64, 7));
+        assert_eq!(Some(72), lcm(24u64, 18));
+        assert_eq!(Some(96577), lcm_all([23u64, 19, 13, 17]));
+        assert_eq!(Some(1), lcm_all::<u64>([]));
+        assert_eq!(Some(0), lcm(0u32, 5));
+        assert_eq!(Some(u64::MAX), lcm(u64::MAX, 5));
+        assert_eq!(None, lcm(u64::MAX, 2));
+        assert_eq!(None, lcm_all([4294967291u64, 4294967279, 3]));
+        assert_eq!(Some(55340231937660813567), lcm_all([4294967291u128, 4294967279, 3]));
+        assert_eq!(3, gcd(12usize, 9));
+        let (g, x, y) = extended_gcd(240, -46);
+        assert_eq!(2, g);
+        assert_eq!(g, 240 * x - 46 * y);
+    }
+
+    #[test]
+    fn modularTest() {
+        assert_eq!(Some(4), mod_inverse(3, 11));
+        assert_eq!(Some(10), mod_inverse(-1, 11));
+        assert_eq!(None, mod_inverse(6, 9));
+        assert_eq!(None, mod_inverse(3, 0));
+        assert_eq!(None, mod_inverse(3, -11));
+        let big = i128::MAX - 1;
+        assert_eq!(1, mul_mod(big - 1, big - 1, big));
+        assert_eq!(big - 2, mul_mod(big - 2, 1, big));
+    }
+
+    #[test]
+    fn crtTest() {
+        assert_eq!(Some((23, 105)), crt([(2, 3), (3, 5), (2, 7)]));
+        assert_eq!(Some((10, 12)), crt([(4, 6), (2, 4)]));
+        assert_eq!(None, crt([(1, 6), (2, 4)]));
+        assert_eq!(Some((0, 1)), crt([]));
+        assert_eq!(None, crt([(2, 3), (1, 0)]));
+        assert_eq!(None, crt([(2, 3), (1, -5)]));
+        assert_eq!(None, crt([(1, 2), (i128::MIN, 3)]));
+        let primes = [1_000_000_007i128, 998_244_353, 1_000_000_009, 999_999_937];
+        let (x, m) = crt(primes.map(|p| (p - 1, p))).unwrap();
+        assert_eq!(primes.iter().product::<i128>(), m);
+        assert_eq!(m - 1, x);
+    }
+}
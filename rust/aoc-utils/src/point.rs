@@ -0,0 +1,245 @@
+use std::fmt;
+use std::num::ParseIntError;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// A point, or the vector between two points, in `N` dimensions. Points are ordered by their
+/// first coordinate, then their second and so on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<const N: usize>(pub [i64; N]);
+
+/// Why text is not a point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The text has another number of coordinates than the point has dimensions.
+    Dimensions { expected: usize, found: usize },
+    Number(ParseIntError),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Dimensions { expected, found } => write!(f, "expected {} coordinates, found {}", expected, found),
+            ParseError::Number(e) => write!(f, "not a coordinate: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl<const N: usize> Point<N> {
+    pub const ORIGIN: Self = Point([0; N]);
+
+    pub fn from_fn(coordinate: impl FnMut(usize) -> i64) -> Self {
+        Point(std::array::from_fn(coordinate))
+    }
+
+    pub fn x(&self) -> i64 {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> i64 {
+        self.0[1]
+    }
+
+    pub fn z(&self) -> i64 {
+        self.0[2]
+    }
+
+    /// The point in `M` dimensions, cut short or with zeros added.
+    pub fn resize<const M: usize>(&self) -> Point<M> {
+        Point::from_fn(|i| self.0.get(i).copied().unwrap_or(0))
+    }
+
+    /// The point with each coordinate replaced by its sign, the unit step from the origin
+    /// towards it along each axis.
+    pub fn signum(&self) -> Self {
+        Point(self.0.map(i64::signum))
+    }
+
+    pub fn abs(&self) -> Self {
+        Point(self.0.map(i64::abs))
+    }
+
+    /// The distance walking along the axes.
+    pub fn manhattan(&self, other: &Self) -> u64 {
+        (*self - *other).0.iter().map(|d| d.unsigned_abs()).sum()
+    }
+
+    /// The distance when a diagonal step is as long as a straight one.
+    pub fn chebyshev(&self, other: &Self) -> u64 {
+        (*self - *other).0.iter().map(|d| d.unsigned_abs()).max().unwrap_or(0)
+    }
+
+    pub fn euclidean(&self, other: &Self) -> f64 {
+        (*self - *other).0.iter().map(|&d| (d * d) as f64).sum::<f64>().sqrt()
+    }
+
+    /// The `2 * N` points one step away along a single axis.
+    pub fn orthogonal_neighbours(&self) -> impl Iterator<Item = Self> + '_ {
+        (0..N).flat_map(move |axis| [-1, 1].map(|d| {
+            let mut neighbour = *self;
+            neighbour.0[axis] += d;
+            neighbour
+        }))
+    }
+
+    /// The `3^N - 1` points around this one, diagonals included, in order.
+    pub fn neighbours(&self) -> impl Iterator<Item = Self> + '_ {
+        let around = 3usize.pow(N as u32);
+        (0..around).filter(move |&i| i != around / 2).map(move |i| {
+            Point::from_fn(|axis| self.0[axis] + (i / 3usize.pow((N - 1 - axis) as u32) % 3) as i64 - 1)
+        })
+    }
+}
+
+impl<const N: usize> Default for Point<N> {
+    fn default() -> Self {
+        Self::ORIGIN
+    }
+}
+
+impl<const N: usize> From<[i64; N]> for Point<N> {
+    fn from(coordinates: [i64; N]) -> Self {
+        Point(coordinates)
+    }
+}
+
+impl<const N: usize> Index<usize> for Point<N> {
+    type Output = i64;
+
+    fn index(&self, axis: usize) -> &i64 {
+        &self.0[axis]
+    }
+}
+
+impl<const N: usize> IndexMut<usize> for Point<N> {
+    fn index_mut(&mut self, axis: usize) -> &mut i64 {
+        &mut self.0[axis]
+    }
+}
+
+impl<const N: usize> Add for Point<N> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point::from_fn(|i| self.0[i] + other.0[i])
+    }
+}
+
+impl<const N: usize> AddAssign for Point<N> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<const N: usize> Sub for Point<N> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Point::from_fn(|i| self.0[i] - other.0[i])
+    }
+}
+
+impl<const N: usize> SubAssign for Point<N> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+/// Scales every coordinate.
+impl<const N: usize> Mul<i64> for Point<N> {
+    type Output = Self;
+
+    fn mul(self, factor: i64) -> Self {
+        Point(self.0.map(|c| c * factor))
+    }
+}
+
+impl<const N: usize> Neg for Point<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Point(self.0.map(|c| -c))
+    }
+}
+
+/// Reads `x,y,z`, whitespace around the coordinates allowed.
+impl<const N: usize> FromStr for Point<N> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let coordinates = s.split(',')
+            .map(|c| c.trim().parse().map_err(ParseError::Number))
+            .collect::<Result<Vec<i64>, _>>()?;
+        let found = coordinates.len();
+        coordinates.try_into().map(Point).map_err(|_| ParseError::Dimensions { expected: N, found })
+    }
+}
+
+/// Writes `x,y,z`, as it is read.
+impl<const N: usize> fmt::Display for Point<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, c) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", c)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmeticTest() {
+        let a = Point([1, -2, 3]);
+        let b = Point([4, 0, -1]);
+        assert_eq!(Point([5, -2, 2]), a + b);
+        assert_eq!(Point([3, 2, -4]), b - a);
+        assert_eq!(Point([-2, 4, -6]), a * -2);
+        assert_eq!(Point([-1, 2, -3]), -a);
+        assert_eq!(Point([1, -1, 1]), a.signum());
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(b, c);
+        assert!(Point([0, 5]) < Point([1, 0]));
+        assert_eq!(Point([1, -2, 0, 0]), Point([1, -2]).resize());
+        assert_eq!(Point([1]), a.resize());
+    }
+
+    #[test]
+    fn distanceTest() {
+        let a = Point([1, 2]);
+        let b = Point([4, -2]);
+        assert_eq!(7, a.manhattan(&b));
+        assert_eq!(4, a.chebyshev(&b));
+        assert_eq!(5.0, a.euclidean(&b));
+    }
+
+    #[test]
+    fn neighboursTest() {
+        let p = Point([1, 2, 3]);
+        let neighbours: Vec<Point<3>> = p.neighbours().collect();
+        assert_eq!(26, neighbours.len());
+        assert_eq!(Point([0, 1, 2]), neighbours[0]);
+        assert_eq!(Point([2, 3, 4]), neighbours[25]);
+        assert!(!neighbours.contains(&p));
+        assert!(neighbours.iter().all(|n| n.chebyshev(&p) == 1));
+        assert_eq!(80, Point::<4>::ORIGIN.neighbours().count());
+        assert_eq!(vec![Point([0, 2]), Point([2, 2]), Point([1, 1]), Point([1, 3])], Point([1, 2]).orthogonal_neighbours().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn parseTest() {
+        assert_eq!(Ok(Point([404, -588, -901])), "404,-588,-901".parse());
+        assert_eq!(Ok(Point([3, 4])), " 3, 4 ".parse());
+        assert_eq!(Err(ParseError::Dimensions { expected: 3, found: 2 }), "0,2".parse::<Point<3>>());
+        assert!(matches!("1,x".parse::<Point<2>>(), Err(ParseError::Number(_))));
+        assert_eq!("-1,0,7", Point([-1, 0, 7]).to_string());
+    }
+}
@@ -0,0 +1,276 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// What a step costs, `Default` is no cost at all.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<C: Copy + Ord + Add<Output = C> + Default> Cost for C {}
+
+/// Every state a search reached, with its cost from the start and the state it was reached from.
+#[derive(Debug, Clone)]
+pub struct Visited<S, C> {
+    costs: HashMap<S, C>,
+    previous: HashMap<S, S>,
+}
+
+impl<S: Eq + Hash + Clone, C: Copy> Visited<S, C> {
+    fn new(start: S) -> Self where C: Default {
+        Visited { costs: HashMap::from([(start, C::default())]), previous: HashMap::new() }
+    }
+
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.costs.get(state).copied()
+    }
+
+    pub fn contains(&self, state: &S) -> bool {
+        self.costs.contains_key(state)
+    }
+
+    pub fn len(&self) -> usize {
+        self.costs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.costs.is_empty()
+    }
+
+    pub fn states(&self) -> impl Iterator<Item = &S> {
+        self.costs.keys()
+    }
+
+    /// The states from the start to `target`, both included.
+    pub fn path_to(&self, target: &S) -> Option<Vec<S>> {
+        if !self.contains(target) {
+            return None;
+        }
+        let mut path = vec![target.clone()];
+        while let Some(previous) = self.previous.get(path.last().unwrap()) {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+// Every search takes the graph as a `neighbours` function from a state to the states next to
+// it, with the cost of each step for the weighted searches.
+
+/// The fewest steps from `start` to a state that is a `goal`, as the states along the way.
+pub fn bfs<S, I>(start: S, neighbours: impl FnMut(&S) -> I, goal: impl FnMut(&S) -> bool) -> Option<Vec<S>>
+    where S: Eq + Hash + Clone, I: IntoIterator<Item = S> {
+    let (visited, found) = traverse(start, neighbours, goal, Order::Breadth);
+    visited.path_to(&found?)
+}
+
+/// Every state reachable from `start`, the cost being the fewest steps to it.
+pub fn bfs_all<S, I>(start: S, neighbours: impl FnMut(&S) -> I) -> Visited<S, usize>
+    where S: Eq + Hash + Clone, I: IntoIterator<Item = S> {
+    traverse(start, neighbours, |_| false, Order::Breadth).0
+}
+
+/// A way, not necessarily the shortest, from `start` to a state that is a `goal`.
+pub fn dfs<S, I>(start: S, neighbours: impl FnMut(&S) -> I, goal: impl FnMut(&S) -> bool) -> Option<Vec<S>>
+    where S: Eq + Hash + Clone, I: IntoIterator<Item = S> {
+    let (visited, found) = traverse(start, neighbours, goal, Order::Depth);
+    visited.path_to(&found?)
+}
+
+/// Every state reachable from `start`, explored depth first, the cost being the depth at
+/// which it was found.
+pub fn dfs_all<S, I>(start: S, neighbours: impl FnMut(&S) -> I) -> Visited<S, usize>
+    where S: Eq + Hash + Clone, I: IntoIterator<Item = S> {
+    traverse(start, neighbours, |_| false, Order::Depth).0
+}
+
+enum Order {
+    Breadth,
+    Depth,
+}
+
+fn traverse<S, I>(start: S, mut neighbours: impl FnMut(&S) -> I, mut goal: impl FnMut(&S) -> bool, order: Order) -> (Visited<S, usize>, Option<S>)
+    where S: Eq + Hash + Clone, I: IntoIterator<Item = S> {
+    let mut visited = Visited { costs: HashMap::new(), previous: HashMap::new() };
+    let mut open = VecDeque::from([(start, None, 0)]);
+    let mut seen = HashSet::new();
+    while let Some((state, previous, depth)) = match order {
+        Order::Breadth => open.pop_front(),
+        Order::Depth => open.pop_back(),
+    } {
+        if !seen.insert(state.clone()) {
+            continue;
+        }
+        visited.costs.insert(state.clone(), depth);
+        if let Some(previous) = previous {
+            visited.previous.insert(state.clone(), previous);
+        }
+        if goal(&state) {
+            return (visited, Some(state));
+        }
+        for next in neighbours(&state) {
+            if !seen.contains(&next) {
+                open.push_back((next, Some(state.clone()), depth + 1));
+            }
+        }
+    }
+    (visited, None)
+}
+
+/// The cheapest way from `start` to a state that is a `goal`, with its cost.
+pub fn dijkstra<S, C, I>(start: S, neighbours: impl FnMut(&S) -> I, goal: impl FnMut(&S) -> bool) -> Option<(Vec<S>, C)>
+    where S: Eq + Hash + Clone, C: Cost, I: IntoIterator<Item = (S, C)> {
+    astar(start, neighbours, |_| C::default(), goal)
+}
+
+/// The cheapest cost of every state reachable from `start`.
+pub fn dijkstra_all<S, C, I>(start: S, neighbours: impl FnMut(&S) -> I) -> Visited<S, C>
+    where S: Eq + Hash + Clone, C: Cost, I: IntoIterator<Item = (S, C)> {
+    best_first(start, neighbours, |_| C::default(), |_| false).0
+}
+
+/// Like `dijkstra`, exploring the states with the lowest cost plus `heuristic` first. The
+/// heuristic must never be more than what reaching a goal really costs. It need not be
+/// consistent: a state is explored again when a cheaper way to it turns up later.
+pub fn astar<S, C, I>(start: S, neighbours: impl FnMut(&S) -> I, heuristic: impl FnMut(&S) -> C, goal: impl FnMut(&S) -> bool) -> Option<(Vec<S>, C)>
+    where S: Eq + Hash + Clone, C: Cost, I: IntoIterator<Item = (S, C)> {
+    let (visited, found) = best_first(start, neighbours, heuristic, goal);
+    let found = found?;
+    Some((visited.path_to(&found)?, visited.cost(&found)?))
+}
+
+/// A state waiting in the queue, the one with the lowest `priority` comes out first.
+struct Open<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> PartialEq for Open<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Open<S, C> {}
+
+impl<S, C: Ord> Ord for Open<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<S, C: Ord> PartialOrd for Open<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn best_first<S, C, I>(start: S, mut neighbours: impl FnMut(&S) -> I, mut heuristic: impl FnMut(&S) -> C, mut goal: impl FnMut(&S) -> bool) -> (Visited<S, C>, Option<S>)
+    where S: Eq + Hash + Clone, C: Cost, I: IntoIterator<Item = (S, C)> {
+    let mut visited = Visited::new(start.clone());
+    let mut queue = BinaryHeap::from([Open { priority: heuristic(&start), cost: C::default(), state: start }]);
+    while let Some(Open { cost, state, .. }) = queue.pop() {
+        // a cheaper way to the state was queued after this one
+        if visited.cost(&state).is_some_and(|best| best < cost) {
+            continue;
+        }
+        if goal(&state) {
+            return (visited, Some(state));
+        }
+        for (next, step) in neighbours(&state) {
+            let cost = cost + step;
+            if visited.cost(&next).is_none_or(|best| cost < best) {
+                visited.costs.insert(next.clone(), cost);
+                visited.previous.insert(next.clone(), state.clone());
+                queue.push(Open { priority: cost + heuristic(&next), cost, state: next });
+            }
+        }
+    }
+    (visited, None)
+}
+
+/// Every path from `start` that ends at its first `goal`. `neighbours` gets the path so far,
+/// so it can decide where the path may go next, and must keep the paths from going on forever.
+pub fn all_paths<S, I>(start: S, mut neighbours: impl FnMut(&[S]) -> I, mut goal: impl FnMut(&S) -> bool) -> Vec<Vec<S>>
+    where S: Clone, I: IntoIterator<Item = S> {
+    let mut paths = vec![];
+    let mut open = vec![vec![start]];
+    while let Some(path) = open.pop() {
+        if goal(path.last().unwrap()) {
+            paths.push(path);
+            continue;
+        }
+        for next in neighbours(&path) {
+            let mut longer = path.clone();
+            longer.push(next);
+            open.push(longer);
+        }
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A ring of ten states, each one step from the next and ten from the one before.
+    fn ring(&n: &u32) -> [(u32, u32); 2] {
+        [((n + 1) % 10, 1), ((n + 9) % 10, 10)]
+    }
+
+    #[test]
+    fn unweightedTest() {
+        let around = |&n: &u32| ring(&n).map(|(m, _)| m);
+        assert_eq!(Some(vec![0, 9, 8]), bfs(0, around, |&n| n == 8));
+        assert_eq!(None, bfs(0, |&n: &u32| (n < 5).then_some(n + 1), |&n| n == 8));
+        let visited = bfs_all(0, around);
+        assert_eq!(10, visited.len());
+        assert_eq!(Some(5), visited.cost(&5));
+        let path = dfs(0, around, |&n| n == 8).unwrap();
+        assert_eq!((Some(&0), Some(&8)), (path.first(), path.last()));
+        assert!(path.windows(2).all(|step| around(&step[0]).contains(&step[1])));
+        assert_eq!(10, dfs_all(0, around).len());
+    }
+
+    #[test]
+    fn weightedTest() {
+        assert_eq!(Some((vec![0, 1, 2, 3, 4, 5, 6, 7, 8], 8)), dijkstra(0, ring, |&n| n == 8));
+        assert_eq!(Some((vec![0, 9], 10)), dijkstra(0, |&n: &u32| [(n + 1, 20), (9, 10)], |&n| n == 9));
+        let visited = dijkstra_all(3, ring);
+        assert_eq!(Some(9), visited.cost(&2));
+        assert_eq!(Some(vec![3, 4, 5]), visited.path_to(&5));
+        let heuristic = |&n: &u32| 8u32.saturating_sub(n);
+        assert_eq!(Some((vec![0, 1, 2, 3, 4, 5, 6, 7, 8], 8)), astar(0, ring, heuristic, |&n| n == 8));
+        // 'b' looks far from the goal, so 'c' is first reached the expensive way through 'a'
+        let edges = |&n: &char| match n {
+            's' => vec![('a', 2), ('b', 4)],
+            'a' => vec![('c', 4)],
+            'b' => vec![('c', 1)],
+            'c' => vec![('g', 10)],
+            _ => vec![],
+        };
+        let inconsistent = |&n: &char| if n == 'b' { 10 } else { 0 };
+        assert_eq!(Some((vec!['s', 'b', 'c', 'g'], 15)), astar('s', edges, inconsistent, |&n| n == 'g'));
+    }
+
+    #[test]
+    fn allPathsTest() {
+        let edges = [("start", "a"), ("start", "b"), ("a", "b"), ("a", "end"), ("b", "end")];
+        let neighbours = |path: &[&'static str]| {
+            let at = *path.last().unwrap();
+            edges.iter()
+                .filter_map(move |&(v, w)| if v == at { Some(w) } else if w == at { Some(v) } else { None })
+                .filter(|next| !path.contains(next))
+                .collect::<Vec<_>>()
+        };
+        let mut paths = all_paths("start", neighbours, |&cave| cave == "end");
+        paths.sort();
+        assert_eq!(vec![
+            vec!["start", "a", "b", "end"],
+            vec!["start", "a", "end"],
+            vec!["start", "b", "a", "end"],
+            vec!["start", "b", "end"],
+        ], paths);
+    }
+}
@@ -0,0 +1,278 @@
+use std::collections::{BTreeSet, HashSet};
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+use crate::{Instruction, Machine};
+
+const HELP: &str = "\
+step [n], s    run the next n instructions, an empty line runs one
+back [n], r    undo the last n instructions
+continue, c    run to a breakpoint, a watched change, the end or a loop
+break pc N     stop before the instruction at N
+break cycle N  stop after the instruction running during cycle N
+delete pc N, delete cycle N
+watch, unwatch stop when the registers change
+print, p       where the program is
+list, l        the instructions around it
+quit, q";
+
+/// What stops `continue`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Breakpoint {
+    Pc(usize),
+    Cycle(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+    Step(usize),
+    Back(usize),
+    Continue,
+    Break(Breakpoint),
+    Delete(Breakpoint),
+    Watch(bool),
+    Print,
+    List,
+    Help,
+    Quit,
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let count = |word: Option<&&str>| match word {
+            None => Ok(1),
+            Some(n) => n.parse().map_err(|_| format!("not a number: {}", n)),
+        };
+        let breakpoint = |words: &[&str]| match words {
+            ["pc", n] => n.parse().map(Breakpoint::Pc).map_err(|_| format!("not a number: {}", n)),
+            ["cycle", n] => n.parse().map(Breakpoint::Cycle).map_err(|_| format!("not a number: {}", n)),
+            _ => Err(String::from("expected pc N or cycle N")),
+        };
+        match words.first().copied() {
+            None => Ok(Command::Step(1)),
+            Some("step" | "s") => count(words.get(1)).map(Command::Step),
+            Some("back" | "r") => count(words.get(1)).map(Command::Back),
+            Some("continue" | "c") => Ok(Command::Continue),
+            Some("break" | "b") => breakpoint(&words[1..]).map(Command::Break),
+            Some("delete" | "d") => breakpoint(&words[1..]).map(Command::Delete),
+            Some("watch" | "w") => Ok(Command::Watch(true)),
+            Some("unwatch") => Ok(Command::Watch(false)),
+            Some("print" | "p") => Ok(Command::Print),
+            Some("list" | "l") => Ok(Command::List),
+            Some("help" | "h") => Ok(Command::Help),
+            Some("quit" | "q") => Ok(Command::Quit),
+            Some(other) => Err(format!("unknown command {}, try help", other)),
+        }
+    }
+}
+
+/// Steps a machine through its program, forwards and back, on commands typed at a prompt.
+pub struct Debugger<I: Instruction> {
+    machine: Machine<I>,
+    breakpoints: BTreeSet<(usize, usize)>,
+    watch: bool,
+}
+
+impl<I: Instruction> Debugger<I> {
+    /// Debugs `machine` from where it is, recording its trace so it can step back.
+    pub fn new(machine: Machine<I>) -> Self {
+        Debugger { machine: machine.record(), breakpoints: BTreeSet::new(), watch: false }
+    }
+
+    /// Reads commands until `quit` or the end of `commands`, answering each on `output`.
+    pub fn run(&mut self, commands: &mut dyn BufRead, output: &mut dyn Write) -> io::Result<()> {
+        writeln!(output, "{}", self.location())?;
+        loop {
+            write!(output, "(vm) ")?;
+            output.flush()?;
+            let mut line = String::new();
+            if commands.read_line(&mut line)? == 0 {
+                return Ok(());
+            }
+            match line.parse() {
+                Ok(Command::Quit) => return Ok(()),
+                Ok(command) => writeln!(output, "{}", self.execute(command))?,
+                Err(e) => writeln!(output, "{}", e)?,
+            }
+        }
+    }
+
+    fn execute(&mut self, command: Command) -> String {
+        match command {
+            Command::Step(n) => {
+                let stepped = (0..n).take_while(|_| self.machine.step()).count();
+                self.report(stepped < n, "the program has finished")
+            }
+            Command::Back(n) => {
+                let undone = (0..n).take_while(|_| self.machine.back()).count();
+                self.report(undone < n, "this is where the trace starts")
+            }
+            Command::Continue => {
+                let reason = self.resume();
+                format!("{}\n{}", reason, self.location())
+            }
+            Command::Break(breakpoint) => {
+                self.breakpoints.insert(key(breakpoint));
+                format!("breakpoint at {}", describe(breakpoint))
+            }
+            Command::Delete(breakpoint) => match self.breakpoints.remove(&key(breakpoint)) {
+                true => format!("deleted the breakpoint at {}", describe(breakpoint)),
+                false => format!("there is no breakpoint at {}", describe(breakpoint)),
+            },
+            Command::Watch(watch) => {
+                self.watch = watch;
+                format!("{} the registers", if watch { "watching" } else { "not watching" })
+            }
+            Command::Print => self.location(),
+            Command::List => self.list(),
+            Command::Help => String::from(HELP),
+            Command::Quit => String::new(),
+        }
+    }
+
+    fn report(&self, cut_short: bool, why: &str) -> String {
+        match cut_short {
+            true => format!("{}\n{}", why, self.location()),
+            false => self.location(),
+        }
+    }
+
+    /// Runs until something stops it, returning what did.
+    fn resume(&mut self) -> String {
+        let mut executed = HashSet::new();
+        loop {
+            let before = self.machine.state().clone();
+            let Some(instruction) = self.machine.current().cloned() else {
+                return String::from("the program has finished");
+            };
+            if !executed.insert(before.pc) {
+                return format!("pc {} is about to run a second time", before.pc);
+            }
+            self.machine.step();
+            let after = self.machine.state();
+            if self.breakpoints.contains(&key(Breakpoint::Pc(after.pc))) {
+                return format!("breakpoint at {}", describe(Breakpoint::Pc(after.pc)));
+            }
+            // An instruction that takes no cycles runs during none, and the range would be empty.
+            if after.cycle > before.cycle {
+                if let Some(&(_, cycle)) = self.breakpoints.range(key(Breakpoint::Cycle(before.cycle + 1))..=key(Breakpoint::Cycle(after.cycle))).next() {
+                    return format!("breakpoint at cycle {}, during {} with registers {:?}", cycle, instruction, before.registers);
+                }
+            }
+            if self.watch && after.registers != before.registers {
+                return format!("the registers went from {:?} to {:?}", before.registers, after.registers);
+            }
+        }
+    }
+
+    /// Where the program is and the instruction it runs next.
+    fn location(&self) -> String {
+        let state = self.machine.state();
+        let next = match self.machine.current() {
+            Some(instruction) => instruction.to_string(),
+            None => String::from("halted"),
+        };
+        format!("pc {}, cycle {}, registers {:?}: {}", state.pc, state.cycle, state.registers, next)
+    }
+
+    /// The instructions around the program counter, marking it and the breakpoints.
+    fn list(&self) -> String {
+        let pc = self.machine.state().pc;
+        let program = self.machine.program();
+        let start = pc.saturating_sub(3).min(program.len());
+        let end = pc.saturating_add(4).min(program.len());
+        let lines: Vec<String> = (start..end).map(|i| {
+            let here = if i == pc { "=>" } else { "  " };
+            let breakpoint = if self.breakpoints.contains(&key(Breakpoint::Pc(i))) { "*" } else { " " };
+            format!("{}{} {:>4}  {}", here, breakpoint, i, program[i])
+        }).collect();
+        match lines.is_empty() {
+            true => String::from("the program counter is outside the program"),
+            false => lines.join("\n"),
+        }
+    }
+}
+
+/// Orders the breakpoints by kind and then by where they are, so those on cycles can be
+/// looked up by range.
+fn key(breakpoint: Breakpoint) -> (usize, usize) {
+    match breakpoint {
+        Breakpoint::Pc(pc) => (0, pc),
+        Breakpoint::Cycle(cycle) => (1, cycle),
+    }
+}
+
+fn describe(breakpoint: Breakpoint) -> String {
+    match breakpoint {
+        Breakpoint::Pc(pc) => format!("pc {}", pc),
+        Breakpoint::Cycle(cycle) => format!("cycle {}", cycle),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Toy;
+
+    fn debugger() -> Debugger<Toy> {
+        Debugger::new(Machine::new(vec![Toy::Add(3), Toy::Jump(2), Toy::Add(100), Toy::Add(-1), Toy::Jump(-3)], 0))
+    }
+
+    fn execute(debugger: &mut Debugger<Toy>, line: &str) -> String {
+        debugger.execute(line.parse().unwrap())
+    }
+
+    #[test]
+    fn commandTest() {
+        assert_eq!(Ok(Command::Step(1)), "".parse());
+        assert_eq!(Ok(Command::Back(3)), "r 3".parse());
+        assert_eq!(Ok(Command::Break(Breakpoint::Cycle(20))), "break cycle 20".parse());
+        assert_eq!(Ok(Command::Delete(Breakpoint::Pc(4))), "d pc 4".parse());
+        assert!("break 4".parse::<Command>().is_err());
+        assert!("step x".parse::<Command>().is_err());
+        assert!("jump".parse::<Command>().is_err());
+    }
+
+    #[test]
+    fn stepTest() {
+        let mut debugger = debugger();
+        assert_eq!("pc 1, cycle 2, registers 3: jump 2", execute(&mut debugger, "step"));
+        assert_eq!("pc 4, cycle 5, registers 2: jump -3", execute(&mut debugger, "s 2"));
+        assert_eq!("pc 3, cycle 3, registers 3: add -1", execute(&mut debugger, "back"));
+        assert_eq!("this is where the trace starts\npc 0, cycle 0, registers 0: add 3", execute(&mut debugger, "back 5"));
+        execute(&mut debugger, "b pc 1");
+        assert_eq!("=>     0  add 3\n  *    1  jump 2\n       2  add 100\n       3  add -1", execute(&mut debugger, "list"));
+    }
+
+    #[test]
+    fn continueTest() {
+        let mut debugger = debugger();
+        execute(&mut debugger, "break cycle 4");
+        assert_eq!("breakpoint at cycle 4, during add -1 with registers 3\npc 4, cycle 5, registers 2: jump -3", execute(&mut debugger, "c"));
+        assert_eq!("pc 4 is about to run a second time\npc 4, cycle 9, registers 1: jump -3", execute(&mut debugger, "c"));
+        execute(&mut debugger, "delete cycle 4");
+        execute(&mut debugger, "watch");
+        assert_eq!("the registers went from 1 to 0\npc 4, cycle 13, registers 0: jump -3", execute(&mut debugger, "c"));
+        execute(&mut debugger, "break pc 1");
+        assert_eq!("breakpoint at pc 1\npc 1, cycle 14, registers 0: jump 2", execute(&mut debugger, "c"));
+
+        let mut finished = Debugger::new(Machine::new(vec![Toy::Add(1), Toy::Jump(1)], 0));
+        assert_eq!("the program has finished\npc 2, cycle 3, registers 1: halted", execute(&mut finished, "c"));
+        assert_eq!("the program has finished\npc 2, cycle 3, registers 1: halted", execute(&mut finished, "s"));
+
+        let mut instant = Debugger::new(Machine::new(vec![Toy::Nop, Toy::Add(1), Toy::Nop], 0));
+        execute(&mut instant, "break cycle 2");
+        assert_eq!("breakpoint at cycle 2, during add 1 with registers 0\npc 2, cycle 2, registers 1: nop", execute(&mut instant, "c"));
+        assert_eq!("the program has finished\npc 3, cycle 2, registers 1: halted", execute(&mut instant, "c"));
+    }
+
+    #[test]
+    fn runTest() {
+        let mut output = vec![];
+        debugger().run(&mut "s\nfoo\nq\ns\n".as_bytes(), &mut output).unwrap();
+        assert_eq!("pc 0, cycle 0, registers 0: add 3\n(vm) pc 1, cycle 2, registers 3: jump 2\n(vm) unknown command foo, try help\n(vm) ",
+                   String::from_utf8(output).unwrap());
+    }
+}
@@ -0,0 +1,252 @@
+#![allow(non_snake_case)]
+
+//! A machine that runs the little assembly programs of the puzzles, one instruction after the
+//! other, with the instructions and registers left to each puzzle.
+
+use std::collections::HashSet;
+use std::fmt::{Debug, Display};
+
+pub mod debugger;
+pub mod registry;
+
+pub use debugger::Debugger;
+
+#[doc(hidden)]
+pub use inventory;
+
+/// What an instruction set has to say about its instructions.
+pub trait Instruction: Clone + Debug + Display {
+    type Registers: Clone + Debug + PartialEq;
+
+    /// How many cycles the instruction takes, the registers change at the end of the last one.
+    fn cycles(&self) -> usize;
+
+    fn execute(&self, registers: &mut Self::Registers) -> Flow;
+}
+
+/// Where the program goes after an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    Next,
+    /// Jumps this many instructions forwards, or backwards when negative.
+    Jump(isize),
+}
+
+/// Why a machine stopped running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Halt {
+    /// The program counter left the program.
+    Finished,
+    /// The instruction at `pc` was about to run a second time.
+    Loop { pc: usize },
+}
+
+/// Where a machine is: `cycle` cycles have passed and `pc` is the instruction to run next.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct State<R> {
+    pub pc: usize,
+    pub cycle: usize,
+    pub registers: R,
+}
+
+pub struct Machine<I: Instruction> {
+    program: Vec<I>,
+    state: State<I::Registers>,
+    /// The state before every instruction run so far, when recording.
+    trace: Option<Vec<State<I::Registers>>>,
+}
+
+impl<I: Instruction> Machine<I> {
+    pub fn new(program: Vec<I>, registers: I::Registers) -> Self {
+        Machine { program, state: State { pc: 0, cycle: 0, registers }, trace: None }
+    }
+
+    /// Keeps the state before every instruction from now on, see `trace`.
+    pub fn record(mut self) -> Self {
+        self.trace = Some(vec![]);
+        self
+    }
+
+    pub fn program(&self) -> &[I] {
+        &self.program
+    }
+
+    pub fn state(&self) -> &State<I::Registers> {
+        &self.state
+    }
+
+    pub fn registers(&self) -> &I::Registers {
+        &self.state.registers
+    }
+
+    /// The instruction to run next, none when the machine has halted.
+    pub fn current(&self) -> Option<&I> {
+        self.program.get(self.state.pc)
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.current().is_none()
+    }
+
+    /// The states before every instruction run since `record`, oldest first.
+    pub fn trace(&self) -> &[State<I::Registers>] {
+        self.trace.as_deref().unwrap_or(&[])
+    }
+
+    /// Goes back to the state before the last instruction run since `record`, false when
+    /// there is none. Patches stay as they are.
+    pub fn back(&mut self) -> bool {
+        match self.trace.as_mut().and_then(|trace| trace.pop()) {
+            Some(state) => {
+                self.state = state;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Puts `instruction` at `pc` in the program, giving back the one that was there. `None`
+    /// leaves the program as it is when `pc` is past its end.
+    pub fn patch(&mut self, pc: usize, instruction: I) -> Option<I> {
+        self.program.get_mut(pc).map(|old| std::mem::replace(old, instruction))
+    }
+
+    /// Runs the next instruction, false when there is none.
+    pub fn step(&mut self) -> bool {
+        self.step_with(|_| {})
+    }
+
+    /// Runs the next instruction, calling `hook` with the state during each of its cycles,
+    /// which is before the registers change.
+    pub fn step_with(&mut self, mut hook: impl FnMut(&State<I::Registers>)) -> bool {
+        let Some(instruction) = self.program.get(self.state.pc) else {
+            return false;
+        };
+        if let Some(trace) = &mut self.trace {
+            trace.push(self.state.clone());
+        }
+        for _ in 0..instruction.cycles() {
+            self.state.cycle += 1;
+            hook(&self.state);
+        }
+        self.state.pc = match instruction.execute(&mut self.state.registers) {
+            Flow::Next => self.state.pc + 1,
+            // a jump before the start wraps around past the end, which halts all the same
+            Flow::Jump(offset) => self.state.pc.wrapping_add_signed(offset),
+        };
+        true
+    }
+
+    pub fn run(&mut self) -> Halt {
+        self.run_with(|_| {})
+    }
+
+    /// Runs until the program counter leaves the program or an instruction comes up a second
+    /// time, calling `hook` during every cycle as `step_with` does. The loop check only looks
+    /// at the program counter, so it suits programs whose jumps do not depend on the registers.
+    pub fn run_with(&mut self, mut hook: impl FnMut(&State<I::Registers>)) -> Halt {
+        let mut executed = HashSet::new();
+        while !self.is_halted() {
+            if !executed.insert(self.state.pc) {
+                return Halt::Loop { pc: self.state.pc };
+            }
+            self.step_with(&mut hook);
+        }
+        Halt::Finished
+    }
+}
+
+/// Every program that is `program` with a single instruction changed by `patch`, in the order
+/// of the instructions, skipping those `patch` leaves alone.
+pub fn patches<'a, I: Clone>(program: &'a [I], patch: impl Fn(&I) -> Option<I> + 'a) -> impl Iterator<Item = Vec<I>> + 'a {
+    program.iter().enumerate().filter_map(move |(pc, instruction)| {
+        let patched = patch(instruction)?;
+        let mut copy = program.to_vec();
+        copy[pc] = patched;
+        Some(copy)
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::fmt;
+
+    /// Adds to the only register in two cycles, jumps in one, or does nothing in none.
+    #[derive(Debug, Clone, PartialEq)]
+    pub(crate) enum Toy {
+        Add(i64),
+        Jump(isize),
+        Nop,
+    }
+
+    impl fmt::Display for Toy {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                Toy::Add(n) => write!(f, "add {}", n),
+                Toy::Jump(offset) => write!(f, "jump {}", offset),
+                Toy::Nop => write!(f, "nop"),
+            }
+        }
+    }
+
+    impl Instruction for Toy {
+        type Registers = i64;
+
+        fn cycles(&self) -> usize {
+            match self {
+                Toy::Add(_) => 2,
+                Toy::Jump(_) => 1,
+                Toy::Nop => 0,
+            }
+        }
+
+        fn execute(&self, register: &mut i64) -> Flow {
+            match self {
+                Toy::Add(n) => {
+                    *register += n;
+                    Flow::Next
+                }
+                Toy::Jump(offset) => Flow::Jump(*offset),
+                Toy::Nop => Flow::Next,
+            }
+        }
+    }
+
+    #[test]
+    fn runTest() {
+        let mut machine = Machine::new(vec![Toy::Add(3), Toy::Jump(2), Toy::Add(100), Toy::Add(-1)], 0);
+        let mut during = vec![];
+        assert_eq!(Halt::Finished, machine.run_with(|state| during.push((state.cycle, state.registers))));
+        assert_eq!(vec![(1, 0), (2, 0), (3, 3), (4, 3), (5, 3)], during);
+        assert_eq!(&State { pc: 4, cycle: 5, registers: 2 }, machine.state());
+        assert!(!machine.step());
+
+        let mut backwards = Machine::new(vec![Toy::Jump(-1)], 0);
+        assert_eq!(Halt::Finished, backwards.run());
+    }
+
+    #[test]
+    fn loopTest() {
+        let program = vec![Toy::Add(1), Toy::Add(2), Toy::Jump(-1)];
+        let mut machine = Machine::new(program.clone(), 0).record();
+        assert_eq!(Halt::Loop { pc: 1 }, machine.run());
+        assert_eq!(3, *machine.registers());
+        assert_eq!(vec![0, 1, 2], machine.trace().iter().map(|state| state.pc).collect::<Vec<_>>());
+        assert_eq!(Some(&Toy::Add(2)), machine.current());
+        assert!(machine.back());
+        assert_eq!(&State { pc: 2, cycle: 4, registers: 3 }, machine.state());
+        assert_eq!(2, machine.trace().len());
+        assert!(!Machine::new(program.clone(), 0).back());
+
+        let flip = |instruction: &Toy| match instruction {
+            Toy::Jump(_) => Some(Toy::Add(0)),
+            _ => None,
+        };
+        let fixed: Vec<Vec<Toy>> = patches(&program, flip).collect();
+        assert_eq!(vec![vec![Toy::Add(1), Toy::Add(2), Toy::Add(0)]], fixed);
+        assert_eq!(None, machine.patch(3, Toy::Add(0)));
+        assert_eq!(Some(Toy::Jump(-1)), machine.patch(2, Toy::Add(0)));
+        assert_eq!(Halt::Finished, machine.run());
+    }
+}
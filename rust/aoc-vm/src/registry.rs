@@ -0,0 +1,47 @@
+use std::io::{self, BufRead, Write};
+
+/// How to debug the program of a day, submitted with `register!` and found by `aoc debug`.
+pub struct Registration {
+    year: u16,
+    day: u8,
+    debug: fn(&str, &mut dyn BufRead, &mut dyn Write) -> io::Result<()>,
+}
+
+impl Registration {
+    pub const fn new(year: u16, day: u8, debug: fn(&str, &mut dyn BufRead, &mut dyn Write) -> io::Result<()>) -> Self {
+        Registration { year, day, debug }
+    }
+
+    /// Loads the program in `input` and debugs it with the `commands`, until they run out.
+    pub fn debug(&self, input: &str, commands: &mut dyn BufRead, output: &mut dyn Write) -> io::Result<()> {
+        (self.debug)(input, commands, output)
+    }
+}
+
+inventory::collect!(Registration);
+
+pub fn find(year: u16, day: u8) -> Option<&'static Registration> {
+    inventory::iter::<Registration>.into_iter().find(|r| r.year == year && r.day == day)
+}
+
+/// The year and day of every program that can be debugged, in order.
+pub fn programs() -> Vec<(u16, u8)> {
+    let mut programs: Vec<(u16, u8)> = inventory::iter::<Registration>.into_iter().map(|r| (r.year, r.day)).collect();
+    programs.sort();
+    programs
+}
+
+/// Lets `aoc debug` step through the program of a day, given a function loading the
+/// `Machine` from the puzzle input, or returning why it could not.
+#[macro_export]
+macro_rules! register {
+    ($year:expr, $day:expr, $machine:expr) => {
+        $crate::inventory::submit! {
+            $crate::registry::Registration::new($year, $day, |input, commands, output| {
+                let machine = ($machine)(input)
+                    .map_err(|e| ::std::io::Error::new(::std::io::ErrorKind::InvalidData, e.to_string()))?;
+                $crate::Debugger::new(machine).run(commands, output)
+            })
+        }
+    };
+}
@@ -0,0 +1,42 @@
+use std::io;
+use crate::input::Source;
+use crate::{years, DebugArgs};
+
+/// Loads the program of a day into the debugger and hands it the commands typed on stdin.
+pub fn debug(args: DebugArgs) -> Result<(), String> {
+    let registration = aoc_vm::registry::find(args.year, args.day).ok_or_else(|| {
+        let programs: Vec<String> = aoc_vm::registry::programs().iter()
+            .map(|(year, day)| format!("{} day {}", year, day))
+            .collect();
+        format!("There is no program to debug for {} day {}, only for {}", args.year, args.day, programs.join(", "))
+    })?;
+    let year = years::select(Some(args.year))?.remove(0);
+    let source = Source::new(args.input.as_deref(), Some(args.day))?;
+    if let Source::Stdin = source {
+        return Err(String::from("The debugger reads its commands from stdin, give the input as a file"));
+    }
+    let input = source.load(&year, args.day)
+        .map_err(|e| format!("Failed to read the input of day {}: {}", args.day, e))?;
+    registration.debug(&input, &mut io::stdin().lock(), &mut io::stdout())
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn programsTest() {
+        // the years link the days in, which register their programs
+        assert!(years::all().len() > 1);
+        assert_eq!(vec![(2020, 8), (2022, 10)], aoc_vm::registry::programs());
+        let mut output = vec![];
+        aoc_vm::registry::find(2020, 8).unwrap()
+            .debug("nop +0\nacc +1\njmp -2", &mut "c\n".as_bytes(), &mut output).unwrap();
+        assert_eq!("pc 0, cycle 0, registers 0: nop +0\n(vm) pc 0 is about to run a second time\npc 0, cycle 3, registers 1: nop +0\n(vm) ",
+                   String::from_utf8(output).unwrap());
+        let bad = aoc_vm::registry::find(2022, 10).unwrap().debug("noop\naddx", &mut "c\n".as_bytes(), &mut vec![]);
+        assert_eq!("day 10, line 2: could not parse \"addx\"", bad.unwrap_err().to_string());
+        assert!(debug(DebugArgs { year: 2021, day: 8, input: None }).is_err());
+    }
+}
@@ -0,0 +1,20 @@
+use aoc_client::{Client, Config, Fetched};
+use crate::{years, FetchArgs};
+
+/// Downloads the inputs of the selected days that are not there yet.
+pub fn fetch(args: FetchArgs) -> Result<(), String> {
+    let client = Client::new(&Config::load().map_err(|e| e.to_string())?).map_err(|e| e.to_string())?;
+    let year = years::select(Some(args.year))?.remove(0);
+    let days: Vec<u8> = match args.day {
+        Some(day) => vec![day],
+        None => year.solutions().iter().map(|s| s.day()).collect(),
+    };
+    for day in days {
+        match client.fetch(year.year(), year.inputs(), day) {
+            Ok(Fetched::Cached(path)) => println!("Day {}: already in {}", day, path.display()),
+            Ok(Fetched::Downloaded(path)) => println!("Day {}: downloaded to {}", day, path.display()),
+            Err(e) => return Err(format!("Failed to fetch {} day {}: {}", year.year(), day, e)),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use aoc_core::{Inputs, Year};
+
+/// Where `--input` says to read the puzzle input from.
+pub enum Source {
+    /// Those in `AOC_INPUT_DIR` and then the year's own inputs.
+    Year,
+    /// A directory of inputs, named either way, directly in it or in a subdirectory per year.
+    Dir(PathBuf),
+    /// The input of the one selected day.
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    pub fn new(input: Option<&Path>, day: Option<u8>) -> Result<Source, String> {
+        let source = match input {
+            None => return Ok(Source::Year),
+            Some(path) if path == Path::new("-") => Source::Stdin,
+            Some(path) if path.is_dir() => return Ok(Source::Dir(path.to_path_buf())),
+            Some(path) => Source::File(path.to_path_buf()),
+        };
+        match day {
+            Some(_) => Ok(source),
+            None => Err(String::from("An input file or stdin can only be used for a single --day")),
+        }
+    }
+
+    pub fn inputs(&self, year: &Year) -> Inputs {
+        match self {
+            Source::Dir(dir) => year.inputs().within(dir, year.year()),
+            _ => year.inputs().clone(),
+        }
+    }
+
+    pub fn load(&self, year: &Year, day: u8) -> io::Result<String> {
+        match self {
+            Source::File(path) => fs::read_to_string(path),
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+            _ => self.inputs(year).load(day),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sourceTest() {
+        assert!(matches!(Source::new(None, None), Ok(Source::Year)));
+        assert!(matches!(Source::new(Some(Path::new("-")), Some(1)), Ok(Source::Stdin)));
+        assert!(Source::new(Some(Path::new("-")), None).is_err());
+        assert!(matches!(Source::new(Some(Path::new("day1.txt")), Some(1)), Ok(Source::File(_))));
+        assert!(Source::new(Some(Path::new("day1.txt")), None).is_err());
+        assert!(matches!(Source::new(Some(&std::env::temp_dir()), None), Ok(Source::Dir(_))));
+    }
+}
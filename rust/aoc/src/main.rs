@@ -0,0 +1,281 @@
+#![allow(non_snake_case)]
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+use clap::{Args, Parser, Subcommand};
+use aoc_core::{runner, DayReport, Format, Output, Part};
+use input::Source;
+
+mod debug;
+mod fetch;
+mod input;
+mod new;
+mod status;
+mod submit;
+mod verify;
+mod watch;
+mod years;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Solves Advent of Code puzzles")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve the selected puzzles, every day of every year by default
+    Run(RunArgs),
+    /// Check the answers against each year's answers.toml
+    Verify(VerifyArgs),
+    /// Download puzzle inputs that are not there yet, with the session token in
+    /// $AOC_SESSION or ~/.config/aoc/config.toml
+    Fetch(FetchArgs),
+    /// Submit the answer to one part, refusing answers the history already knows are wrong,
+    /// and refusing while a cool-down is running with the seconds left
+    Submit(SubmitArgs),
+    /// Solve every day and show a calendar of the stars of each year, with the parts that
+    /// are not implemented, return placeholders or have no tests
+    Status(StatusArgs),
+    /// Test and solve a day again whenever its input, its examples, its year's code or the
+    /// shared crates change, pointing out answers that changed
+    Watch(WatchArgs),
+    /// Add a day to its year from the template, leaving its input for fetch
+    New(NewArgs),
+    /// Step through the program of an assembly-style day, with breakpoints, watches and
+    /// stepping back, reading commands from stdin
+    Debug(DebugArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Only solve the puzzles of this year
+    #[arg(long)]
+    year: Option<u16>,
+    /// Only solve this day
+    #[arg(long, requires = "year")]
+    day: Option<u8>,
+    /// Only solve this part, 1 or 2
+    #[arg(long)]
+    part: Option<Part>,
+    /// Read the inputs from this directory, or the day's input from this file or from
+    /// stdin with `-`, instead of $AOC_INPUT_DIR or the year's resource directory
+    #[arg(long)]
+    input: Option<PathBuf>,
+    /// Solve every part this many times and report the min, median and max times
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    runs: u32,
+    /// Write the results as text, as JSON lines or as CSV, one record per part
+    #[arg(long, default_value_t = Format::Text)]
+    format: Format,
+    #[command(flatten)]
+    execution: Execution,
+}
+
+#[derive(Args)]
+struct FetchArgs {
+    #[arg(long)]
+    year: u16,
+    /// Only fetch this day, every day with a solution by default
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+}
+
+#[derive(Args)]
+struct SubmitArgs {
+    #[arg(long)]
+    year: u16,
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    #[arg(long)]
+    part: Part,
+    /// The answer to submit, the day is solved for it when left out
+    answer: Option<String>,
+}
+
+#[derive(Args)]
+struct StatusArgs {
+    /// Only show this year
+    #[arg(long)]
+    year: Option<u16>,
+    #[command(flatten)]
+    execution: Execution,
+}
+
+#[derive(Args)]
+struct WatchArgs {
+    #[arg(long)]
+    year: u16,
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+}
+
+#[derive(Args)]
+struct NewArgs {
+    #[arg(long)]
+    year: u16,
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+}
+
+#[derive(Args)]
+struct DebugArgs {
+    #[arg(long)]
+    year: u16,
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// Load the program from this file instead of the day's input, see `run --input`
+    #[arg(long)]
+    input: Option<PathBuf>,
+}
+
+/// How the days are solved, shared by `run`, `verify` and `status`.
+#[derive(Args)]
+struct Execution {
+    /// Solve this many days at the same time. Their times will be less accurate
+    #[arg(long, short, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    jobs: u32,
+    /// Report a day that takes longer than this many seconds as failed and move on
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    timeout: Option<u64>,
+}
+
+impl Execution {
+    fn timeout(&self) -> Option<Duration> {
+        self.timeout.map(Duration::from_secs)
+    }
+}
+
+#[derive(Args)]
+struct VerifyArgs {
+    /// Only verify the puzzles of this year
+    #[arg(long)]
+    year: Option<u16>,
+    /// Only verify this day
+    #[arg(long, requires = "year")]
+    day: Option<u8>,
+    /// Read the inputs from this directory, see `run --input`. The answers stay in
+    /// the year's own answers.toml
+    #[arg(long)]
+    input: Option<PathBuf>,
+    /// Add answers that are not known yet to answers.toml, except placeholders like 0
+    #[arg(long)]
+    record: bool,
+    #[command(flatten)]
+    execution: Execution,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Run(args) => run(args),
+        Command::Verify(args) => verify::verify(args),
+        Command::Fetch(args) => fetch::fetch(args),
+        Command::Submit(args) => submit::submit(args),
+        Command::Status(args) => status::status(args),
+        Command::Watch(args) => watch::watch(args),
+        Command::New(args) => new::new(args),
+        Command::Debug(args) => debug::debug(args),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: RunArgs) -> Result<(), String> {
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+    let source = Source::new(args.input.as_deref(), args.day)?;
+    let mut output = Output::new(args.format);
+    let mut failed = 0;
+    for year in years::select(args.year)? {
+        let solutions = years::days(&year, args.day)?;
+        if output.format() == Format::Text {
+            println!("=======================================");
+            println!("Advent of Code {}", year.year());
+        }
+        let days = solutions.into_iter()
+            .map(|solution| (solution, source.load(&year, solution.day())))
+            .collect();
+        let mut reports: Vec<DayReport> = vec![];
+        let execution = &args.execution;
+        runner::solve_all(days, &parts, args.runs as usize, execution.timeout(), execution.jobs as usize, |report| {
+            output.write(&report, &parts);
+            reports.push(report);
+        });
+        if reports.len() > 1 && output.format() == Format::Text {
+            runner::print_summary(year.year(), &reports);
+        }
+        failed += reports.iter().filter(|r| !r.solved()).count();
+    }
+    match failed {
+        0 => Ok(()),
+        1 => Err(String::from("1 day failed")),
+        n => Err(format!("{} days failed", n)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn cliTest() {
+        Cli::command().debug_assert();
+        let cli = Cli::try_parse_from(["aoc", "run", "--year", "2022", "--day", "15", "--part", "2"]).unwrap();
+        let Command::Run(args) = cli.command else { panic!("expected the run command") };
+        assert_eq!(Some(2022), args.year);
+        assert_eq!(Some(15), args.day);
+        assert_eq!(Some(Part::Two), args.part);
+        assert_eq!(1, args.runs);
+        assert_eq!(Format::Text, args.format);
+        assert_eq!((1, None), (args.execution.jobs, args.execution.timeout()));
+        let cli = Cli::try_parse_from(["aoc", "verify", "-j", "8", "--timeout", "30"]).unwrap();
+        let Command::Verify(args) = cli.command else { panic!("expected the verify command") };
+        assert_eq!((8, Some(Duration::from_secs(30))), (args.execution.jobs, args.execution.timeout()));
+        assert!(Cli::try_parse_from(["aoc", "run", "--jobs", "0"]).is_err());
+        let cli = Cli::try_parse_from(["aoc", "fetch", "--year", "2022", "--day", "16"]).unwrap();
+        let Command::Fetch(args) = cli.command else { panic!("expected the fetch command") };
+        assert_eq!((2022, Some(16)), (args.year, args.day));
+        assert!(Cli::try_parse_from(["aoc", "fetch", "--day", "16"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "fetch", "--year", "2022", "--day", "26"]).is_err());
+        let cli = Cli::try_parse_from(["aoc", "submit", "--year", "2022", "--day", "5", "--part", "1", "CMZ"]).unwrap();
+        let Command::Submit(args) = cli.command else { panic!("expected the submit command") };
+        assert_eq!((Part::One, Some(String::from("CMZ"))), (args.part, args.answer));
+        assert!(Cli::try_parse_from(["aoc", "submit", "--year", "2022", "--day", "5"]).is_err());
+        let cli = Cli::try_parse_from(["aoc", "new", "--year", "2022", "--day", "16"]).unwrap();
+        let Command::New(args) = cli.command else { panic!("expected the new command") };
+        assert_eq!((2022, 16), (args.year, args.day));
+        assert!(Cli::try_parse_from(["aoc", "new", "--year", "2022", "--day", "0"]).is_err());
+        let cli = Cli::try_parse_from(["aoc", "watch", "--year", "2022", "--day", "5"]).unwrap();
+        let Command::Watch(args) = cli.command else { panic!("expected the watch command") };
+        assert_eq!((2022, 5), (args.year, args.day));
+        let cli = Cli::try_parse_from(["aoc", "status", "--year", "2021", "-j", "4"]).unwrap();
+        let Command::Status(args) = cli.command else { panic!("expected the status command") };
+        assert_eq!((Some(2021), 4), (args.year, args.execution.jobs));
+        let cli = Cli::try_parse_from(["aoc", "run", "--format", "csv"]).unwrap();
+        let Command::Run(args) = cli.command else { panic!("expected the run command") };
+        assert_eq!(Format::Csv, args.format);
+        assert!(Cli::try_parse_from(["aoc", "run", "--format", "xml"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run", "--runs", "0"]).is_err());
+        let cli = Cli::try_parse_from(["aoc", "verify", "--year", "2020", "--record"]).unwrap();
+        let Command::Verify(args) = cli.command else { panic!("expected the verify command") };
+        assert_eq!((Some(2020), None, true), (args.year, args.day, args.record));
+        assert!(Cli::try_parse_from(["aoc", "run", "--day", "15"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run", "--year", "2022", "--day", "1", "--input", "-"]).is_ok());
+        assert!(Cli::try_parse_from(["aoc", "run", "--part", "3"]).is_err());
+        let cli = Cli::try_parse_from(["aoc", "debug", "--year", "2020", "--day", "8", "--input", "boot.txt"]).unwrap();
+        let Command::Debug(args) = cli.command else { panic!("expected the debug command") };
+        assert_eq!((2020, 8, Some(PathBuf::from("boot.txt"))), (args.year, args.day, args.input));
+        assert!(Cli::try_parse_from(["aoc", "debug", "--year", "2020"]).is_err());
+    }
+}
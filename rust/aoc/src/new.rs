@@ -0,0 +1,151 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use aoc_core::input::spell;
+use aoc_core::Naming;
+use crate::{years, NewArgs};
+
+/// Adds a day to its year's crate: the module from the template and its `pub mod` in
+/// `lib.rs`. The input is left for `aoc fetch` to download. An existing day is never
+/// overwritten.
+pub fn new(args: NewArgs) -> Result<(), String> {
+    let year = years::select(Some(args.year))
+        .map_err(|e| format!("{}, a new year needs a crate of its own first", e))?
+        .remove(0);
+    if year.solution(args.day).is_some() {
+        return Err(format!("{} day {} already has a solution", year.year(), args.day));
+    }
+    let module = scaffold(year.dir(), args.day, year.inputs().naming())?;
+    println!("Wrote {}", module.display());
+
+    let input = year.inputs().path(args.day);
+    if input.exists() {
+        println!("Kept the input in {}", input.display());
+    } else {
+        println!("Fetch the input with aoc fetch --year {} --day {}", year.year(), args.day);
+    }
+    Ok(())
+}
+
+/// Where the module of `day` is in the crate in `dir`, `src/day15.rs` or `src/dayFifteen.rs`.
+pub fn module_path(dir: &Path, day: u8, naming: Naming) -> PathBuf {
+    dir.join("src").join(format!("day{}.rs", naming.name(day)))
+}
+
+/// Writes the module of `day` into the crate in `dir` and declares it in its `lib.rs`,
+/// leaving neither behind when the other cannot be written.
+fn scaffold(dir: &Path, day: u8, naming: Naming) -> Result<PathBuf, String> {
+    let module = format!("day{}", naming.name(day));
+    let path = module_path(dir, day, naming);
+    let lib_path = dir.join("src").join("lib.rs");
+    let lib = fs::read_to_string(&lib_path).map_err(|e| format!("Failed to read {}: {}", lib_path.display(), e))?;
+    if path.exists() {
+        return Err(format!("{} already exists", path.display()));
+    }
+    if lib.lines().any(|line| line.trim() == format!("pub mod {};", module)) {
+        return Err(format!("{} already declares {}", lib_path.display(), module));
+    }
+
+    let lib = declare(&lib, &module, day).ok_or_else(|| format!("No modules to add {} next to in {}", module, lib_path.display()))?;
+    fs::write(&path, template(day)).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    if let Err(e) = fs::write(&lib_path, lib) {
+        let _ = fs::remove_file(&path);
+        return Err(format!("Failed to write {}: {}", lib_path.display(), e));
+    }
+    Ok(path)
+}
+
+/// `lib` with `pub mod <module>;` added among the days, in day order.
+fn declare(lib: &str, module: &str, day: u8) -> Option<String> {
+    let mut lines: Vec<&str> = lib.lines().collect();
+    let modules: Vec<usize> = (0..lines.len()).filter(|&i| lines[i].starts_with("pub mod ")).collect();
+    let days: Vec<usize> = modules.iter().copied().filter(|&i| module_day(lines[i]).is_some()).collect();
+    let at = match days.iter().rev().find(|&&i| module_day(lines[i]) < Some(day)) {
+        Some(&before) => before + 1,
+        None => days.first().copied().or(modules.last().map(|&last| last + 1))?,
+    };
+    let declaration = format!("pub mod {};", module);
+    lines.insert(at, &declaration);
+    Some(lines.join("\n") + "\n")
+}
+
+/// The day of a `pub mod day15;` or `pub mod dayFifteen;` line.
+fn module_day(line: &str) -> Option<u8> {
+    let name = line.strip_prefix("pub mod day")?.strip_suffix(';')?;
+    name.parse().ok().or_else(|| (1..=25).find(|&day| spell(day) == name))
+}
+
+fn template(day: u8) -> String {
+    let name = format!("Day{}", spell(day));
+    format!(r#"use aoc_core::AocDay;
+
+#[derive(AocDay)]
+#[aoc(day = {day}, answer = u64)]
+pub struct {name} {{
+    input: String
+}}
+
+// A part answers 0 until it is solved, which aoc status and aoc verify take for not
+// implemented.
+impl {name} {{
+    fn part_one(&self) -> u64 {{
+        let _ = &self.input;
+        0
+    }}
+
+    fn part_two(&self) -> u64 {{
+        0
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    aoc_core::aoc_examples!({name},
+        part_one: [],
+        part_two: []);
+}}
+"#)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn declareTest() {
+        let lib = "#![allow(non_snake_case)]\n\npub mod core;\npub mod day1;\npub mod day15;\n\npub const YEAR: u16 = 2022;\n";
+        assert_eq!("#![allow(non_snake_case)]\n\npub mod core;\npub mod day1;\npub mod day9;\npub mod day15;\n\npub const YEAR: u16 = 2022;\n",
+                   declare(lib, "day9", 9).unwrap());
+        assert!(declare(lib, "day16", 16).unwrap().contains("pub mod day15;\npub mod day16;\n\n"));
+        let lib = "pub mod core;\npub mod dayTwo;\n";
+        assert_eq!("pub mod core;\npub mod dayOne;\npub mod dayTwo;\n", declare(lib, "dayOne", 1).unwrap());
+        assert_eq!("pub mod core;\npub mod day3;\n", declare("pub mod core;\n", "day3", 3).unwrap());
+        assert_eq!(None, declare("", "day3", 3));
+    }
+
+    #[test]
+    fn scaffoldTest() {
+        let dir = std::env::temp_dir().join("aoc-new-scaffold");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src").join("lib.rs"), "pub mod core;\npub mod dayFifteen;\n").unwrap();
+
+        let path = scaffold(&dir, 16, Naming::Spelled).unwrap();
+        assert_eq!(dir.join("src").join("daySixteen.rs"), path);
+        let module = fs::read_to_string(&path).unwrap();
+        assert!(module.contains("#[aoc(day = 16, answer = u64)]\npub struct DaySixteen {"));
+        assert!(module.contains("aoc_core::aoc_examples!(DaySixteen,"));
+        assert!(!module.contains("todo!"));
+        assert!(fs::read_to_string(dir.join("src").join("lib.rs")).unwrap().ends_with("pub mod daySixteen;\n"));
+
+        fs::write(&path, "// solved").unwrap();
+        assert!(scaffold(&dir, 16, Naming::Spelled).is_err());
+        assert_eq!("// solved", fs::read_to_string(&path).unwrap());
+
+        fs::write(dir.join("src").join("lib.rs"), "").unwrap();
+        assert!(scaffold(&dir, 17, Naming::Spelled).is_err());
+        assert!(!module_path(&dir, 17, Naming::Spelled).exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
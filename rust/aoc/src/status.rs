@@ -0,0 +1,211 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::Command;
+use aoc_core::answers::is_placeholder;
+use aoc_core::{runner, Answers, Error, Part};
+use crate::new::module_path;
+use crate::{years, StatusArgs};
+
+/// Where one part of one day stands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Progress {
+    /// The answer is the one in answers.toml.
+    Star,
+    /// There is an answer, but no known answer to compare it with.
+    Answered,
+    Wrong,
+    /// An answer like the `0` of a solver that was never written.
+    Placeholder,
+    NotImplemented,
+    Failed(String),
+    NoSolution,
+}
+
+impl Progress {
+    fn of(expected: Option<&str>, answer: Result<Option<&str>, &Error>) -> Self {
+        match answer {
+            // a placeholder that made it into answers.toml is no star either
+            Ok(Some(answer)) if is_placeholder(answer) => Progress::Placeholder,
+            Ok(Some(answer)) if expected == Some(answer) => Progress::Star,
+            Ok(Some(_)) if expected.is_some() => Progress::Wrong,
+            Ok(Some(_)) => Progress::Answered,
+            Ok(None) => Progress::NotImplemented,
+            Err(Error::Panic(message)) if message.starts_with("not yet implemented")
+                || message.starts_with("not implemented") => Progress::NotImplemented,
+            Err(e) => Progress::Failed(e.to_string()),
+        }
+    }
+
+    fn mark(&self) -> char {
+        match self {
+            Progress::Star => '*',
+            Progress::Answered => 'o',
+            Progress::Wrong => 'x',
+            Progress::Placeholder => '0',
+            Progress::NotImplemented => '-',
+            Progress::Failed(_) => '!',
+            Progress::NoSolution => '.',
+        }
+    }
+}
+
+const LEGEND: &str = "* star  o answered, not in answers.toml  x wrong  0 placeholder  - not implemented  ! failed  . no solution";
+
+/// One day of the calendar: how far each part got and whether the day's tests cover it,
+/// unknown when the tests could not be listed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DayStatus {
+    parts: [Progress; 2],
+    tested: Option<[bool; 2]>,
+}
+
+impl Default for DayStatus {
+    fn default() -> Self {
+        DayStatus { parts: [Progress::NoSolution, Progress::NoSolution], tested: None }
+    }
+}
+
+/// Solves every day of the selected years and draws a calendar of the stars per year,
+/// followed by the parts that still need work.
+pub fn status(args: StatusArgs) -> Result<(), String> {
+    for year in years::select(args.year)? {
+        let answers = Answers::load(&year.inputs().answers_path()).map_err(|e| e.to_string())?;
+        let days = year.solutions().iter()
+            .map(|solution| (solution, year.inputs().load(solution.day())))
+            .collect();
+        let tests = match test_names(year.dir()) {
+            Ok(tests) => Some(tests),
+            Err(e) => {
+                println!("Not checking the tests of {}: {}", year.year(), e);
+                None
+            }
+        };
+        let mut calendar: BTreeMap<u8, DayStatus> = (1..=25).map(|day| (day, DayStatus::default())).collect();
+        let execution = &args.execution;
+        runner::solve_all(days, &Part::ALL, 1, execution.timeout(), execution.jobs as usize, |report| {
+            let module = module_path(year.dir(), report.day, year.inputs().naming());
+            let module = module.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+            calendar.insert(report.day, DayStatus {
+                parts: Part::ALL.map(|part| Progress::of(answers.get(report.day, part), report.answer(part))),
+                tested: tests.as_ref().map(|tests| tested(tests, &module)),
+            });
+        });
+        print!("{}", render(year.year(), &calendar));
+    }
+    println!("{}", LEGEND);
+    Ok(())
+}
+
+/// The unit tests of the crate in `dir`, as `cargo test -- --list` names them.
+fn test_names(dir: &Path) -> Result<Vec<String>, String> {
+    let output = Command::new("cargo")
+        .args(["test", "--quiet", "--lib", "--manifest-path"])
+        .arg(dir.join("Cargo.toml"))
+        .args(["--", "--list", "--format", "terse"])
+        .output()
+        .map_err(|e| format!("failed to run cargo test: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from("cargo test could not list them"));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).lines()
+        .filter_map(|line| line.strip_suffix(": test"))
+        .map(String::from)
+        .collect())
+}
+
+/// Which parts the tests in `module` cover, by a test name with `part_one`, `partOne` or
+/// `PartOne` in it and the same for two. The examples of `aoc_examples!` are tested as
+/// `examples::part_one` and `examples::part_two`, a part without any has no test.
+fn tested(tests: &[String], module: &str) -> [bool; 2] {
+    let names: Vec<String> = tests.iter()
+        .filter_map(|test| test.strip_prefix(module)?.strip_prefix("::"))
+        .map(|name| name.to_lowercase().replace('_', ""))
+        .collect();
+    ["partone", "parttwo"].map(|part| names.iter().any(|name| name.contains(part)))
+}
+
+fn render(year: u16, calendar: &BTreeMap<u8, DayStatus>) -> String {
+    let stars = calendar.values().flat_map(|day| &day.parts).filter(|&p| *p == Progress::Star).count();
+    let mut text = format!("Advent of Code {}: {} stars\n", year, stars);
+    let days: Vec<(&u8, &DayStatus)> = calendar.iter().collect();
+    for week in days.chunks(5) {
+        let cells: Vec<String> = week.iter()
+            .map(|(day, status)| format!("{:>2} {}{}", day, status.parts[0].mark(), status.parts[1].mark()))
+            .collect();
+        text += &format!("  {}\n", cells.join("   "));
+    }
+
+    let mut notes: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for (day, status) in calendar {
+        for (i, part) in Part::ALL.iter().enumerate() {
+            let at = format!("day {} part {}", day, part);
+            match &status.parts[i] {
+                Progress::NotImplemented => notes.entry("Not implemented").or_default().push(at.clone()),
+                Progress::Placeholder => notes.entry("Placeholders").or_default().push(at.clone()),
+                Progress::Wrong => notes.entry("Wrong").or_default().push(at.clone()),
+                Progress::Failed(e) => notes.entry("Failed").or_default().push(format!("{} ({})", at, e)),
+                Progress::NoSolution => continue,
+                Progress::Star | Progress::Answered => (),
+            }
+            if status.tested.is_some_and(|tested| !tested[i]) {
+                notes.entry("Without tests").or_default().push(at);
+            }
+        }
+    }
+    for (note, parts) in notes {
+        text += &format!("  {}: {}\n", note, parts.join(", "));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progressTest() {
+        assert_eq!(Progress::Placeholder, Progress::of(Some("0"), Ok(Some("0"))));
+        assert_eq!(Progress::Star, Progress::of(Some("10"), Ok(Some("10"))));
+        assert_eq!(Progress::Placeholder, Progress::of(Some("24000"), Ok(Some("0"))));
+        assert_eq!(Progress::Wrong, Progress::of(Some("24000"), Ok(Some("45000"))));
+        assert_eq!(Progress::Answered, Progress::of(None, Ok(Some("45000"))));
+        assert_eq!(Progress::NotImplemented, Progress::of(Some("24000"), Ok(None)));
+        let todo = Error::Panic(String::from("not yet implemented: part two"));
+        assert_eq!(Progress::NotImplemented, Progress::of(None, Err(&todo)));
+        let panic = Error::Panic(String::from("no beacon"));
+        assert_eq!(Progress::Failed(String::from("panicked: no beacon")), Progress::of(None, Err(&panic)));
+    }
+
+    #[test]
+    fn testedTest() {
+        let tests: Vec<String> = [
+            "day4::tests::examples::part_one",
+            "day4::tests::parseTest",
+            "daySixteen::tests::partOneTest",
+            "daySixteen::tests::sixteenPartTwoExampleTest",
+            "daySix::tests::parseTest",
+        ].map(String::from).to_vec();
+        assert_eq!([true, false], tested(&tests, "day4"));
+        assert_eq!([true, true], tested(&tests, "daySixteen"));
+        assert_eq!([false, false], tested(&tests, "daySix"));
+        assert_eq!([false, false], tested(&tests, "day5"));
+    }
+
+    #[test]
+    fn renderTest() {
+        let mut calendar: BTreeMap<u8, DayStatus> = (1..=25).map(|day| (day, DayStatus::default())).collect();
+        calendar.insert(1, DayStatus { parts: [Progress::Star, Progress::Star], tested: Some([true, true]) });
+        calendar.insert(9, DayStatus { parts: [Progress::Star, Progress::NotImplemented], tested: Some([true, false]) });
+        calendar.insert(12, DayStatus { parts: [Progress::Placeholder, Progress::Failed(String::from("panicked"))], tested: None });
+        let text = render(2022, &calendar);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!("Advent of Code 2022: 3 stars", lines[0]);
+        assert_eq!("   1 **    2 ..    3 ..    4 ..    5 ..", lines[1]);
+        assert_eq!("   6 ..    7 ..    8 ..    9 *-   10 ..", lines[2]);
+        assert_eq!("  11 ..   12 0!   13 ..   14 ..   15 ..", lines[3]);
+        assert_eq!("  Failed: day 12 part 2 (panicked)", lines[6]);
+        assert_eq!("  Not implemented: day 9 part 2", lines[7]);
+        assert_eq!("  Placeholders: day 12 part 1", lines[8]);
+        assert_eq!("  Without tests: day 9 part 2", lines[9]);
+    }
+}
@@ -0,0 +1,67 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+use aoc_client::{Attempt, Client, Config, History, Outcome};
+use aoc_core::{runner, Answers};
+use crate::{years, SubmitArgs};
+
+/// Submits the answer to one part, solving the day for it unless it is given, and keeps
+/// every attempt in the history so a known wrong answer is never sent twice.
+pub fn submit(args: SubmitArgs) -> Result<(), String> {
+    let config = Config::load().map_err(|e| e.to_string())?;
+    let client = Client::new(&config).map_err(|e| e.to_string())?;
+    let history_path = config.history_path()
+        .ok_or("Nowhere to keep the history, set history in the config file or $HOME")?;
+    let mut history = History::load(&history_path).map_err(|e| e.to_string())?;
+    let year = years::select(Some(args.year))?.remove(0);
+
+    let answer = match args.answer {
+        Some(answer) => answer.trim().to_string(),
+        None => {
+            let solution = years::days(&year, Some(args.day))?.remove(0);
+            let report = runner::solve(solution, year.inputs().load(args.day), &[args.part], 1);
+            match report.answer(args.part) {
+                Ok(Some(answer)) => answer.to_string(),
+                Ok(None) => return Err(format!("Day {}, part {} is not solved yet", args.day, args.part)),
+                Err(e) => return Err(format!("Day {}, part {} failed: {}", args.day, args.part, e)),
+            }
+        }
+    };
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default();
+    let part = args.part.number();
+    history.check(year.year(), args.day, part, &answer, now)
+        .map_err(|refusal| format!("Not submitting {}: {}", answer, refusal))?;
+
+    let response = client.submit(year.year(), args.day, args.part, &answer).map_err(|e| e.to_string())?;
+    history.record(Attempt {
+        year: year.year(),
+        day: args.day,
+        part,
+        answer: answer.clone(),
+        outcome: response.outcome,
+        at: now,
+        wait: response.wait.map(|wait| wait.as_secs()),
+    });
+    history.save(&history_path).map_err(|e| format!("Failed to write {}: {}", history_path.display(), e))?;
+
+    println!("Day {}, part {}: {} is {}", args.day, args.part, answer, response.outcome);
+    if let Some(wait) = response.wait {
+        println!("Wait {}s before the next answer", wait.as_secs());
+    }
+    match response.outcome {
+        Outcome::Correct => record_answer(&year, args.day, args.part, &answer),
+        Outcome::Unknown => Err(format!("Did not understand the reply: {}", response.message)),
+        outcome => Err(format!("The answer was {}", outcome)),
+    }
+}
+
+/// Adds a right answer to the year's answers.toml, for `aoc verify`.
+fn record_answer(year: &aoc_core::Year, day: u8, part: aoc_core::Part, answer: &str) -> Result<(), String> {
+    let path = year.inputs().answers_path();
+    let mut answers = Answers::load(&path).map_err(|e| e.to_string())?;
+    if answers.get(day, part).is_none() {
+        answers.insert(day, part, answer);
+        answers.save(&path).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        println!("Recorded the answer in {}", path.display());
+    }
+    Ok(())
+}
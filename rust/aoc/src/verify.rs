@@ -0,0 +1,86 @@
+use aoc_core::answers::{check, is_placeholder};
+use aoc_core::{runner, Answers, DayReport, Part, Verdict};
+use crate::input::Source;
+use crate::{years, VerifyArgs};
+
+#[derive(Default)]
+struct Tally {
+    correct: usize,
+    wrong: usize,
+    missing: usize,
+    new: usize,
+}
+
+/// Solves the selected days and compares the answers to the known ones. Fails when an
+/// answer changed or a known answer is no longer found.
+pub fn verify(args: VerifyArgs) -> Result<(), String> {
+    let source = Source::new(args.input.as_deref(), args.day)?;
+    let mut regressions = 0;
+    for year in years::select(args.year)? {
+        let path = source.inputs(&year).answers_path();
+        let mut answers = Answers::load(&path).map_err(|e| e.to_string())?;
+        let mut tally = Tally::default();
+        let mut recorded = 0;
+        println!("=======================================");
+        println!("Verifying Advent of Code {}", year.year());
+        let days = years::days(&year, args.day)?.into_iter()
+            .map(|solution| (solution, source.load(&year, solution.day())))
+            .collect();
+        let mut reports = vec![];
+        let execution = &args.execution;
+        runner::solve_all(days, &Part::ALL, 1, execution.timeout(), execution.jobs as usize, |r| reports.push(r));
+        for report in reports {
+            for part in Part::ALL {
+                let expected = answers.get(report.day, part);
+                match check(expected, report.answer(part)) {
+                    Verdict::Correct => tally.correct += 1,
+                    Verdict::Wrong { expected } => {
+                        tally.wrong += 1;
+                        println!("Day {}, part {}: wrong, expected {} but got {}",
+                                 report.day, part, expected, describe(&report, part));
+                    }
+                    Verdict::Missing { expected } => {
+                        tally.missing += 1;
+                        println!("Day {}, part {}: missing, expected {} but {}",
+                                 report.day, part, expected, describe(&report, part));
+                    }
+                    Verdict::New => {
+                        tally.new += 1;
+                        let answer = describe(&report, part);
+                        println!("Day {}, part {}: new answer {}", report.day, part, answer);
+                        if args.record && is_placeholder(&answer) {
+                            println!("Day {}, part {}: not recording {:?}, it looks like a placeholder", report.day, part, answer);
+                        } else if args.record {
+                            answers.insert(report.day, part, answer);
+                            recorded += 1;
+                        }
+                    }
+                    Verdict::Unknown => {
+                        if let Err(e) = report.answer(part) {
+                            println!("Day {}, part {}: failed without a known answer: {}", report.day, part, e);
+                        }
+                    }
+                }
+            }
+        }
+        println!("{} correct, {} wrong, {} missing, {} new", tally.correct, tally.wrong, tally.missing, tally.new);
+        if recorded > 0 {
+            answers.save(&path).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            println!("Recorded {} new answers in {}", recorded, path.display());
+        }
+        regressions += tally.wrong + tally.missing;
+    }
+    match regressions {
+        0 => Ok(()),
+        1 => Err(String::from("1 answer does not match")),
+        n => Err(format!("{} answers do not match", n)),
+    }
+}
+
+fn describe(report: &DayReport, part: Part) -> String {
+    match report.answer(part) {
+        Ok(Some(answer)) => answer.to_string(),
+        Ok(None) => String::from("it is not solved yet"),
+        Err(e) => format!("it failed: {}", e),
+    }
+}
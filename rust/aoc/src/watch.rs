@@ -0,0 +1,260 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use aoc_core::output::Status;
+use aoc_core::timing::Elapsed;
+use aoc_core::{examples, Record};
+use crate::new::module_path;
+use crate::{years, WatchArgs};
+
+const POLL: Duration = Duration::from_millis(500);
+
+/// The crates of the workspace that the days of every year build on.
+const SHARED: [&str; 3] = ["aoc-core", "aoc-utils", "aoc-vm"];
+
+/// The files a day depends on: its input, its example files and the sources of its year and
+/// of the shared crates.
+struct Watched {
+    day: u8,
+    input: PathBuf,
+    examples: PathBuf,
+    sources: Vec<PathBuf>,
+}
+
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// The answer each part had in the previous run, `None` when it had none.
+type Answers = BTreeMap<u8, Option<String>>;
+
+impl Watched {
+    /// When each of the files was last modified.
+    fn snapshot(&self) -> Snapshot {
+        let mut files = vec![self.input.clone()];
+        let prefix = format!("day{}_", self.day);
+        if let Ok(entries) = fs::read_dir(&self.examples) {
+            files.extend(entries.flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.file_name().is_some_and(|name| name.to_string_lossy().starts_with(&prefix))));
+        }
+        self.sources.iter().for_each(|dir| sources(dir, &mut files));
+        files.into_iter()
+            .filter_map(|path| Some((path.clone(), fs::metadata(&path).and_then(|m| m.modified()).ok()?)))
+            .collect()
+    }
+}
+
+fn sources(dir: &Path, files: &mut Vec<PathBuf>) {
+    for path in fs::read_dir(dir).into_iter().flatten().flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            sources(&path, files);
+        } else if path.extension().is_some_and(|extension| extension == "rs") {
+            files.push(path);
+        }
+    }
+}
+
+/// The files added, changed or removed between two snapshots.
+fn changes(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = after.iter()
+        .filter(|(path, modified)| before.get(*path) != Some(modified))
+        .map(|(path, _)| path.clone())
+        .collect();
+    changed.extend(before.keys().filter(|path| !after.contains_key(*path)).cloned());
+    changed
+}
+
+/// Runs the tests of the day and solves it again whenever its input, its examples or the
+/// code of its year or of the shared crates change, pointing out the answers that are not
+/// what they were.
+pub fn watch(args: WatchArgs) -> Result<(), String> {
+    let year = years::select(Some(args.year))?.remove(0);
+    let workspace = workspace(year.dir())?;
+    let module = module_path(year.dir(), args.day, year.inputs().naming());
+    let module = module.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+    let mut sources = vec![year.dir().join("src")];
+    sources.extend(SHARED.iter().map(|name| workspace.join(name).join("src")));
+    let watched = Watched {
+        day: args.day,
+        input: year.inputs().path(args.day),
+        examples: examples::dir(year.dir()),
+        sources,
+    };
+    let sources: Vec<String> = watched.sources.iter().map(|dir| dir.display().to_string()).collect();
+    println!("Watching {}, {} and {}, stop with Ctrl-C",
+             watched.input.display(), watched.examples.display(), sources.join(", "));
+
+    let mut seen = Snapshot::new();
+    let mut answers = Answers::new();
+    loop {
+        let snapshot = watched.snapshot();
+        let changed = changes(&seen, &snapshot);
+        if !changed.is_empty() {
+            println!("=======================================");
+            if !seen.is_empty() {
+                changed.iter().for_each(|path| println!("Changed {}", path.display()));
+            }
+            seen = snapshot;
+            test(year.dir(), &module);
+            solve(&workspace, year.year(), args.day, &mut answers);
+        }
+        thread::sleep(POLL);
+    }
+}
+
+/// The workspace the crate of a year is in, found where the crate is rather than where
+/// `aoc` was built.
+fn workspace(year_dir: &Path) -> Result<PathBuf, String> {
+    year_dir.parent()
+        .filter(|dir| dir.join("Cargo.toml").is_file())
+        .map(Path::to_path_buf)
+        .ok_or_else(|| format!("There is no workspace Cargo.toml above {}", year_dir.display()))
+}
+
+/// Runs the tests of the day's module, examples included, showing their output only when
+/// they fail.
+fn test(dir: &Path, module: &str) {
+    let output = Command::new("cargo")
+        .args(["test", "--quiet", "--lib", "--manifest-path"])
+        .arg(dir.join("Cargo.toml"))
+        .arg(format!("{}::", module))
+        .output();
+    match output {
+        Ok(output) if output.status.success() => println!("Tests of {} passed", module),
+        Ok(output) => {
+            print_output(&output);
+            println!("{}", highlight(&format!("Tests of {} failed", module)));
+        }
+        Err(e) => println!("Failed to run cargo test: {}", e),
+    }
+}
+
+/// Builds and solves the day with the `aoc` of the workspace, `answers` holds what the
+/// previous run found.
+fn solve(workspace: &Path, year: u16, day: u8, answers: &mut Answers) {
+    let output = Command::new("cargo")
+        .args(["run", "--quiet", "--release", "--package", "aoc", "--manifest-path"])
+        .arg(workspace.join("Cargo.toml"))
+        .args(["--", "run", "--format", "json"])
+        .args(["--year", &year.to_string(), "--day", &day.to_string()])
+        .output();
+    let output = match output {
+        Ok(output) => output,
+        Err(e) => return println!("Failed to run cargo run: {}", e),
+    };
+    let records: Vec<Record> = String::from_utf8_lossy(&output.stdout).lines()
+        .filter_map(|line| Record::from_json(line).ok())
+        .collect();
+    if records.is_empty() {
+        print_output(&output);
+        return println!("{}", highlight("Failed to solve the day"));
+    }
+    for (line, changed) in compare(&records, answers) {
+        match changed {
+            true => println!("{}", highlight(&line)),
+            false => println!("{}", line),
+        }
+    }
+}
+
+/// Describes each part and whether its answer differs from the one in `answers`, the
+/// answers of the previous run, which are then replaced.
+fn compare(records: &[Record], answers: &mut Answers) -> Vec<(String, bool)> {
+    records.iter().map(|record| {
+        let answer = match record.status {
+            Status::Solved => record.answer.clone(),
+            _ => None,
+        };
+        let mut line = match (&answer, record.status) {
+            (Some(answer), _) => {
+                let time = record.duration_ns.map(|ns| format!(" ({})", Elapsed(Duration::from_nanos(ns))));
+                format!("Part {}: {}{}", record.part, answer, time.unwrap_or_default())
+            }
+            (None, Status::Unsolved) => format!("Part {}: not solved yet", record.part),
+            (None, _) => format!("Part {} failed: {}", record.part, record.error.as_deref().unwrap_or("no reason")),
+        };
+        let changed = match answers.insert(record.part, answer) {
+            Some(previous) if previous != answers[&record.part] => {
+                line += &format!(", it was {}", previous.as_deref().unwrap_or("not answered"));
+                true
+            }
+            _ => false,
+        };
+        (line, changed)
+    }).collect()
+}
+
+fn print_output(output: &Output) {
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+    print!("{}", String::from_utf8_lossy(&output.stderr));
+}
+
+/// Makes a line stand out, in bold yellow on a terminal.
+fn highlight(line: &str) -> String {
+    if std::io::stdout().is_terminal() {
+        format!("\x1b[1;33m{}\x1b[0m", line)
+    } else {
+        format!(">> {}", line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(part: u8, status: Status, answer: Option<&str>) -> Record {
+        Record { year: 2022, day: 5, part, answer: answer.map(String::from), duration_ns: None, status, error: None }
+    }
+
+    #[test]
+    fn compareTest() {
+        let mut answers = Answers::new();
+        let first = compare(&[record(1, Status::Solved, Some("CMZ")), record(2, Status::Unsolved, None)], &mut answers);
+        assert_eq!(vec![(String::from("Part 1: CMZ"), false), (String::from("Part 2: not solved yet"), false)], first);
+        let second = compare(&[record(1, Status::Solved, Some("CMZ")), record(2, Status::Solved, Some("MCD"))], &mut answers);
+        assert_eq!(vec![(String::from("Part 1: CMZ"), false), (String::from("Part 2: MCD, it was not answered"), true)], second);
+        let third = compare(&[record(1, Status::Failed, None)], &mut answers);
+        assert_eq!(vec![(String::from("Part 1 failed: no reason, it was CMZ"), true)], third);
+    }
+
+    #[test]
+    fn snapshotTest() {
+        let dir = std::env::temp_dir().join("aoc-watch-snapshot");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("src").join("bin")).unwrap();
+        fs::create_dir_all(dir.join("aoc-utils").join("src")).unwrap();
+        fs::create_dir_all(dir.join("examples")).unwrap();
+        fs::write(dir.join("src").join("lib.rs"), "pub mod day5;").unwrap();
+        fs::write(dir.join("src").join("bin").join("main.rs"), "fn main() {}").unwrap();
+        fs::write(dir.join("aoc-utils").join("src").join("grid.rs"), "pub struct Grid;").unwrap();
+        fs::write(dir.join("examples").join("day5_1.txt"), "move 1 from 2 to 1").unwrap();
+        fs::write(dir.join("examples").join("day15_1.txt"), "Sensor at x=2, y=18").unwrap();
+        let sources = vec![dir.join("src"), dir.join("aoc-utils").join("src"), dir.join("aoc-vm").join("src")];
+        let watched = Watched { day: 5, input: dir.join("day5.txt"), examples: dir.join("examples"), sources };
+
+        let before = watched.snapshot();
+        assert_eq!(4, before.len());
+        assert!(before.contains_key(&dir.join("src").join("bin").join("main.rs")));
+        assert!(before.contains_key(&dir.join("aoc-utils").join("src").join("grid.rs")));
+        fs::write(&watched.input, "[D]").unwrap();
+        fs::remove_file(dir.join("examples").join("day5_1.txt")).unwrap();
+        let after = watched.snapshot();
+        let mut changed = changes(&before, &after);
+        changed.sort();
+        assert_eq!(vec![dir.join("day5.txt"), dir.join("examples").join("day5_1.txt")], changed);
+        assert!(changes(&after, &watched.snapshot()).is_empty());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn workspaceTest() {
+        let year = years::select(Some(2022)).unwrap().remove(0);
+        let workspace = workspace(year.dir()).unwrap();
+        assert!(workspace.join("aoc").join("Cargo.toml").is_file());
+        assert!(SHARED.iter().all(|name| workspace.join(name).join("src").is_dir()));
+        assert!(super::workspace(Path::new("/")).is_err());
+    }
+}
@@ -0,0 +1,50 @@
+use aoc_core::{Solution, Year};
+
+// Days register themselves, the year crates only need to be linked in.
+use fifteen as _;
+use twentytwenty as _;
+use twentyone as _;
+use twentytwolib as _;
+
+pub fn all() -> Vec<Year> {
+    aoc_core::registry::years()
+}
+
+pub fn select(year: Option<u16>) -> Result<Vec<Year>, String> {
+    let years = all();
+    match year {
+        None => Ok(years),
+        Some(y) => {
+            let selected: Vec<Year> = years.into_iter().filter(|s| s.year() == y).collect();
+            if selected.is_empty() {
+                return Err(format!("There are no solutions for {}", y));
+            }
+            Ok(selected)
+        }
+    }
+}
+
+/// One day of `year`, or all of its days.
+pub fn days(year: &Year, day: Option<u8>) -> Result<Vec<&Solution>, String> {
+    match day {
+        Some(day) => Ok(vec![year.solution(day)
+            .ok_or_else(|| format!("There is no solution for {} day {}", year.year(), day))?]),
+        None => Ok(year.solutions().iter().collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selectYearTest() {
+        assert_eq!(4, select(None).unwrap().len());
+        let selected = select(Some(2022)).unwrap();
+        assert_eq!(1, selected.len());
+        assert!(selected[0].solution(15).is_some());
+        assert!(select(Some(2019)).is_err());
+        assert_eq!(1, days(&selected[0], Some(15)).unwrap().len());
+        assert!(days(&selected[0], Some(8)).is_err());
+    }
+}
@@ -0,0 +1,25 @@
+#![allow(non_snake_case)]
+
+use std::process::Command;
+use aoc_core::Record;
+
+/// Runs `aoc run --format json` for a day and gives back its stdout.
+fn runJson(year: u16, day: u8) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["run", "--format", "json", "--year", &year.to_string(), "--day", &day.to_string()])
+        .output()
+        .expect("aoc runs");
+    String::from_utf8(output.stdout).expect("stdout is UTF-8")
+}
+
+#[test]
+fn stdoutIsOnlyRecordsTest() {
+    // days whose solutions have printed to stdout before
+    for (year, day) in [(2020, 9), (2020, 16), (2021, 7), (2021, 13), (2021, 14)] {
+        let stdout = runJson(year, day);
+        assert!(!stdout.is_empty(), "no records for {} day {}", year, day);
+        for line in stdout.lines() {
+            assert!(Record::from_json(line).is_ok(), "{} day {} wrote {:?} to stdout", year, day, line);
+        }
+    }
+}
@@ -1,48 +1,31 @@
-use std::fmt::Display;
-use std::fs;
+use std::fmt;
 use std::path::PathBuf;
+use aoc_core::{Inputs, Naming};
 
-pub fn load_input(day: &str) -> std::io::Result<String> {
-    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    d.push("resource");
-    d.push(format!("day{}.txt", day));
-    return fs::read_to_string(d);
+pub use aoc_core::{parse_lines, AocDay, BadLine, Day, TryDay};
+
+pub fn inputs() -> Inputs {
+    Inputs::locate(crate::YEAR, PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("resource"), Naming::Numeric)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// A line of the input, counting from 1, that could not be parsed.
+    Parse { day: u8, line: usize, text: String },
 }
 
-pub trait Day {
-    type R: Display;
+impl Error {
+    pub fn parse(day: u8, line: usize, text: &str) -> Self {
+        Error::Parse { day, line, text: text.to_string() }
+    }
+}
 
-    fn day() -> String where Self: Sized;
-    fn part_one(&self) -> Self::R;
-    fn part_two(&self) -> Self::R;
-    fn solve() where Self: Sized + From<String> {
-        println!("---------------------------------------");
-        let res = load_input(&Self::day());
-        let day = match res {
-            Ok(problem) => Self::from(problem),
-            Err(_) => {
-                println!("Failed to read input for day {}", Self::day());
-                panic!()
-            }
-        };
-        println!("Day {}, part 1: {}", Self::day(), day.part_one());
-        println!("Day {}, part 2: {}", Self::day(), day.part_two());
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse { day, line, text } => write!(f, "day {}, line {}: could not parse {:?}", day, line, text),
+        }
     }
 }
 
-#[macro_export]
-macro_rules! solve {
-    ($day:ty) => {
-        println!("---------------------------------------");
-        let res = fifteen::core::load_input(&<$day>::day());
-        let day = match res {
-            Ok(input) => <$day>::from(input),
-            Err(_) => {
-                println!("Failed to read input for day {}", <$day>::day());
-                panic!()
-            }
-        };
-        println!("Day {}, part 1: {}", <$day>::day(), day.part_one());
-        println!("Day {}, part 2: {}", <$day>::day(), day.part_two());
-    };
-}
\ No newline at end of file
+impl std::error::Error for Error {}
@@ -1,23 +1,13 @@
-use crate::core::{Day};
+use crate::core::AocDay;
 
+#[derive(AocDay)]
+#[aoc(day = 1, answer = i32)]
 pub struct DayOne {
     input: String
 }
 
-impl From<String> for DayOne {
-    fn from(input: String) -> Self {
-        DayOne { input }
-    }
-}
-
-impl Day for DayOne {
-    type R = i32;
-
-    fn day() -> String {
-       String::from("1")
-    }
-
-    fn part_one(&self) -> Self::R {
+impl DayOne {
+    fn part_one(&self) -> i32 {
         let mut floor = 0;
         self.input.chars().for_each(|c| match c {
             '(' => floor = floor + 1,
@@ -27,7 +17,7 @@ impl Day for DayOne {
         floor
     }
 
-    fn part_two(&self) -> Self::R {
+    fn part_two(&self) -> i32 {
         let mut floor = 0;
         for (i, c) in self.input.chars().enumerate()
         {
@@ -69,32 +59,8 @@ mod tests {
         assert_eq!(res, 0);
     }
 
-    #[test]
-    fn partOneExample2Test() {
-        let input = String::from("(((");
-        let d = DayOne { input };
-        let res = d.part_one();
-        assert_eq!(res, 3);
-        let input = String::from("))(((((");
-        let d = DayOne { input };
-        let res = d.part_one();
-        assert_eq!(res, 3);
-        let input = String::from(")())())");
-        let d = DayOne { input };
-        let res = d.part_one();
-        assert_eq!(res, -3);
-    }
-
-    #[test]
-    fn partTwoExampleTest() {
-        let input = String::from(")");
-        let d = DayOne { input };
-        let res = d.part_two();
-        assert_eq!(res, 1);
-        let input = String::from("()())");
-        let d = DayOne { input };
-        let res = d.part_two();
-        assert_eq!(res, 5);
-    }
+    aoc_core::aoc_examples!(DayOne,
+        part_one: [("(((", 3), ("))(((((", 3), (")())())", -3)],
+        part_two: [(")", 1), ("()())", 5)]);
 
 }
\ No newline at end of file
@@ -1,13 +1,16 @@
-use crate::core::Day;
+use std::str::FromStr;
+use crate::core::{parse_lines, AocDay, BadLine, Error};
 use itertools::Itertools;
 
+#[derive(AocDay)]
+#[aoc(day = 2, answer = u32, error = Error, parse = Self::parse)]
 pub struct DayTwo {
-    input: String
+    presents: Vec<Present>
 }
 
-impl From<String> for DayTwo {
-    fn from(input: String) -> Self {
-        DayTwo { input }
+impl DayTwo {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(DayTwo { presents: parse_lines(2, input, Error::parse)? })
     }
 }
 
@@ -44,38 +47,31 @@ impl Present {
     }
 }
 
-impl From<&str> for Present {
-    fn from(dimensions: &str) -> Self {
+impl FromStr for Present {
+    type Err = BadLine;
+
+    fn from_str(dimensions: &str) -> Result<Self, Self::Err> {
         let d = dimensions.split('x')
-            .filter_map(|size| size.parse::<u32>().ok())
-            .collect::<Vec<u32>>();
-        Present {
-            l: d[0],
-            w: d[1],
-            h: d[2]
+            .map(|size| size.parse::<u32>())
+            .collect::<Result<Vec<u32>, _>>()?;
+        match d[..] {
+            [l, w, h] => Ok(Present { l, w, h }),
+            _ => Err(BadLine)
         }
     }
 }
 
-impl Day for DayTwo {
-    type R = u32;
-
-    fn day() -> String where Self: Sized {
-        String::from("2")
-    }
-
-    fn part_one(&self) -> Self::R {
-        self.input.lines()
-            .map(|l| Present::from(l))
+impl DayTwo {
+    fn part_one(&self) -> Result<u32, Error> {
+        Ok(self.presents.iter()
             .map(|p| p.wrapping_paper())
-            .sum::<u32>()
+            .sum::<u32>())
     }
 
-    fn part_two(&self) -> Self::R {
-        self.input.lines()
-            .map(|l| Present::from(l))
+    fn part_two(&self) -> Result<u32, Error> {
+        Ok(self.presents.iter()
             .map(|p| p.ribbon_wrap() + p.ribbon_bow())
-            .sum::<u32>()
+            .sum::<u32>())
     }
 }
 
@@ -86,34 +82,40 @@ mod tests {
     #[test]
     fn partOneExampleTest() {
         let input = String::from("2x3x4");
-        let mut res = DayTwo { input }.part_one();
-        assert_eq!(res, 58);
+        let mut res = DayTwo::try_from(input).unwrap().part_one();
+        assert_eq!(res, Ok(58));
         let input = String::from("1x1x10");
-        res = DayTwo { input }.part_one();
-        assert_eq!(res, 43);
+        res = DayTwo::try_from(input).unwrap().part_one();
+        assert_eq!(res, Ok(43));
     }
 
     #[test]
     fn ribbonLengthExampleTest() {
         vec![("2x3x4", 10), ("1x1x10", 4)].iter()
-            .map(|(l, expected)| (Present::from(*l).ribbon_wrap(), expected))
+            .map(|(l, expected)| (l.parse::<Present>().unwrap().ribbon_wrap(), expected))
             .for_each(|(actual, expected)| assert_eq!(actual, *expected as u32));
     }
 
     #[test]
     fn ribbonBowExampleTest() {
         vec![("2x3x4", 24), ("1x1x10", 10)].iter()
-            .map(|(l, expected)| (Present::from(*l).ribbon_bow(), expected))
+            .map(|(l, expected)| (l.parse::<Present>().unwrap().ribbon_bow(), expected))
             .for_each(|(actual, expected)| assert_eq!(actual, *expected as u32));
     }
     #[test]
     fn partTwoExampleTest() {
         let input = String::from("2x3x4");
-        let day = DayTwo { input };
+        let day = DayTwo::try_from(input).unwrap();
         let mut res = day.part_two();
-        assert_eq!(res, 34);
+        assert_eq!(res, Ok(34));
         let input = String::from("1x1x10");
-        res = DayTwo { input }.part_two();
-        assert_eq!(res, 14);
+        res = DayTwo::try_from(input).unwrap().part_two();
+        assert_eq!(res, Ok(14));
+    }
+
+    #[test]
+    fn badPresentTest() {
+        let input = String::from("2x3x4\n1x1\n1x1x10");
+        assert_eq!(Some(Error::parse(2, 2, "1x1")), DayTwo::try_from(input).err());
     }
 }
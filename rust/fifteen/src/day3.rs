@@ -1,14 +1,17 @@
-use crate::core::Day;
+use crate::core::{parse_lines, AocDay, BadLine, Error};
 use std::collections::HashSet;
+use std::str::FromStr;
 
+#[derive(AocDay)]
+#[aoc(day = 3, answer = u32, error = Error, parse = Self::parse)]
 pub struct DayThree {
-    input: String
+    moves: Vec<Position>
 }
 
-// this implementation should/could be a derive-macro
-impl From<String> for DayThree {
-    fn from(input: String) -> Self {
-        DayThree { input }
+impl DayThree {
+    fn parse(input: &str) -> Result<Self, Error> {
+        let lines: Vec<Moves> = parse_lines(3, input, Error::parse)?;
+        Ok(DayThree { moves: lines.into_iter().flat_map(|moves| moves.0).collect() })
     }
 }
 
@@ -21,43 +24,50 @@ impl Position {
     }
 }
 
+/// The moves on a line of the directions.
+struct Moves(Vec<Position>);
+
+impl FromStr for Moves {
+    type Err = BadLine;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        input.chars()
+            .map(|c| match c {
+                '^' => Ok(Position(0, 1)),
+                'v' => Ok(Position(0, -1)),
+                '>' => Ok(Position(1, 0)),
+                '<' => Ok(Position(-1, 0)),
+                _ => Err(BadLine)
+            })
+            .collect::<Result<_, _>>()
+            .map(Moves)
+    }
+}
+
 impl DayThree {
-    fn visit(&self, filter: &dyn Fn(&(usize, char)) -> bool) -> HashSet<Position> {
+    fn visit(&self, filter: &dyn Fn(&(usize, &Position)) -> bool) -> HashSet<Position> {
         let mut pos = Position(0,0);
         let mut visited = HashSet::new();
         visited.insert(pos);
-        for (_i, c) in self.input.chars().enumerate().filter(filter) {
-            let next_move = match c {
-                '^' => Position(0, 1),
-                'v' => Position(0, -1),
-                '>' => Position(1, 0),
-                '<' => Position(-1, 0),
-                _ => panic!("Unkown move: '{}'", c)
-            };
-            pos = pos.movement(next_move);
+        for (_i, next_move) in self.moves.iter().enumerate().filter(filter) {
+            pos = pos.movement(*next_move);
             visited.insert(pos);
         }
         return visited;
     }
 }
 
-impl Day for DayThree {
-    type R = u32;
-
-    fn day() -> String where Self: Sized {
-        String::from("3")
-    }
-
-    fn part_one(&self) -> Self::R {
-        return self.visit(&|(_i,_c)| true).len() as u32;
+impl DayThree {
+    fn part_one(&self) -> Result<u32, Error> {
+        return Ok(self.visit(&|(_i,_m)| true).len() as u32);
     }
 
 
 
-    fn part_two(&self) -> Self::R {
-        let santa_visits = self.visit(&|(i, _c)| i % 2 > 0);
-        let robo_visits = self.visit(&|(i, _c)| i % 2 == 0);
-        return santa_visits.union(&robo_visits).count() as u32;
+    fn part_two(&self) -> Result<u32, Error> {
+        let santa_visits = self.visit(&|(i, _m)| i % 2 > 0);
+        let robo_visits = self.visit(&|(i, _m)| i % 2 == 0);
+        return Ok(santa_visits.union(&robo_visits).count() as u32);
     }
 }
 
@@ -68,14 +78,19 @@ mod tests {
     #[test]
     fn partOneExampleTest() {
         vec![(">", 2), ("^>v<", 4), ("^v^v^v^v^v", 2)].iter()
-            .map(|(l, expected)| (DayThree::from(String::from(*l)).part_one(), expected))
-            .for_each(|(actual, expected)| assert_eq!(actual, *expected as u32));
+            .map(|(l, expected)| (DayThree::try_from(String::from(*l)).unwrap().part_one(), expected))
+            .for_each(|(actual, expected)| assert_eq!(actual, Ok(*expected as u32)));
     }
 
     #[test]
     fn partTwoExampleTest() {
         vec![("^v", 3), ("^>v<", 3), ("^v^v^v^v^v", 11)].iter()
-            .map(|(l, expected)| (DayThree::from(String::from(*l)).part_two(), expected))
-            .for_each(|(actual, expected)| assert_eq!(actual, *expected as u32));
+            .map(|(l, expected)| (DayThree::try_from(String::from(*l)).unwrap().part_two(), expected))
+            .for_each(|(actual, expected)| assert_eq!(actual, Ok(*expected as u32)));
+    }
+
+    #[test]
+    fn badMoveTest() {
+        assert_eq!(Some(Error::parse(3, 1, "^>x<")), DayThree::try_from(String::from("^>x<")).err());
     }
 }
\ No newline at end of file
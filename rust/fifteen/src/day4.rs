@@ -1,24 +1,13 @@
-use crate::core::{Day};
+use crate::core::AocDay;
 
+#[derive(AocDay)]
+#[aoc(day = 4, answer = u64)]
 pub struct DayFour {
     input: String
 }
 
-// this implementation should/could be a derive-macro
-impl From<String> for DayFour {
-    fn from(input: String) -> Self {
-        DayFour { input }
-    }
-}
-
-impl Day for DayFour {
-    type R = u64;
-
-    fn day() -> String where Self: Sized {
-        String::from("4")
-    }
-
-    fn part_one(&self) -> Self::R {
+impl DayFour {
+    fn part_one(&self) -> u64 {
         let mut counter:u64 = 0;
         loop {
             let digest = md5::compute(format!("{}{}", self.input, counter).as_bytes());
@@ -31,7 +20,7 @@ impl Day for DayFour {
         return counter;
     }
 
-    fn part_two(&self) -> Self::R {
+    fn part_two(&self) -> u64 {
         let mut counter:u64 = 0;
         loop {
             let digest = md5::compute(format!("{}{}", self.input, counter).as_bytes());
@@ -49,10 +38,5 @@ impl Day for DayFour {
 mod tests {
     use super::*;
 
-    #[test]
-    fn partOneExampleTest() {
-        vec![("abcdef", 609043), ("pqrstuv", 1048970)].iter()
-            .map(|(l, expected)| (DayFour::from(String::from(*l)).part_one(), expected))
-            .for_each(|(actual, expected)| assert_eq!(actual, *expected as u64));
-    }
+    aoc_core::aoc_examples!(DayFour, part_one: [("abcdef", 609043), ("pqrstuv", 1048970)]);
 }
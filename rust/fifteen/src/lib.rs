@@ -1,7 +1,17 @@
 #![allow(non_snake_case)]
 
+use aoc_core::Solution;
+
 pub mod core;
 pub mod day1;
 pub mod day2;
 pub mod day3;
 pub mod day4;
+
+pub const YEAR: u16 = 2015;
+
+aoc_core::register_year!(YEAR, core::inputs);
+
+pub fn solutions() -> Vec<Solution> {
+    aoc_core::registry::solutions(YEAR)
+}
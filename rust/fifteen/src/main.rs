@@ -1,13 +1,11 @@
-use fifteen::core::Day;
-use fifteen::day1::DayOne;
-use fifteen::day2::DayTwo;
-use fifteen::day3::DayThree;
-use fifteen::day4::DayFour;
-use fifteen::solve;
+use std::process::ExitCode;
+use fifteen::core::inputs;
+use fifteen::solutions;
 
-fn main() {
-    solve!(DayOne);
-    solve!(DayTwo);
-    solve!(DayThree);
-    solve!(DayFour);
+fn main() -> ExitCode {
+    if aoc_core::run(&solutions(), &inputs()) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
 }
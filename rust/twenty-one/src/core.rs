@@ -0,0 +1,50 @@
+use std::fmt;
+use std::path::PathBuf;
+use aoc_core::{Inputs, Naming};
+
+pub use aoc_core::{parse_lines, BadLine};
+
+pub fn inputs() -> Inputs {
+    Inputs::locate(crate::YEAR, PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("resources"), Naming::Spelled)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// A line of the input, counting from 1, that could not be parsed.
+    Parse { day: u8, line: usize, text: String },
+    /// The input was parsed, but has no answer.
+    NoAnswer { day: u8, reason: String },
+}
+
+impl Error {
+    pub fn parse(day: u8, line: usize, text: &str) -> Self {
+        Error::Parse { day, line, text: text.to_string() }
+    }
+
+    pub fn no_answer(day: u8, reason: impl Into<String>) -> Self {
+        Error::NoAnswer { day, reason: reason.into() }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse { day, line, text } => write!(f, "day {}, line {}: could not parse {:?}", day, line, text),
+            Error::NoAnswer { day, reason } => write!(f, "day {}: {}", day, reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parseErrorTest() {
+        let numbers: Result<Vec<u32>, Error> = parse_lines(1, "1\n\nthree\n4", Error::parse);
+        assert_eq!(Err(Error::parse(1, 3, "three")), numbers);
+        assert_eq!("day 1, line 3: could not parse \"three\"", numbers.unwrap_err().to_string());
+    }
+}
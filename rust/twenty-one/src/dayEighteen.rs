@@ -1,5 +1,6 @@
 use std::str::FromStr;
 use itertools::Itertools;
+use crate::core::{parse_lines, Error};
 
 #[derive(Debug, Clone, Copy)]
 struct LeftOrd {
@@ -23,7 +24,7 @@ impl FromStr for SnailfishNode {
             if '[' == c || ',' == c {
                 match numberStack.pop() {
                     Some(nr_string) => {
-                        let nr: u32 = nr_string.parse().unwrap();
+                        let nr: u32 = nr_string.parse().map_err(|_| "Unable to parse a regular number")?;
                         let snailfish = LeftOrd { value: nr, ord: leftCount };
                         stack.push(SnailfishNode::Leaf(snailfish));
                         leftCount += 1;
@@ -33,7 +34,7 @@ impl FromStr for SnailfishNode {
             } else if ']' == c {
                 match numberStack.pop() {
                     Some(nr_string) => {
-                        let nr: u32 = nr_string.parse().unwrap();
+                        let nr: u32 = nr_string.parse().map_err(|_| "Unable to parse a regular number")?;
                         let snailfish = LeftOrd { value: nr, ord: leftCount };
                         stack.push(SnailfishNode::Leaf(snailfish));
                         leftCount += 1;
@@ -262,30 +263,22 @@ impl ExplodeState {
     }
 }
 
-pub fn partOne(input: &str) -> u64 {
-    let res: Option<SnailfishNode> = input.lines()
-        .map(|l| l.trim())
-        .map(|l| {
-            //println!("{}", l);
-            l.parse().unwrap()
-        })
+pub fn partOne(input: &str) -> Result<u64, Error> {
+    let res: Option<SnailfishNode> = parse_lines(18, input, Error::parse)?.into_iter()
         .reduce(|result: SnailfishNode, term| {
             let mut sum: SnailfishNode = result.addition(&term);
             sum.reduce()
         });
     //println!("final node {}", match &res { Some(n) => n.toString(), None => "".to_string() } );
-    return match res {
+    return Ok(match res {
         Some(node) => node.magnitude(),
         None => 0
-    };
+    });
 }
 
-pub fn partTwo(input: &str) -> u64 {
-    let snailfishNumbers: Vec<SnailfishNode> = input.lines()
-            .map(|l| l.trim())
-            .map(|l| l.parse().unwrap())
-            .collect();
-    return match snailfishNumbers.iter().combinations(2).
+pub fn partTwo(input: &str) -> Result<u64, Error> {
+    let snailfishNumbers: Vec<SnailfishNode> = parse_lines(18, input, Error::parse)?;
+    return Ok(match snailfishNumbers.iter().combinations(2).
         map(|combo| {
             let mut sum1 = combo[0].addition(combo[1]);
             let magitude1 = sum1.reduce().magnitude();
@@ -295,9 +288,11 @@ pub fn partTwo(input: &str) -> u64 {
         }).max() {
             Some(maximum) => maximum,
             None => 0
-        };
+        });
 }
  
+aoc_core::register!(aoc_core::Solution::try_functions(crate::YEAR, 18, partOne, partTwo));
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -410,7 +405,7 @@ mod tests {
         [3,3]
         [4,4]";
         let res = partOne(&input);
-        assert_eq!(445, res);
+        assert_eq!(Ok(445), res);
 
         let input = "[1,1]
         [2,2]
@@ -418,7 +413,7 @@ mod tests {
         [4,4]
         [5,5]";
         let res = partOne(&input);
-        assert_eq!(791, res);
+        assert_eq!(Ok(791), res);
 
         let input = "[1,1]
         [2,2]
@@ -427,12 +422,12 @@ mod tests {
         [5,5]
         [6,6]";
         let res = partOne(&input);
-        assert_eq!(1137, res);
+        assert_eq!(Ok(1137), res);
 
         let input = "[[[[4,3],4],4],[7,[[8,4],9]]]
         [1,1]";
         let res = partOne(&input);
-        assert_eq!(1384, res);
+        assert_eq!(Ok(1384), res);
 
         let input = "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
         [[[5,[2,8]],4],[5,[[9,9],0]]]
@@ -445,7 +440,7 @@ mod tests {
         [[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
         [[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]";
         let res = partOne(&input);
-        assert_eq!(4140, res);
+        assert_eq!(Ok(4140), res);
     }
 
     #[test]
@@ -461,6 +456,12 @@ mod tests {
         [[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
         [[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]";
         let res = partTwo(&input);
-        assert_eq!(3993 ,res);
+        assert_eq!(Ok(3993), res);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn badNumberTest() {
+        assert_eq!(Err(Error::parse(18, 2, "[2,x]")), partOne("[1,1]\n[2,x]"));
+        assert_eq!(Err(Error::parse(18, 1, "[1,1]]")), partTwo("[1,1]]\n[2,2]"));
+    }
+}
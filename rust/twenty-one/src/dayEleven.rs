@@ -1,5 +1,7 @@
 use std::str::FromStr;
 use std::collections::HashSet;
+use aoc_utils::automaton::Automaton;
+use aoc_utils::grid::{ParseError, Pos};
 
 #[derive(Copy, Clone, Debug)]
 struct DumboOctopus {
@@ -18,99 +20,62 @@ impl DumboOctopus {
 
 
 struct Grid {
-    octupuses: [[DumboOctopus; 10]; 10]
+    octupuses: aoc_utils::Grid<DumboOctopus>
 }
 
 impl FromStr for Grid {
-    type Err = &'static str;
+    type Err = ParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let mut grid: [[DumboOctopus; 10]; 10] = [[DumboOctopus { energylevel: 0};10];10];
-        for (row, line) in input.lines().enumerate() {
-            for (col, c) in line.trim().chars().enumerate() {
-                let energy = c.to_digit(10);
-                if energy.is_some() {
-                    grid[row][col] = DumboOctopus { energylevel: energy.unwrap() };
-                } else if energy.is_none() {
-                    return Err("Failed to parse octupus grid due to unknown energylevel");
-                }
-            }
-        }
-        return Ok(Grid { octupuses: grid });
+        return aoc_utils::Grid::parse(input, |c| c.to_digit(10).map(|energylevel| DumboOctopus { energylevel }))
+            .map(|octupuses| Grid { octupuses });
     }
 }
 
 impl Grid {
 
-    fn neighbours(row: usize, col:usize) -> Vec<(usize, usize)> {
-        let start_x = match row.checked_sub(1) {
-            Some(x) => x,
-            None => row
-        };
-        let start_y = match col.checked_sub(1) {
-            Some(x) => x,
-            None => col
-        };
-        let end_x = if row == 9 { row } else { row + 1 };
-        let end_y = if col == 9 { col } else { col + 1 };
-        let mut points = Vec::new();
-        for x in start_x..=end_x {
-            for y in start_y..=end_y {
-                if !(x == row && y == col) {
-                    points.push((x,y));
-                }
-            }
-        }
-        return points;
-    }
-
-    fn flash(&mut self, neighbourhood: Vec<(usize, usize)>, flashed: &mut HashSet<(usize, usize)>) {
-        for (row,col) in neighbourhood.iter() {
-            if self.octupuses[*row][*col].energylevel > 9 && !flashed.contains(&(*row,*col)) {
-                flashed.insert((*row, *col));
-                let neighbours = Grid::neighbours(*row, *col);
-                neighbours.iter().for_each(|(x,y)| self.octupuses[*x][*y].energylevel += 1 );
+    fn flash(&mut self, neighbourhood: Vec<Pos>, flashed: &mut HashSet<Pos>) {
+        for pos in neighbourhood {
+            if self.octupuses[pos].energylevel > 9 && !flashed.contains(&pos) {
+                flashed.insert(pos);
+                let neighbours: Vec<Pos> = self.octupuses.neighbours8(pos).collect();
+                neighbours.iter().for_each(|&n| self.octupuses[n].energylevel += 1 );
                 self.flash(neighbours, flashed);
             }
         }
     } 
 
-    fn next(& mut self) -> u32 {
-        self.octupuses.iter_mut()
-            .for_each(|row| row.iter_mut()
-                .for_each(|octopus| octopus.energylevel += 1 )
-            );
+    /// The octopuses a step later, each one that flashed back at 0.
+    fn next(&self) -> Grid {
+        let mut next = Grid { octupuses: self.octupuses.clone() };
+        next.octupuses.values_mut()
+            .for_each(|octopus| octopus.energylevel += 1 );
         
-        self.flash(self.octupuses.iter().enumerate()
-            .map(|(x, row)| row.iter().enumerate().map(move |(y, _o)| (x,y))).flatten().collect(),
-            &mut HashSet::new()
-        );    
+        next.flash(next.octupuses.positions().collect(), &mut HashSet::new());
+        next.octupuses.values_mut().for_each(|octopus| { octopus.flash(); });
+        return next;
+    }
 
-        return self.octupuses.iter_mut()
-            .map(|row| row.iter_mut()
-                .map(|octopus| octopus.flash())
-                .filter(|flashed| *flashed)
-            )
-            .flatten()
+    fn flashed(&self) -> u32 {
+        return self.octupuses.values()
+            .filter(|octopus| octopus.energylevel == 0)
             .count() as u32;
     }
 }
 
-pub fn partOne(input: &str) -> u32 {
-    let mut grid: Grid = input.parse().unwrap();
+pub fn partOne(input: &str) -> Result<u32, ParseError> {
+    let mut grid = Automaton::new(input.parse::<Grid>()?, Grid::next);
     //println!("Iteration: {}, flashed: {} grid: {:?}", 0, grid.flash_count, grid.octupuses);
-    return (0..100).map(|_i| grid.next()).sum();
+    return Ok((0..100).map(|_i| grid.step().flashed()).sum());
 }
 
-pub fn partTwo(input: &str) -> u32 {
-    let mut grid: Grid = input.parse().unwrap();
-    let mut count = 1;
-    while grid.next() != 100 {
-        count += 1;
-    }
-    return count;
+pub fn partTwo(input: &str) -> Result<u32, ParseError> {
+    let mut grid = Automaton::new(input.parse::<Grid>()?, Grid::next);
+    return Ok(grid.run_until(|octopuses| octopuses.flashed() == octopuses.octupuses.rows() as u32 * octopuses.octupuses.cols() as u32) as u32);
 }
 
+aoc_core::register!(aoc_core::Solution::try_functions(crate::YEAR, 11, partOne, partTwo));
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -128,7 +93,7 @@ mod tests {
         5283751526
         ";
         let res = partOne(input);
-        assert_eq!(1656, res);
+        assert_eq!(Ok(1656), res);
     }
 
     #[test]
@@ -145,6 +110,6 @@ mod tests {
         5283751526
         ";
         let res = partTwo(input);
-        assert_eq!(195, res);
+        assert_eq!(Ok(195), res);
     }
 }
\ No newline at end of file
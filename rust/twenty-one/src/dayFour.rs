@@ -138,6 +138,8 @@ pub fn partTwo(input: &str) -> u32 {
     return lastWinnerNr * lastWinner;
 }
 
+aoc_core::register!(aoc_core::Solution::functions(crate::YEAR, 4, partOne, partTwo));
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -117,12 +117,13 @@ pub fn partTwo(input: &str) -> u64 {
     }
 
     //println!("{:?}", new_counter);
-    println!("{:?}", frequencies);
     let mostCommon = frequencies.values().max();
     let leastCommon = frequencies.values().min();
     return mostCommon.unwrap() - leastCommon.unwrap();
 }
 
+aoc_core::register!(aoc_core::Solution::functions(crate::YEAR, 14, partOne, partTwo));
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,117 +1,68 @@
 use std::str::FromStr;
-use std::collections::VecDeque;
-use std::collections::HashSet;
-use std::iter::FromIterator;
+use aoc_utils::{search, Grid};
+use aoc_utils::grid::{ParseError, Pos};
 
 #[derive(Debug)]
-struct HeightMap {
-    map: Vec<Vec<u32>>,
-    rows: usize,
-    cols: usize
-}
+struct HeightMap(Grid<u32>);
 
 impl HeightMap {
-    fn orthogonal_neighbours(&self, x: &usize, y: &usize) -> Vec<(usize, usize)> {
-        let mut neighbours = Vec::new();
-        if *x >= 1 {
-            neighbours.push((x - 1, *y));
-        }
-        if x + 1 < self.rows {
-            neighbours.push((x + 1, *y));
-        }
-        if *y >= 1 {
-            neighbours.push((*x, y - 1));
-        }
-        if y + 1 < self.cols {
-            neighbours.push((*x, y + 1));
-        } 
-        
-        return neighbours;
-    }
-
-    fn low_points(&self) -> Vec<(usize, usize)> {
-        let mut low_points = Vec::new();
-        for x in 0..self.map.len() {
-            for y in 0..self.map[0].len() {
-                let smallestNeighbour = match self.orthogonal_neighbours(&x, &y).iter()
-                    .map(|(row, col)| self.map[*row][*col])
-                    .min() {
-                        Some(risk) => risk,
-                        None => u32::MAX
-                    };
-                if self.map[x][y] < smallestNeighbour {
-                    low_points.push((x,y));
-                }
-            }
-        }
-        return low_points;
+    fn low_points(&self) -> Vec<Pos> {
+        return self.0.positions()
+            .filter(|&pos| {
+                let smallestNeighbour = match self.0.neighbours4(pos).map(|n| self.0[n]).min() {
+                    Some(risk) => risk,
+                    None => u32::MAX
+                };
+                self.0[pos] < smallestNeighbour
+            })
+            .collect();
     }
 
     fn total_risk(&self) -> u32 {
         return self.low_points().iter()
-            .map(|(x,y)| self.map[*x][*y] + 1)
+            .map(|&pos| self.0[pos] + 1)
             .sum();
     }
 
-    fn adjacent(&self, x: &usize, y: &usize) -> Vec<(usize, usize)> {
-        return self.orthogonal_neighbours(x, y)
-            .into_iter()
-            .filter(|(row, col)| self.map[*row][*col] < 9)
+    fn adjacent(&self, pos: Pos) -> Vec<Pos> {
+        return self.0.neighbours4(pos)
+            .filter(|&n| self.0[n] < 9)
             .collect();
     }
 
-    fn basin(&self, x: &usize, y: &usize) -> Vec<(usize, usize)> {
-
-        // breadth first search to find all positions connected to (x,y)
-        let mut queue: VecDeque<(usize, usize)> = VecDeque::new();
-        let mut visited: HashSet<(usize, usize)> = HashSet::new(); 
-        queue.push_back((*x, *y));
-        visited.insert((*x, *y));
-        while let Some(v) = queue.pop_front() {
-            for n in self.adjacent(&v.0, &v.1) {
-                if !visited.contains(&n) {
-                    visited.insert(n);
-                    queue.push_back(n);
-                }
-            }
-        };
-
-        return Vec::from_iter(visited);
+    fn basin(&self, pos: Pos) -> Vec<Pos> {
+        // breadth first search to find all positions connected to pos
+        return search::bfs_all(pos, |&v| self.adjacent(v)).states().copied().collect();
     }
 
-    fn basins(&self) -> Vec<Vec<(usize, usize)>> {
+    fn basins(&self) -> Vec<Vec<Pos>> {
         return self.low_points().iter()
-            .map(|(x,y)| self.basin(x,y))
+            .map(|&pos| self.basin(pos))
             .collect();
     }
 }
 
 impl FromStr for HeightMap {
-    type Err = &'static str;
+    type Err = ParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let heights: Vec<Vec<u32>> = input.lines()
-            .map(|line| line.trim())
-            .filter(|line| !line.is_empty())
-            .map(|line| line.chars()
-                .map(|c| match c.to_digit(10) { Some(x) => x, None => 999})
-                .collect()
-            ).collect();
-        return Ok(HeightMap { map: heights, rows: input.lines().count(), cols: input.lines().next().unwrap().chars().count() });
+        return Grid::parse(input, |c| c.to_digit(10)).map(HeightMap);
     }
 }
-pub fn partOne(input: &str) -> u32 {
-    let heightMap: HeightMap = input.parse().unwrap();
-    return heightMap.total_risk();
+pub fn partOne(input: &str) -> Result<u32, ParseError> {
+    let heightMap: HeightMap = input.parse()?;
+    return Ok(heightMap.total_risk());
 }
 
-pub fn partTwo(input: &str) -> u32 {
-    let heightMap: HeightMap = input.parse().unwrap();
+pub fn partTwo(input: &str) -> Result<u32, ParseError> {
+    let heightMap: HeightMap = input.parse()?;
     let mut basins = heightMap.basins();
         basins.sort_by(|a,b| b.len().cmp(&a.len()));
-    return basins.iter().take(3).map(|b| b.len() as u32).product();
+    return Ok(basins.iter().take(3).map(|b| b.len() as u32).product());
 }
 
+aoc_core::register!(aoc_core::Solution::try_functions(crate::YEAR, 9, partOne, partTwo));
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -124,7 +75,7 @@ mod tests {
         8767896789
         9899965678";
         let res = partOne(input);
-        assert_eq!(15, res);
+        assert_eq!(Ok(15), res);
     }
 
     #[test]
@@ -135,6 +86,6 @@ mod tests {
         8767896789
         9899965678";
         let res = partTwo(input);
-        assert_eq!(1134, res);
+        assert_eq!(Ok(1134), res);
     }
 }
\ No newline at end of file
@@ -2,79 +2,32 @@ use regex::Regex;
 use std::str::FromStr;
 use std::collections::HashMap;
 use itertools::Itertools;
+use aoc_utils::point::{self, Point};
 use std::collections::HashSet;
 use std::iter::FromIterator;
 
 #[derive(Debug)]
 struct BeaconMap {
-    scanners: HashMap<usize, HashSet<Coordinate>>
+    scanners: HashMap<usize, HashSet<Point<3>>>
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy)]
-struct Coordinate(i32,i32,i32);
-
-type CoordinateErr = &'static str;
-
-impl Coordinate {
-    
-    fn createVector(&self, to: &Coordinate) -> Coordinate {
-        return Coordinate( to.0 - self.0, to.1 - self.1, to.2 - self.2 );
-    }
-
-    fn translate(&self, translation: &Coordinate) -> Coordinate {
-        return Coordinate( self.0 - translation.0, self.1 - translation.1, self.2 - translation.2);
-    }
-
-    fn manhattan(&self, other: &Coordinate) -> u32 {
-        return ((self.0 - other.0).abs() + (self.1 - other.1).abs() + (self.2 - other.2).abs()) as u32;
-    }
-
-    fn toVec(&self) -> Vec<i32> {
-        return vec![ self.0, self.1, self.2 ];
-    }
-
-    fn fromVec(coords: Vec<i32>) -> Result<Self, CoordinateErr> {
-        if coords.len() == 2 {
-            return Ok(Coordinate(coords[0], coords[1], 0));
-        } else if coords.len() == 3 {
-            return Ok(Coordinate(coords[0], coords[1], coords[2]));
-        }
-        return Err("Unable to create Coordinate from Vec");
-    }
-}
-
-impl FromStr for Coordinate {
-    type Err = &'static str;
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let nrs: Result<Vec<i32>, _> = Regex::new(r"(-?\d+)").unwrap().captures_iter(input)
-            .filter_map(|cap| cap.get(0))
-            .map(|nr| nr.as_str())
-            .map(|nr| nr.parse::<i32>())
-            .collect();
-        
-        if let Ok(coords) = nrs {
-            if coords.len() == 2 {
-                return Ok(Coordinate(coords[0], coords[1], 0));
-            } else if coords.len() == 3 {
-                return Ok(Coordinate(coords[0], coords[1], coords[2]));
-            }
-        }
-        return Err("Unable to parse into Coordinate");
-    }
+/// A beacon, read from `x,y,z` or from `x,y` in the flat examples.
+fn parseBeacon(line: &str) -> Result<Point<3>, point::ParseError> {
+    return line.parse::<Point<3>>().or_else(|_| line.parse::<Point<2>>().map(|p| p.resize()));
 }
 
 impl FromStr for BeaconMap {
     type Err = &'static str;
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let scanners: HashMap<usize, HashSet<Coordinate>> = Regex::new(r"--- scanner \d+ ---").unwrap()
+        let scanners: HashMap<usize, HashSet<Point<3>>> = Regex::new(r"--- scanner \d+ ---").unwrap()
             .split(input)
             .filter(|block| !block.is_empty())
             .enumerate()
             .map(|(i, block)| {
-                let scanners: HashSet<Coordinate> = block.lines()
+                let scanners: HashSet<Point<3>> = block.lines()
                     .map(|l| l.trim())
                     .filter(|l| !l.is_empty())
-                    .map(|l| l.parse().unwrap())
+                    .map(|l| parseBeacon(l).unwrap())
                     .collect();
                 (i, scanners)
                 }
@@ -84,26 +37,26 @@ impl FromStr for BeaconMap {
 }
 
 impl BeaconMap {
-    fn beaconVectors(&self, orientation: Option<&Orientation>, scannerId: &usize) -> HashMap<Coordinate, (Coordinate, Coordinate)> {
+    fn beaconVectors(&self, orientation: Option<&Orientation>, scannerId: &usize) -> HashMap<Point<3>, (Point<3>, Point<3>)> {
         return match self.scanners.get(scannerId) {
             None => HashMap::new(),
             Some(beacons) => beacons.iter()
                 .map(|b| match orientation { Some(or) => or.rotate(b), None => b.clone() })
                 .tuple_combinations().map(|(p1, p2)| {
                 //println!("from {:?} to {:?}", p1, p2);
-                let key = if p1 >= p2 { p1.createVector(&p2) } else { p2.createVector(&p1) };
+                let key = if p1 >= p2 { p2 - p1 } else { p1 - p2 };
                 (key, (p1, p2))
             })
             .collect()
         }
     }
 
-    fn overlap(&self, scannerId1: &usize, scannerId2: &usize, orientations: &Vec<Orientation>) -> Option<(Orientation, Coordinate, HashSet<Coordinate>)> {
+    fn overlap(&self, scannerId1: &usize, scannerId2: &usize, orientations: &Vec<Orientation>) -> Option<(Orientation, Point<3>, HashSet<Point<3>>)> {
         let scanner1BeaconMap = self.beaconVectors(None, scannerId1);
         for rot in orientations.iter() {
             let mut rotatedVectors = self.beaconVectors(Some(rot), scannerId2);
             rotatedVectors.retain(|k, _| scanner1BeaconMap.contains_key(k));
-            let overlappingBeacons: HashSet<&Coordinate> = scanner1BeaconMap.iter()
+            let overlappingBeacons: HashSet<&Point<3>> = scanner1BeaconMap.iter()
                 .filter(|(k,_beacons)| rotatedVectors.contains_key(k))
                 .map(|(_k, (b1, b2))| [b1,b2]).flatten().collect();
         
@@ -114,10 +67,10 @@ impl BeaconMap {
                 let commonBeacon = rotatedVectors.iter().next().unwrap();
                 // calculate translation from it.
                 let originBeacon = scanner1BeaconMap.get(commonBeacon.0).unwrap();
-                let translation0 = originBeacon.0.createVector(&commonBeacon.1.0);
-                let translation1 = originBeacon.0.createVector(&commonBeacon.1.1);
-                let translation2 = originBeacon.1.createVector(&commonBeacon.1.0);
-                let translation3 = originBeacon.1.createVector(&commonBeacon.1.1);
+                let translation0 = commonBeacon.1.0 - originBeacon.0;
+                let translation1 = commonBeacon.1.1 - originBeacon.0;
+                let translation2 = commonBeacon.1.0 - originBeacon.1;
+                let translation3 = commonBeacon.1.1 - originBeacon.1;
                 let translation = if translation0 == translation1 || translation0 == translation2 || translation0 == translation3 {
                     translation0
                 } else {
@@ -131,7 +84,7 @@ impl BeaconMap {
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
-struct Orientation(Vec<Vec<i32>>);
+struct Orientation(Vec<Vec<i64>>);
 
 impl Orientation {
     fn axis_rotations() -> Vec<Orientation> {
@@ -144,19 +97,19 @@ impl Orientation {
                     let gamma = g as f32 * (std::f32::consts::PI / 2f32);
                     let rot = vec![
                         vec![
-                            (beta.cos() * gamma.cos()) as i32,
-                            (alpha.sin() * beta.sin() * gamma.cos() - alpha.cos() * gamma.sin()) as i32,
-                            (alpha.cos() * beta.sin() * gamma.cos() + alpha.sin() * gamma.sin()) as i32
+                            (beta.cos() * gamma.cos()) as i64,
+                            (alpha.sin() * beta.sin() * gamma.cos() - alpha.cos() * gamma.sin()) as i64,
+                            (alpha.cos() * beta.sin() * gamma.cos() + alpha.sin() * gamma.sin()) as i64
                         ],
                         vec![
-                            (beta.cos() * gamma.sin()) as i32,
-                            (alpha.sin() * beta.sin() * gamma.sin() + alpha.cos() * gamma.cos()) as i32,
-                            (alpha.cos() * beta.sin() * gamma.sin() - alpha.sin() * gamma.cos()) as i32
+                            (beta.cos() * gamma.sin()) as i64,
+                            (alpha.sin() * beta.sin() * gamma.sin() + alpha.cos() * gamma.cos()) as i64,
+                            (alpha.cos() * beta.sin() * gamma.sin() - alpha.sin() * gamma.cos()) as i64
                         ],
                         vec![
-                            (-beta.sin()) as i32,
-                            (alpha.sin() * beta.cos()) as i32,
-                            (alpha.cos() * beta.cos()) as i32
+                            (-beta.sin()) as i64,
+                            (alpha.sin() * beta.cos()) as i64,
+                            (alpha.cos() * beta.cos()) as i64
                         ]
                     ];
                     orientations.insert(Orientation(rot));
@@ -167,25 +120,19 @@ impl Orientation {
         return Vec::from_iter(orientations.into_iter());
     }
 
-    fn rotate(&self, columnVector: &Coordinate) -> Coordinate {
-        let coords = columnVector.toVec();
-        let mut res = Vec::new();
-        for row in self.0.iter() {
-            let u = row.iter().zip(coords.iter()).map(|(w,k)| w * k).sum();
-            res.push(u);
-        }
-        return Coordinate::fromVec(res).unwrap();
+    fn rotate(&self, columnVector: &Point<3>) -> Point<3> {
+        return Point::from_fn(|i| self.0[i].iter().zip(columnVector.0.iter()).map(|(w,k)| w * k).sum());
     }
 }
 
-fn transform(beacons: HashSet<Coordinate>, rotation: &Orientation, translation: &Coordinate) ->  HashSet<Coordinate> {
+fn transform(beacons: HashSet<Point<3>>, rotation: &Orientation, translation: &Point<3>) ->  HashSet<Point<3>> {
     return beacons.iter().map(|beacon| rotation.rotate(beacon))
-                 .map(|rotated| rotated.translate(translation))
+                 .map(|rotated| rotated - *translation)
                  .collect();
 }
 
-fn recurTransform(source: &usize, beaconMap: &BeaconMap, orientations: &Vec<Orientation>, visited: &mut HashSet<(usize, usize)>) -> HashSet<Coordinate> {
-    let mut beacons: HashSet<Coordinate> = beaconMap.scanners.get(source).unwrap().iter().cloned().collect();
+fn recurTransform(source: &usize, beaconMap: &BeaconMap, orientations: &Vec<Orientation>, visited: &mut HashSet<(usize, usize)>) -> HashSet<Point<3>> {
+    let mut beacons: HashSet<Point<3>> = beaconMap.scanners.get(source).unwrap().iter().cloned().collect();
     for targetScanner in beaconMap.scanners.keys() {
         if visited.contains(&(*source,*targetScanner)) || source == targetScanner {
             continue;
@@ -237,7 +184,7 @@ pub fn partOne(input: &str) -> u32 {
     */
 }
 
-fn scannerDistances(source: &usize, beaconMap: &BeaconMap, orientations: &Vec<Orientation>, visited: &mut HashSet<(usize, usize)>) -> Vec<Coordinate> {
+fn scannerDistances(source: &usize, beaconMap: &BeaconMap, orientations: &Vec<Orientation>, visited: &mut HashSet<(usize, usize)>) -> Vec<Point<3>> {
     let mut overlaps = vec![];
     for targetScanner in beaconMap.scanners.keys() {
         if visited.contains(&(*source,*targetScanner)) || source == targetScanner {
@@ -248,11 +195,11 @@ fn scannerDistances(source: &usize, beaconMap: &BeaconMap, orientations: &Vec<Or
             visited.insert((*targetScanner, *source));
             let dist = scannerDistances(targetScanner, beaconMap, orientations, visited);
             for i in 0..dist.len() {
-                overlaps.push(rotation.rotate(&dist[i]).translate(&translation));
+                overlaps.push(rotation.rotate(&dist[i]) - translation);
             }
         };
     }
-    overlaps.push(Coordinate(0,0,0));
+    overlaps.push(Point::ORIGIN);
     return overlaps;
 } 
 
@@ -262,10 +209,12 @@ pub fn partTwo(input: &str) -> u32 {
     let overlaps = scannerDistances(&0, &map, &orientations, &mut HashSet::new());
     return match overlaps.iter()
         .tuple_combinations()
-        .map(|(a,b)| a.manhattan(b))
+        .map(|(a,b)| a.manhattan(b) as u32)
         .max() { Some(max) => max, None => 0 };
 }
 
+aoc_core::register!(aoc_core::Solution::functions(crate::YEAR, 19, partOne, partTwo));
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -347,13 +296,13 @@ mod tests {
         0,7,-8";
         let expected: BeaconMap = expectedInput.parse().unwrap();
         // put scanner 1-4 in a vector of HashSet<Coordinate>
-        let expectedRotations: Vec<HashSet<Coordinate>> = expected.scanners.values()
+        let expectedRotations: Vec<HashSet<Point<3>>> = expected.scanners.values()
             .map(|beacons| HashSet::from_iter(beacons.iter().cloned()))
             .collect();
         // for each rotation, put rotated points in a Vec<Hashset<Coordinates>>
         let orientations = Orientation::axis_rotations();
         assert_eq!(24, orientations.len());
-        let res: Vec<HashSet<Coordinate>> = orientations.iter().map(
+        let res: Vec<HashSet<Point<3>>> = orientations.iter().map(
             |rot| map.scanners.get(&0).unwrap().iter().map(|v| rot.rotate(v)).collect()
         ).collect();
         // for each expected Hashset compare against all the rotated ones, if all expected match rotations are ok
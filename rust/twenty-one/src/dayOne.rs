@@ -0,0 +1,77 @@
+pub fn partOne(input: &str) -> i32 {
+    let lines: Result<Vec<i32>, _> = input.split("\n")
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| line.parse())
+        .collect();
+    
+    let res: i32 = lines.unwrap().windows(2)
+        .map(|chunk| 
+            match chunk.len() {
+                2 => if chunk[1] > chunk[0] { return 1; } else { return 0; }, 
+                _ => 0
+            }
+        ).sum();
+    return res;
+}
+
+pub fn partTwo(input: &str) -> i32 {
+    let lines: Result<Vec<i32>, _> = input.split("\n")
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| line.parse())
+        .collect();
+    
+    let res: i32 = lines.unwrap().windows(3)
+        .map(|chunk| chunk.iter().sum())
+        .collect::<Vec<i32>>()
+        .windows(2)
+        .map(|chunk| 
+            match chunk.len() {
+                2 => if chunk[1] > chunk[0] { return 1; } else { return 0; }, 
+                _ => 0
+            }
+        ).sum();
+    return res;
+}
+
+aoc_core::register!(aoc_core::Solution::functions(crate::YEAR, 1, partOne, partTwo));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partOneExample() {
+        let input = "199
+        200
+        208
+        210
+        200
+        207
+        240
+        269
+        260
+        263
+        ";
+        let res = partOne(input);
+        assert_eq!(7, res);
+    }
+
+    #[test]
+    fn part2Example() {
+        let input = "199
+        200
+        208
+        210
+        200
+        207
+        240
+        269
+        260
+        263
+        ";
+        let res = partTwo(input);
+        assert_eq!(5, res);
+    }
+}
@@ -10,7 +10,7 @@ impl FromStr for Crabs {
     fn from_str(input: &str) -> Result<Self, Self::Err> {
         let positions: Vec<i32> = input.split(",")
             .map(|sub_str| sub_str.trim())
-            .map(|nr| match nr.parse() { Err(_e) => { println!("{}", nr); -1 }, Ok(x) => x } )
+            .filter_map(|nr| nr.parse().ok())
             .collect();
         return Ok(Crabs { positions });
     }
@@ -50,6 +50,8 @@ fn fuelCost(distance: &i32) -> i32 {
     return delta * (1 + delta) / 2;
 }
 
+aoc_core::register!(aoc_core::Solution::functions(crate::YEAR, 7, partOne, partTwo));
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -59,6 +61,7 @@ mod tests {
         let input = "16,1,2,0,4,2,7,1,2,14";
         let res = partOne(input);
         assert_eq!(37, res);
+        assert_eq!(37, partOne("16,1,2,0,4,2,7,1,2,14,\n"));
     }
     
     #[test]
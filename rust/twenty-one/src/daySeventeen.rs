@@ -2,29 +2,25 @@ use regex::Regex;
 use std::str::FromStr;
 use std::ops::RangeInclusive;
 use std::f32;
+use aoc_utils::Point;
 
 #[derive(Debug)]
 struct Trajectory {
-    target_area: (RangeInclusive<i32>, RangeInclusive<i32>),
+    target_area: (RangeInclusive<i64>, RangeInclusive<i64>),
     v_init: Velocity,
-    y_max: i32
+    y_max: i64
 }
 
 #[derive(Debug, Clone, Copy)]
-struct Velocity(i32, i32);
+struct Velocity(Point<2>);
 
 impl Velocity {
     fn next(&mut self) {
-        if self.0 > 0 {
-            self.0 -= 1;
-        } else if self.0 < 0 {
-            self.0 += 1;
-        }
-        self.1 -= 1;
+        self.0 -= Point([self.0.x().signum(), 1]);
     }
 
-    fn yMax(&self) -> i32 {
-        let Velocity(_x_0, y_0) = self;
+    fn yMax(&self) -> i64 {
+        let y_0 = &self.0.y();
         if y_0 > &0 {
             return y_0 * (1 + y_0) / 2;
         } else {
@@ -33,38 +29,28 @@ impl Velocity {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-struct Coordinate(i32, i32);
-
-impl Coordinate {
-    fn next(&mut self, v: Velocity) {
-        self.0 = self.0 + v.0;
-        self.1 = self.1 + v.1;
-    }
-}
-
 impl FromStr for Trajectory {
     type Err = &'static str;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
         let TARGET_BOX_PATTERN: Regex = Regex::new(r"(-*\d+)").unwrap();
         let caps = TARGET_BOX_PATTERN.captures_iter(input);
-        let numbers: Result<Vec<i32>, _> = caps.filter_map(|m| m.get(0))
+        let numbers: Result<Vec<i64>, _> = caps.filter_map(|m| m.get(0))
             .map(|nr| nr.as_str())
-            .map(|nr| nr.parse::<i32>())
+            .map(|nr| nr.parse::<i64>())
             .collect();
         let area = match numbers {
             Ok(nrs) => (nrs[0]..=nrs[1], nrs[2]..=nrs[3]),
             _ => (0..=0, 0..=0)
         };
-        return Ok(Trajectory { target_area: area, v_init: Velocity(0,0), y_max: 0 });
+        return Ok(Trajectory { target_area: area, v_init: Velocity(Point::ORIGIN), y_max: 0 });
     }
 }
 
 impl Trajectory {
 
-    fn simulate(&mut self) -> Option<Coordinate> {
-        let mut pos = Coordinate(0,0);
+    fn simulate(&mut self) -> Option<Point<2>> {
+        let mut pos = Point::ORIGIN;
         loop {
             //println!("{:?}", pos);
             if self.is_hit(&pos) {
@@ -75,21 +61,21 @@ impl Trajectory {
                 //println!("{:?} is a MISS ", pos);
                 return None;
             }
-            pos.next(self.v_init);
-            if pos.1 > self.y_max {
+            pos += self.v_init.0;
+            if pos.y() > self.y_max {
                 //println!("new y_max {:?} old y_max {}", pos, self.y_max);
-                self.y_max = pos.1;
+                self.y_max = pos.y();
             }
             self.v_init.next();
         }
     }
 
-    fn is_hit(&self, pos: &Coordinate) -> bool {
-        let Coordinate(x, y) = pos;
+    fn is_hit(&self, pos: &Point<2>) -> bool {
+        let Point([x, y]) = pos;
         return self.target_area.0.contains(x) && self.target_area.1.contains(y);
     }
 
-    fn is_miss(&self, Coordinate(x, y): &Coordinate) -> bool {
+    fn is_miss(&self, Point([x, y]): &Point<2>) -> bool {
         let x_overshot = x > self.target_area.0.end();
         let y_overshot = if *self.target_area.1.end() < 0 && *y > 0 {
             false
@@ -129,12 +115,12 @@ v_x0_min = -1 +- sqrt (1 + 4 * 2 * x_target_min) / 2
 v_x0_min --> T_tot = 
 */
 
-pub fn partOne(input: &str) -> i32 {
+pub fn partOne(input: &str) -> i64 {
     let mut trajectory: Trajectory = input.parse().unwrap();
-    let x_0 = (((1f32 + 8f32 * *trajectory.target_area.0.start() as f32).sqrt() - 1f32) / 2f32).ceil() as i32;
+    let x_0 = (((1f32 + 8f32 * *trajectory.target_area.0.start() as f32).sqrt() - 1f32) / 2f32).ceil() as i64;
     let mut y_max = 0;
     let mut v_y = 0;
-    trajectory.v_init = Velocity(x_0, v_y);
+    trajectory.v_init = Velocity(Point([x_0, v_y]));
     for _i in 0..200 {
         let possible = trajectory.v_init.yMax();
         let res = trajectory.simulate();
@@ -145,19 +131,19 @@ pub fn partOne(input: &str) -> i32 {
             _ => ()
         }
         v_y += 1;
-        trajectory.v_init = Velocity(x_0, v_y);
+        trajectory.v_init = Velocity(Point([x_0, v_y]));
     }
     return y_max;
 }
 
-pub fn partTwo(input: &str) -> i32 {
+pub fn partTwo(input: &str) -> i64 {
     let mut trajectory: Trajectory = input.parse().unwrap();
-    let x_0 = (((1f32 + 8f32 * *trajectory.target_area.0.start() as f32).sqrt() - 1f32) / 2f32).ceil() as i32;
+    let x_0 = (((1f32 + 8f32 * *trajectory.target_area.0.start() as f32).sqrt() - 1f32) / 2f32).ceil() as i64;
     let mut count = 0;
     
     for v_x in x_0..500 {
         for v_y in -600..600 {
-            trajectory.v_init = Velocity(v_x, v_y);
+            trajectory.v_init = Velocity(Point([v_x, v_y]));
             let res = trajectory.simulate();
             match res {
                 Some(_c) => {
@@ -171,6 +157,8 @@ pub fn partTwo(input: &str) -> i32 {
     return count;
 }
 
+aoc_core::register!(aoc_core::Solution::functions(crate::YEAR, 17, partOne, partTwo));
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -186,41 +174,41 @@ mod tests {
     fn simulateTest() {
         const INPUT: &str = "target area: x=20..30, y=-10..-5";
         let mut trajectory: Trajectory = INPUT.parse().unwrap();
-        trajectory.v_init = Velocity(7,2);
+        trajectory.v_init = Velocity(Point([7,2]));
         
         let res = trajectory.simulate();
         let (x, y) = match res {
-            Some(Coordinate(x,y)) => (x,y),
+            Some(Point([x,y])) => (x,y),
             None => (0,0)
         };
         assert_eq!(28, x);
         assert_eq!(-7, y);
 
-        trajectory.v_init = Velocity(6,3);
+        trajectory.v_init = Velocity(Point([6,3]));
         
         let res = trajectory.simulate();
         let (x, y) = match res {
-            Some(Coordinate(x,y)) => (x,y),
+            Some(Point([x,y])) => (x,y),
             None => (0,0)
         };
         assert_eq!(21, x);
         assert_eq!(-9, y);
 
-        trajectory.v_init = Velocity(9,0);
+        trajectory.v_init = Velocity(Point([9,0]));
         
         let res = trajectory.simulate();
         let (x, y) = match res {
-            Some(Coordinate(x,y)) => (x,y),
+            Some(Point([x,y])) => (x,y),
             None => (0,0)
         };
         assert_eq!(-6, y);
         assert_eq!(30, x);
 
-        trajectory.v_init = Velocity(17,-4);
+        trajectory.v_init = Velocity(Point([17,-4]));
         
         let res = trajectory.simulate();
         let (x, y) = match res {
-            Some(Coordinate(x,y)) => (x,y),
+            Some(Point([x,y])) => (x,y),
             None => (0,0)
         };
         assert_eq!(0, y);
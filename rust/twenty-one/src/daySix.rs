@@ -89,6 +89,8 @@ pub fn partTwo(input: &str) -> u64 {
     return 0;
 }
 
+aoc_core::register!(aoc_core::Solution::functions(crate::YEAR, 6, partOne, partTwo));
+
 #[cfg(test)]
 mod tests {
     use super::*;
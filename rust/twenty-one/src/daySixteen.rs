@@ -197,6 +197,8 @@ impl Packet {
     }
 }
 
+aoc_core::register!(aoc_core::Solution::functions(crate::YEAR, 16, partOne, partTwo));
+
 #[cfg(test)]
 mod tests {
     use super::*;
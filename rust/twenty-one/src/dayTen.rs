@@ -182,6 +182,8 @@ pub fn partTwo(input: &str) -> u64 {
     return scores[(autocomplete.score().len() + (2 - 1)) / 2 - 1];
 }
 
+aoc_core::register!(aoc_core::Solution::functions(crate::YEAR, 10, partOne, partTwo));
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,58 +1,42 @@
 use regex::Regex;
 use std::str::FromStr;
 use std::collections::HashSet;
-
-#[derive(Debug)]
-#[derive(PartialEq, Eq, Hash, Copy, Clone)]
-struct Coordinate(i32,i32);
-
-impl FromStr for Coordinate {
-    type Err = &'static str;
-
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let coords: Result<Vec<i32>, _> = input.split(",").map(|c| c.parse()).collect();
-        return match coords {
-            Ok(coords) if coords.len() == 2 => Ok(Coordinate(coords[0], coords[1])),
-            Ok(_c) => Err("Only supports 2D coordinates"),
-            Err(_) => Err("Unable to parse coordinates")
-        }
-    }
-}
+use aoc_utils::Point;
 
 #[derive(Debug)]
 enum Folding {
-    XAXIS(i32),
-    YAXIS(i32)
+    XAXIS(i64),
+    YAXIS(i64)
 }
 
 impl Folding {
 
-    fn fold_on_x(offset: &i32, coordinate: &Coordinate) -> Coordinate {
+    fn fold_on_x(offset: &i64, coordinate: &Point<2>) -> Point<2> {
         //println!("fold on x {} {},{}", offset, coordinate.0, coordinate.1);
-        let c: Coordinate = match offset {
-            offset if offset > &coordinate.0 => Coordinate::from(*coordinate),
+        let c: Point<2> = match offset {
+            offset if offset > &coordinate.x() => *coordinate,
             offset => {
-                let delta = coordinate.0 - offset;
-                Coordinate(offset - delta, coordinate.1)
+                let delta = coordinate.x() - offset;
+                Point([offset - delta, coordinate.y()])
             }
         };
         //println!("{},{}", c.0, c.1);
         return c;
     }
 
-    fn fold_on_y(offset: &i32, coordinate: &Coordinate) -> Coordinate {
+    fn fold_on_y(offset: &i64, coordinate: &Point<2>) -> Point<2> {
         //println!("fold on y {} {},{}", offset, coordinate.0, coordinate.1);
-        let c = if offset > &coordinate.1 {
-            Coordinate::from(*coordinate)
+        let c = if offset > &coordinate.y() {
+            *coordinate
         } else {
-            let delta = coordinate.1 - offset;
-            Coordinate(coordinate.0, offset - delta)
+            let delta = coordinate.y() - offset;
+            Point([coordinate.x(), offset - delta])
         };
         //println!("{},{}", c.0, c.1);
         return c;
     }
 
-    fn fold(&self, coordinate: &Coordinate) -> Coordinate {
+    fn fold(&self, coordinate: &Point<2>) -> Point<2> {
         return match self {
             Folding::XAXIS(offset) => Folding::fold_on_x(offset, coordinate),
             Folding::YAXIS(offset) => Folding::fold_on_y(offset, coordinate)
@@ -88,7 +72,7 @@ impl FromStr for Folding {
 
 pub fn partOne(input: &str) -> u32 {
     let coordinatePattern: Regex = Regex::new(r"\d+,\d+").unwrap();
-    let points: HashSet<Coordinate> = input.lines()
+    let points: HashSet<Point<2>> = input.lines()
         .map(|l| l.trim())
         .filter(|line| coordinatePattern.is_match(line))
         .filter_map(|line| line.parse().ok())
@@ -100,7 +84,7 @@ pub fn partOne(input: &str) -> u32 {
         .filter_map(|line| line.parse().ok())
         .collect();
     
-    let folded: HashSet<Coordinate> = points.iter().map(|p| foldingInstructions[0].fold(p)).collect();
+    let folded: HashSet<Point<2>> = points.iter().map(|p| foldingInstructions[0].fold(p)).collect();
     //let folded: HashSet<Coordinate> = folded.iter().map(|p| foldingInstructions[1].fold(p)).collect();
     // let folded = foldingInstructions.iter()
     //     .fold(points, |folded, folding| folded.iter().map(|point| folding.fold(point)).collect());
@@ -108,9 +92,10 @@ pub fn partOne(input: &str) -> u32 {
     return folded.len() as u32;
 }
 
-pub fn partTwo(input: &str) -> u32 {
+/// The dots left after every fold, drawn a row of `#` and `.` per line, the code to read.
+pub fn partTwo(input: &str) -> String {
     let coordinatePattern: Regex = Regex::new(r"\d+,\d+").unwrap();
-    let points: HashSet<Coordinate> = input.lines()
+    let points: HashSet<Point<2>> = input.lines()
         .map(|l| l.trim())
         .filter(|line| coordinatePattern.is_match(line))
         .filter_map(|line| line.parse().ok())
@@ -124,20 +109,16 @@ pub fn partTwo(input: &str) -> u32 {
 
     let folded = foldingInstructions.iter()
         .fold(points, |folded, folding| folded.iter().map(|point| folding.fold(point)).collect());
-    let x_max = folded.iter().map(|p| p.0).max().unwrap();
-    let y_max = folded.iter().map(|p| p.1).max().unwrap();
-    for x in 0..(x_max+5) {
-        let mut line: String = String::from("");
-        for y in 0..(y_max+5) {
-            let marker = if folded.contains(&Coordinate(x,y)) { "#" } else { "."};
-            line.push_str(marker);
-        }
-        println!("{}", line);
-    }
-    //folded.iter().for_each(|p| println!("{:?}", p));
-    return folded.len() as u32;
+    let x_max = folded.iter().map(|p| p.x()).max().unwrap_or(-1);
+    let y_max = folded.iter().map(|p| p.y()).max().unwrap_or(-1);
+    return (0..=y_max)
+        .map(|y| (0..=x_max).map(|x| if folded.contains(&Point([x, y])) { '#' } else { '.' }).collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n");
 }
 
+aoc_core::register!(aoc_core::Solution::functions(crate::YEAR, 13, partOne, partTwo));
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -168,4 +149,31 @@ mod tests {
         let res = partOne(input);
         assert_eq!(17, res);
     }
+
+    #[test]
+    fn partTwoSmallExample() {
+        let input = "6,10
+        0,14
+        9,10
+        0,3
+        10,4
+        4,11
+        6,0
+        6,12
+        4,1
+        0,13
+        10,12
+        3,4
+        3,0
+        8,4
+        1,10
+        2,14
+        8,10
+        9,0
+        
+        fold along y=7
+        fold along x=5";
+        let res = partTwo(input);
+        assert_eq!("#####\n#...#\n#...#\n#...#\n#####", res);
+    }
 }
\ No newline at end of file
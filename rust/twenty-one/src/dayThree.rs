@@ -103,6 +103,8 @@ fn getBitMask(bitsize: usize) -> u32 {
     return u32::from_str_radix(&bitmask, 2).unwrap();
 }
 
+aoc_core::register!(aoc_core::Solution::functions(crate::YEAR, 3, partOne, partTwo));
+
 #[cfg(test)]
 mod tests {
     use super::*;
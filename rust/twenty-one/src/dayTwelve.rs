@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use aoc_utils::search;
 
 /*
     Hypothesis, small-small are one way connections, large-small are bidirectional    
@@ -16,8 +17,7 @@ use std::collections::HashMap;
 
 */
 struct Caves {
-    graph: HashMap<String, Vec<String>>,
-    paths: Vec<Vec<String>>
+    graph: HashMap<String, Vec<String>>
 }
 
 impl Caves {
@@ -41,26 +41,15 @@ impl Caves {
         }
     }
 
-    fn get_neighbours(&self, v: &str) -> Vec<String> {
-        return match self.graph.get(v) { Some(n) => n.to_vec(), None => Vec::new() };
-    }
-
-    fn depth_first_search<'a>(&mut self, start: &'a str, path: &'a mut Vec<&'a str>) {
-        //println!("At Node: {}, path {:?}", start, path);
-        path.push(start);
-        if start == "end" {
-            self.paths.push(path.iter().map(|s| s.to_string()).collect());
-            return;
-        };
-
-        let neighbours = self.get_neighbours(start);
-        neighbours.iter()
-            .filter(|v| Caves::is_big_cave(v) || !path.contains(&v.as_str()))
-            .for_each(|v| self.depth_first_search(v, &mut path.to_vec()));
-            //.flatten()
-            //.for_each
-            //.collect();
-        //return paths;
+    /// Every path from start to end, going through a small cave only when `allowed` by the path so far.
+    fn paths(&self, allowed: impl Fn(&str, &Vec<&str>) -> bool) -> Vec<Vec<&str>> {
+        return search::all_paths("start", |path: &[&str]| {
+            let path = path.to_vec();
+            self.graph.get(*path.last().unwrap()).into_iter().flatten()
+                .map(|v| v.as_str())
+                .filter(|v| Caves::is_big_cave(v) || allowed(v, &path))
+                .collect::<Vec<&str>>()
+        }, |&cave| cave == "end");
     }
 
     fn small_cave_constraint(cave: &str, path: &Vec<&str>) -> bool {
@@ -82,29 +71,11 @@ impl Caves {
             return existingDuplicates == 0;
         }
     }
-
-    fn relaxed_small_cave<'a>(&mut self, start: &'a str, path: &'a mut Vec<&'a str>) {
-        //println!("relaxed_small_cave At Node: {}, path {:?}", start, path);
-        path.push(start);
-        if start == "end" {
-            self.paths.push(path.iter().map(|s| s.to_string()).collect());
-            return;
-        };
-
-        let neighbours = self.get_neighbours(start);
-        neighbours.iter()
-            .filter(|v| Caves::is_big_cave(v) || Caves::small_cave_constraint(v, path) )
-            .for_each(|v| self.relaxed_small_cave(v, &mut path.to_vec()));
-            //.flatten()
-            //.for_each
-            //.collect();
-        //return paths;
-    }
 }
 
 pub fn partOne(input: &str) -> u32 {
     //println!("PArt one input: {}", input);
-    let mut caves: Caves = Caves { graph: HashMap::new(), paths: vec![] };
+    let mut caves: Caves = Caves { graph: HashMap::new() };
     input.lines()
         .map(|line| line.trim())
         .filter(|line| !line.is_empty())
@@ -112,14 +83,14 @@ pub fn partOne(input: &str) -> u32 {
             .collect::<Vec<&str>>())
         .for_each(|nodes| caves.connect(nodes[0], nodes[1]));
     //println!("{:?}", caves.graph);
-    caves.depth_first_search("start", &mut Vec::new());
-    //caves.paths.iter().for_each(|path| println!("{:?}", path));
-    return caves.paths.len() as u32;
+    let paths = caves.paths(|v, path| !path.contains(&v));
+    //paths.iter().for_each(|path| println!("{:?}", path));
+    return paths.len() as u32;
 }
 
 pub fn partTwo(input: &str) -> u32 {
     //println!("PArt one input: {}", input);
-    let mut caves: Caves = Caves { graph: HashMap::new(), paths: vec![] };
+    let mut caves: Caves = Caves { graph: HashMap::new() };
     input.lines()
         .map(|line| line.trim())
         .filter(|line| !line.is_empty())
@@ -127,11 +98,13 @@ pub fn partTwo(input: &str) -> u32 {
             .collect::<Vec<&str>>())
         .for_each(|nodes| caves.connect(nodes[0], nodes[1]));
     //println!("{:?}", caves.graph);
-    caves.relaxed_small_cave("start", &mut Vec::new());
-    //caves.paths.iter().for_each(|path| println!("{:?}", path));
-    return caves.paths.len() as u32;
+    let paths = caves.paths(Caves::small_cave_constraint);
+    //paths.iter().for_each(|path| println!("{:?}", path));
+    return paths.len() as u32;
 }
 
+aoc_core::register!(aoc_core::Solution::functions(crate::YEAR, 12, partOne, partTwo));
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,5 +1,6 @@
 use regex::Regex;
 use std::str::FromStr;
+use crate::core::{parse_lines, BadLine, Error};
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 struct Player {
@@ -29,12 +30,12 @@ impl CylindricalPosition {
 }
 
 impl FromStr for Player {
-    type Err = &'static str;
+    type Err = BadLine;
     
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let caps = Regex::new(r"Player (\d+) starting position: (\d+)").unwrap().captures(input).unwrap();
-        let id = caps.get(1).map_or("", |m| m.as_str()).parse().unwrap();
-        let pos = caps.get(2).map_or("", |m| m.as_str()).parse().unwrap();
+        let caps = Regex::new(r"Player (\d+) starting position: (\d+)").unwrap().captures(input).ok_or(BadLine)?;
+        let id = caps.get(1).map_or("", |m| m.as_str()).parse()?;
+        let pos = caps.get(2).map_or("", |m| m.as_str()).parse()?;
         return Ok(Player { id: id, postion: CylindricalPosition(pos), points: 0 });
     }
 }
@@ -46,14 +47,10 @@ struct DiracDiceEngine {
 }
 
 impl FromStr for DiracDiceEngine {
-    type Err = &'static str;
+    type Err = Error;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let mut players: Vec<Player> = vec![];
-        for l in input.lines() {
-            let p: Player = l.parse().unwrap();
-            players.push(p);
-        }
+        let players: Vec<Player> = parse_lines(21, input, Error::parse)?;
         return Ok(DiracDiceEngine { players: players, rolls: 0 });
     }
 }
@@ -92,15 +89,17 @@ impl DiracDiceEngine {
     }
 }
 
-pub fn partOne(input: &str) -> u32 {
-    let mut engine: DiracDiceEngine = input.parse().unwrap();
+pub fn partOne(input: &str) -> Result<u32, Error> {
+    let mut engine: DiracDiceEngine = input.parse()?;
     
     while let None = engine.loser() {
         engine.roll(3);
     }
-    return engine.loser().unwrap().points * engine.rolls();
+    return Ok(engine.loser().unwrap().points * engine.rolls());
 }
 
+aoc_core::register!(aoc_core::Solution::try_part_one_only(crate::YEAR, 21, partOne));
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -127,6 +126,13 @@ mod tests {
         let input = "Player 1 starting position: 4
         Player 2 starting position: 8";
         let res = partOne(input);
-        assert_eq!(739785, res);
+        assert_eq!(Ok(739785), res);
+    }
+
+    #[test]
+    fn badPlayerTest() {
+        let input = "Player 1 starting position: 4
+        Player 2 starting at 8";
+        assert_eq!(Err(Error::parse(21, 2, "Player 2 starting at 8")), partOne(input));
     }
 }
\ No newline at end of file
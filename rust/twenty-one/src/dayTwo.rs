@@ -1,4 +1,5 @@
-use std::convert::TryFrom;
+use std::str::FromStr;
+use crate::core::{parse_lines, BadLine, Error};
 
 #[derive(Debug)]
 enum Instruction {
@@ -7,29 +8,24 @@ enum Instruction {
     DOWN(i32)
 }
 
-impl TryFrom<&str> for Instruction {
-    type Error = &'static str;
+impl FromStr for Instruction {
+    type Err = BadLine;
 
-    fn try_from(input: &str) -> Result<Self, Self::Error> {
-        let split: Vec<&str> = input.split(" ").collect();
-        return match split[0] {
-            "forward" => Ok(Instruction::FORWARD(split[1].parse().unwrap())),
-            "up" => Ok(Instruction::UP(split[1].parse().unwrap())),
-            "down" => Ok(Instruction::DOWN(split[1].parse().unwrap())),
-            _instr => Err("Unknown Instruction")
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let (direction, units) = input.split_once(" ").ok_or(BadLine)?;
+        let units = units.parse()?;
+        return match direction {
+            "forward" => Ok(Instruction::FORWARD(units)),
+            "up" => Ok(Instruction::UP(units)),
+            "down" => Ok(Instruction::DOWN(units)),
+            _instr => Err(BadLine)
         }
     }
 }
 
 
-pub fn partOne(input: &str) -> i32 {
-    let lines: Vec<Instruction> = input.split("\n")
-        .map(|line| line.trim())
-        .filter(|line| !line.is_empty())
-        .map(|line| Instruction::try_from(line))
-        .filter(|res| res.is_ok())
-        .map(|res| res.unwrap())
-        .collect();
+pub fn partOne(input: &str) -> Result<i32, Error> {
+    let lines: Vec<Instruction> = parse_lines(2, input, Error::parse)?;
     let forward: i32 = lines.iter()
         .filter(|instruction| match instruction {
             Instruction::FORWARD(_) => true,
@@ -43,7 +39,7 @@ pub fn partOne(input: &str) -> i32 {
             _ => 0
         })
         .sum();  
-    return forward * depth;
+    return Ok(forward * depth);
 }
 
 struct SubmarinePosition {
@@ -52,23 +48,18 @@ struct SubmarinePosition {
     aim: i32
 }
 
-pub fn partTwo(input: &str) -> i32 {
-    let lines: Vec<Instruction> = input.split("\n")
-        .map(|line| line.trim())
-        .filter(|line| !line.is_empty())
-        .map(|line| Instruction::try_from(line))
-        .filter(|res| res.is_ok())
-        .map(|res| res.unwrap())
-        .collect();
+pub fn partTwo(input: &str) -> Result<i32, Error> {
+    let lines: Vec<Instruction> = parse_lines(2, input, Error::parse)?;
     let p: SubmarinePosition = lines.iter()
         .fold(SubmarinePosition{ horizontal: 0, depth: 0, aim: 0 }, |pos, instruction| match instruction {
             Instruction::FORWARD(v) => SubmarinePosition{ horizontal: pos.horizontal + v, depth: pos.depth + v * pos.aim, aim: pos.aim },
             Instruction::DOWN(v) => SubmarinePosition{ horizontal: pos.horizontal, depth: pos.depth, aim: pos.aim + v },
             Instruction::UP(v) => SubmarinePosition{ horizontal: pos.horizontal, depth: pos.depth, aim: pos.aim - v }
         });
-    return p.horizontal * p.depth;
+    return Ok(p.horizontal * p.depth);
 }
 
+aoc_core::register!(aoc_core::Solution::try_functions(crate::YEAR, 2, partOne, partTwo));
 
 #[cfg(test)]
 mod tests {
@@ -83,7 +74,7 @@ mod tests {
         down 8
         forward 2";
         let res = partOne(example);
-        assert_eq!(150, res);
+        assert_eq!(Ok(150), res);
     }
 
     #[test]
@@ -95,6 +86,12 @@ mod tests {
         down 8
         forward 2";
         let res = partTwo(example);
-        assert_eq!(900, res);
+        assert_eq!(Ok(900), res);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn badInstructionTest() {
+        assert_eq!(Err(Error::parse(2, 2, "backward 5")), partOne("forward 5\nbackward 5"));
+        assert_eq!(Err(Error::parse(2, 1, "up")), partTwo("up"));
+    }
+}
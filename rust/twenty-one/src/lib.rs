@@ -1,5 +1,9 @@
 #![allow(non_snake_case)]
 
+use aoc_core::Solution;
+
+pub mod core;
+pub mod dayOne;
 pub mod dayTwo;
 pub mod dayThree;
 pub mod dayFour;
@@ -19,4 +23,12 @@ pub mod daySeventeen;
 pub mod dayEighteen;
 pub mod dayNineteen;
 pub mod dayTwenty;
-pub mod dayTwentyOne;
\ No newline at end of file
+pub mod dayTwentyOne;
+
+pub const YEAR: u16 = 2021;
+
+aoc_core::register_year!(YEAR, core::inputs);
+
+pub fn solutions() -> Vec<Solution> {
+    aoc_core::registry::solutions(YEAR)
+}
@@ -1,10 +1,10 @@
 #![allow(non_snake_case)]
 
+use std::fmt;
 use std::path::PathBuf;
-use std::fs;
-use std::collections::HashMap;
-use std::hash::Hash;
-use itertools::Itertools;
+use aoc_core::{Inputs, Naming, Part, Puzzle, Solution};
+
+pub use aoc_core::{parse_lines, BadLine};
 
 pub trait AdventOfCodeSolver {
     fn partOne(&self) -> u64 {
@@ -13,21 +13,110 @@ pub trait AdventOfCodeSolver {
     fn partTwo(&self) -> u64 {
         return 0;
     }
+    /// Part one, for a day whose input can leave it without an answer.
+    fn tryPartOne(&self) -> Result<u64, Error> {
+        return Ok(self.partOne());
+    }
+    /// Part two, for a day whose input can leave it without an answer.
+    fn tryPartTwo(&self) -> Result<u64, Error> {
+        return Ok(self.partTwo());
+    }
     fn day(&self) -> &str;
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub struct Coordinate(pub isize, pub isize);
+fn answer(answer: Result<u64, Error>) -> Result<Option<String>, aoc_core::Error> {
+    answer.map(|answer| Some(answer.to_string())).map_err(|e| aoc_core::Error::Solve(Box::new(e)))
+}
+
+/// Lets an `AdventOfCodeSolver` be driven by the shared `aoc_core` runner.
+pub struct Solver<T>(pub T);
+
+impl<T: AdventOfCodeSolver> Puzzle for Solver<T> {
+    fn solve(&self, part: Part) -> Result<Option<String>, aoc_core::Error> {
+        match part {
+            Part::One => answer(self.0.tryPartOne()),
+            Part::Two => answer(self.0.tryPartTwo()),
+        }
+    }
+}
+
+/// Like `Solver`, for days whose part two is not solved yet.
+pub struct PartOneSolver<T>(pub T);
+
+impl<T: AdventOfCodeSolver> Puzzle for PartOneSolver<T> {
+    fn solve(&self, part: Part) -> Result<Option<String>, aoc_core::Error> {
+        match part {
+            Part::One => answer(self.0.tryPartOne()),
+            Part::Two => Ok(None),
+        }
+    }
+}
+
+pub fn solver<T, F>(day: u8, init: F) -> Solution
+    where T: AdventOfCodeSolver + 'static, F: Fn(&str) -> T + Send + Sync + 'static {
+    Solution::new(crate::YEAR, day, move |input: String| Solver(init(&input)))
+}
+
+/// Like `solver`, for a day whose `init` can reject the input.
+pub fn try_solver<T, E, F>(day: u8, init: F) -> Solution
+    where T: AdventOfCodeSolver + 'static, E: std::error::Error + Send + Sync + 'static,
+          F: Fn(&str) -> Result<T, E> + Send + Sync + 'static {
+    Solution::try_new(crate::YEAR, day, move |input: String| init(&input).map(Solver))
+}
+
+pub fn unfinished<T, F>(day: u8, init: F) -> Solution
+    where T: AdventOfCodeSolver + 'static, F: Fn(&str) -> T + Send + Sync + 'static {
+    Solution::new(crate::YEAR, day, move |input: String| PartOneSolver(init(&input)))
+}
+
+pub fn inputs() -> Inputs {
+    Inputs::locate(crate::YEAR, PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("resources"), Naming::Spelled)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// A line of the input, counting from 1, that could not be parsed.
+    Parse { day: u8, line: usize, text: String },
+    /// A line of the input, counting from 1, that was read but makes no sense, and why.
+    Invalid { day: u8, line: usize, reason: String },
+    /// The input was parsed, but has no answer.
+    NoAnswer { day: u8, reason: String },
+}
+
+impl Error {
+    pub fn parse(day: u8, line: usize, text: &str) -> Self {
+        Error::Parse { day, line, text: text.to_string() }
+    }
+
+    pub fn invalid(day: u8, line: usize, reason: impl fmt::Display) -> Self {
+        Error::Invalid { day, line, reason: reason.to_string() }
+    }
+
+    pub fn no_answer(day: u8, reason: impl Into<String>) -> Self {
+        Error::NoAnswer { day, reason: reason.into() }
+    }
+}
 
-impl Coordinate {
-    pub fn manhattan(&self, other: Coordinate) -> usize {
-        return ((self.0 - other.0).abs() + (self.1 - other.1).abs()) as usize;
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse { day, line, text } => write!(f, "day {}, line {}: could not parse {:?}", day, line, text),
+            Error::Invalid { day, line, reason } => write!(f, "day {}, line {}: {}", day, line, reason),
+            Error::NoAnswer { day, reason } => write!(f, "day {}: {}", day, reason),
+        }
     }
 }
 
-pub fn loadInput(day: &str) -> String {
-    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    d.push("resources");
-    d.push(format!("day{}.txt", day));
-    return fs::read_to_string(d).unwrap();
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parseErrorTest() {
+        let numbers: Result<Vec<u32>, Error> = parse_lines(1, "1\n\nthree\n4", Error::parse);
+        assert_eq!(Err(Error::parse(1, 3, "three")), numbers);
+        assert_eq!("day 1, line 3: could not parse \"three\"", numbers.unwrap_err().to_string());
+    }
 }
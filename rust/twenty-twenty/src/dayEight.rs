@@ -1,116 +1,114 @@
 #![allow(non_snake_case)]
 
 use crate::core::*;
-use std::collections::HashSet;
+use aoc_vm::{Flow, Halt, Machine};
+use std::fmt;
+use std::str::FromStr;
 
 pub struct DayEight {
     program: Vec<Instruction>
 }
 
 #[derive(Debug, Clone, PartialEq, Hash, Eq)]
-struct Instruction {
-    opCode: String,
-    arg: i32,
-    id: usize
+enum Instruction {
+    Acc(i32),
+    Jmp(i32),
+    Nop(i32)
 }
 
 impl DayEight {
-    fn init(input: &str) -> DayEight {
-        return DayEight {
-            program: parseInput(input)
-        };
-    }
-
-    pub fn new() -> DayEight {
-        return DayEight::init(&loadInput("Eight"));
+    pub fn init(input: &str) -> Result<DayEight, Error> {
+        return Ok(DayEight {
+            program: parseInput(input)?
+        });
     }
 
-    fn replace(&self, instruction: Instruction) -> Vec<Instruction> {
-        let mut copy = self.program.clone();
-        copy.remove(instruction.id);
-        copy.insert(instruction.id, instruction);
-        return copy;
+    /// Runs the boot code to where it ends or loops, with the accumulator at that point.
+    fn execute(program: Vec<Instruction>) -> (Halt, i32) {
+        let mut console = Machine::new(program, 0);
+        let halt = console.run();
+        return (halt, *console.registers());
     }
 }
 
+aoc_core::register!(try_solver(8, DayEight::init));
+aoc_vm::register!(crate::YEAR, 8, |input: &str| parseInput(input).map(|program| Machine::new(program, 0)));
+
 impl AdventOfCodeSolver for DayEight {
     fn day(&self) -> &str {
         return "Eight";
     }
 
     fn partOne(&self) -> u64 {
-        return match execute(&self.program) {
-            Ok(res) => res as u64,
-            Err(res) => res as u64
-        };
+        return DayEight::execute(self.program.clone()).1 as u64;
     }
 
     fn partTwo(&self) -> u64 {
-        return self.program.iter()
-            .enumerate()
-            .filter(|(_i, inst)| inst.opCode == "nop" || inst.opCode == "jmp")
-            .map(|(i, inst)| if inst.opCode == "nop" {
-                Instruction {
-                    id: i,
-                    opCode: "jmp".to_string(),
-                    arg: inst.arg
-                }
-                } else {
-                    Instruction {
-                        id: i,
-                        opCode: "nop".to_string(),
-                        arg: inst.arg
-                    }
-                }
-            )
-            .map(|instr| execute(&self.replace(instr)))
-            .find_map(|res| res.ok())
+        return aoc_vm::patches(&self.program, Instruction::flipped)
+            .map(DayEight::execute)
+            .find_map(|(halt, acc)| (halt == Halt::Finished).then(|| acc))
             .unwrap() as u64;
     }
 }
 
+impl FromStr for Instruction {
+    type Err = BadLine;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (opCode, arg) = line.trim().split_once(" ").ok_or(BadLine)?;
+        let arg = arg.replace("+", "").parse()?;
+        return match opCode {
+            "acc" => Ok(Instruction::Acc(arg)),
+            "jmp" => Ok(Instruction::Jmp(arg)),
+            "nop" => Ok(Instruction::Nop(arg)),
+            _ => Err(BadLine)
+        };
+    }
+}
+
 impl Instruction {
-    fn from(line: &str, id: usize) -> Instruction {
-        let mut tmp = line.trim().splitn(2, " ");
-        return Instruction {
-            opCode: tmp.next().unwrap().to_string(),
-            arg: tmp.next().unwrap().replace("+", "").parse().unwrap(),
-            id: id
-        }
+    /// The `jmp` as a `nop` or the other way around, the corruption part two repairs.
+    fn flipped(&self) -> Option<Instruction> {
+        return match self {
+            Instruction::Jmp(arg) => Some(Instruction::Nop(*arg)),
+            Instruction::Nop(arg) => Some(Instruction::Jmp(*arg)),
+            Instruction::Acc(_) => None
+        };
     }
 }
 
-fn execute(program: &Vec<Instruction>) -> Result<i32, i32> {
-    let mut executed: HashSet<&Instruction> = HashSet::new();
-    let mut index: usize = 0;
-    let mut instruction: &Instruction = program.get(index).unwrap();
-    let mut acc: i32 = 0;
-    while !executed.contains(&instruction) {
-        if instruction.opCode == "acc" {
-            acc += instruction.arg;
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            Instruction::Acc(arg) => write!(f, "acc {:+}", arg),
+            Instruction::Jmp(arg) => write!(f, "jmp {:+}", arg),
+            Instruction::Nop(arg) => write!(f, "nop {:+}", arg)
         };
-        index = match &instruction.opCode[..] {
-            "nop" => index + 1,
-            "acc" => index + 1,
-            "jmp" => (index as i32 + instruction.arg) as usize,
-            _ => index 
+    }
+}
+
+/// The handheld's boot code, whose only register is the accumulator.
+impl aoc_vm::Instruction for Instruction {
+    type Registers = i32;
+
+    fn cycles(&self) -> usize {
+        return 1;
+    }
+
+    fn execute(&self, acc: &mut i32) -> Flow {
+        return match self {
+            Instruction::Acc(arg) => {
+                *acc += arg;
+                Flow::Next
+            },
+            Instruction::Jmp(arg) => Flow::Jump(*arg as isize),
+            Instruction::Nop(_) => Flow::Next
         };
-        executed.insert(instruction);
-        if index >= program.len() {
-            //println!("Exit program successfully");
-            return Ok(acc);
-        }
-        instruction = match program.get(index) { Some(inst) => inst, None => instruction };
     }
-    return Err(acc);
-} 
-
-fn parseInput(input: &str) -> Vec<Instruction> {
-    return input.split("\n")
-        .enumerate()
-        .filter(|(_i, line)| !line.trim().is_empty())
-        .map(|(i, line)| Instruction::from(line, i))
-        .collect();
+}
+
+fn parseInput(input: &str) -> Result<Vec<Instruction>, Error> {
+    return parse_lines(8, input, Error::parse);
 }
 
 #[cfg(test)]
@@ -128,7 +126,7 @@ mod tests {
         acc +1
         jmp -4
         acc +6";
-        let result = DayEight::init(INPUT).partOne();
+        let result = DayEight::init(INPUT).unwrap().partOne();
         assert_eq!(result, 5);
     }
 
@@ -143,7 +141,16 @@ mod tests {
         acc +1
         jmp -4
         acc +6";
-        let result = DayEight::init(INPUT).partTwo();
+        let result = DayEight::init(INPUT).unwrap().partTwo();
         assert_eq!(result, 8);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn badInstructionTest() {
+        const INPUT: &str = "nop +0
+        acc +1
+        hop +4";
+        assert_eq!(Some(Error::parse(8, 3, "hop +4")), DayEight::init(INPUT).err());
+        assert_eq!(Some(Error::parse(8, 1, "acc")), DayEight::init("acc").err());
+    }
+}
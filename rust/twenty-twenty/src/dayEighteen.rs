@@ -1,166 +1,83 @@
 #![allow(non_snake_case)]
 
 use crate::core::*;
+use aoc_utils::expression::{Associativity, Expr, Operators};
+use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::str::FromStr;
 
 pub struct Day {
-    input: Vec<String>
+    homework: Vec<Homework>
+}
+
+/// A line of the homework, parsed with the rules of each part.
+struct Homework {
+    line: usize,
+    parts: [Expr; 2]
 }
 
 impl FromStr for Day {
-    type Err = std::string::ParseError;
+    type Err = Error;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        return Ok(Day {
-            input: input.split("\n")
-                        .map(|line| line.trim())
-                        .filter(|line| !line.is_empty())
-                        .map(|line| line.replace(" ", "").to_string())
-                        .collect()
-        });
+        let rules = [leftToRight(), additionFirst()];
+        let mut homework = Vec::new();
+        for (i, line) in input.split("\n").enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let parse = |operators| Expr::parse(line, operators).map_err(|e| Error::invalid(18, i + 1, e));
+            homework.push(Homework {
+                line: i + 1,
+                parts: [parse(&rules[0])?, parse(&rules[1])?]
+            });
+        }
+        return Ok(Day { homework });
     }
 }
 
-impl AdventOfCodeSolver for Day {
-    fn day(&self) -> &str {
-        return "Eighteen";
-    }
-
-    fn partOne(&self) -> u64 {
-        let result: u64 = self.input.iter()
-            .map(|line| tokenize(line))
-            .map(|tokens| parse(&tokens[..], &equalPrecedent))
-            .sum();
-        return result;
-    }
-
-    fn partTwo(&self) -> u64 {
-        let result: u64 = self.input.iter()
-            .map(|line| tokenize(line))
-            .map(|tokens| parse(&tokens[..], &addPrecedent))
-            .sum();
-        return result;
-    }
+/// No precedence, everything from left to right.
+fn leftToRight() -> Operators {
+    return Operators::new()
+        .binary('+', 1, Associativity::Left)
+        .binary('*', 1, Associativity::Left);
 }
 
-#[derive(Debug, Clone, PartialEq, Copy)]
-enum Token {
-    INT(u64),
-    OP(char),
-    START_PAR,
-    END_PAR
+/// Addition before multiplication.
+fn additionFirst() -> Operators {
+    return Operators::new()
+        .binary('+', 2, Associativity::Left)
+        .binary('*', 1, Associativity::Left);
 }
 
-fn parse(tokens: &[Token], comparator: &dyn Fn(&Token, &Token) -> bool) -> u64 {
-    let mut stack: Vec<Token> = Vec::new();
-    let mut res: Vec<Token> = Vec::new();
-    for t in tokens {
-        match t {
-            Token::INT(_) => res.push(*t),
-            Token::OP(_) => {
-                while match stack.last() {
-                    None => false,
-                    Some(t2) if comparator(t, t2) => true,
-                    Some(_) => false
-                } {
-                    res.push(stack.pop().unwrap())
-                }
-                stack.push(*t);
-            },
-            Token::START_PAR => stack.push(*t),
-            Token::END_PAR => {
-                while match stack.last() {
-                    None => false,
-                    Some(Token::START_PAR) => false,
-                    _ => true
-                } {
-                    res.push(stack.pop().unwrap())
-                }
-                stack.pop();
-            },
-            _ => println!("unhandled")
-        }
-    }
-    while match stack.last() {
-        None => false,
-        Some(Token::START_PAR) => false,
-        _ => true
-    } {
-        res.push(stack.pop().unwrap())
-    }
-    return eval(&res).unwrap();
-}
+aoc_core::register!(try_solver(18, |input| input.parse::<Day>()));
 
-impl Token {
-    fn precedent(&self) -> u8 {
-        return match self {
-            Token::START_PAR => 0,
-            Token::OP(_) => 1,
-            _ => 0
-        };
+impl AdventOfCodeSolver for Day {
+    fn day(&self) -> &str {
+        return "Eighteen";
     }
 
-    fn precedentPartTwo(&self) -> u8 {
-        return match self {
-            Token::START_PAR => 0,
-            Token::OP('+') => 2,
-            Token::OP('*') => 1,
-            _ => 0
-        };
+    fn tryPartOne(&self) -> Result<u64, Error> {
+        return self.sum(0);
     }
-}
-
-fn equalPrecedent(t: &Token, t2: &Token) -> bool {
-    return t.precedent() <= t2.precedent();
-}
-
-fn addPrecedent(t: &Token, t2: &Token) -> bool {
-    return t.precedentPartTwo() <= t2.precedentPartTwo();
-}
-
-fn eval(postFixExpr: &Vec<Token>) -> Option<u64> {
-    let mut expression = postFixExpr.into_iter().rev().collect::<Vec<&Token>>();
-    let mut token = expression.pop();
-    let mut evalStack: Vec<u64> = Vec::new();
-    while let Some(t) = token {
-        match t {
-            Token::INT(x) => evalStack.push(*x),
-            Token::OP('+') => {
-                let lhs = evalStack.pop().unwrap();
-                let rhs = evalStack.pop().unwrap();
-                evalStack.push(lhs + rhs);
-            },
-            Token::OP('*') => {
-                let lhs = evalStack.pop().unwrap();
-                let rhs = evalStack.pop().unwrap();
-                evalStack.push(lhs * rhs);
-            },
-            Token::START_PAR => {
 
-            }
-            _ => println!("not implemented")
-        }
-        token = expression.pop();
+    fn tryPartTwo(&self) -> Result<u64, Error> {
+        return self.sum(1);
     }
-    return evalStack.pop();
 }
 
-fn tokenize(inputStr: &str) -> Vec<Token> {
-    let mut input = inputStr.chars();
-    let mut res =  Vec::new();
-    let mut current = input.next();
-    while let Some(x) = current {
-        match current {
-            Some(c) if char::is_numeric(c) => res.push(Token::INT(c.to_digit(10).unwrap() as u64)),
-            Some('+') => res.push(Token::OP('+')),
-            Some('*') => res.push(Token::OP('*')),
-            Some('(') => res.push(Token::START_PAR),
-            Some(')') => res.push(Token::END_PAR),
-            _ => panic!("Unkown character")
-        };
-        current = input.next();
+impl Day {
+    fn sum(&self, part: usize) -> Result<u64, Error> {
+        return self.homework.iter()
+            .map(|homework| {
+                let value = homework.parts[part].eval(&HashMap::new())
+                    .map_err(|e| Error::invalid(18, homework.line, e))?;
+                return u64::try_from(value)
+                    .map_err(|_| Error::invalid(18, homework.line, format!("{} is below 0", value)));
+            })
+            .sum();
     }
-    return res;
 }
 
 #[cfg(test)]
@@ -170,57 +87,71 @@ mod tests {
     #[test]
     fn eighteenPartOneSimpleExampleTest() {
         const INPUT: &str = "1 + 2 * 3 + 4 * 5 + 6";
-        let result = INPUT.parse::<Day>().unwrap().partOne();
-        assert_eq!(result, 71);
+        let result = INPUT.parse::<Day>().unwrap().tryPartOne();
+        assert_eq!(result, Ok(71));
     }
 
     #[test]
     fn eighteenPartOneParTestExampleTest() {
         const INPUT: &str = "1 + (2 * 3) + (4 * (5 + 6))";
-        let result = INPUT.parse::<Day>().unwrap().partOne();
-        assert_eq!(result, 51);
+        let result = INPUT.parse::<Day>().unwrap().tryPartOne();
+        assert_eq!(result, Ok(51));
     }
 
     #[test]
     fn eighteenPartOneExampleTest2() {
         const INPUT: &str = "5 + (8 * 3 + 9 + 3 * 4 * 3)";
-        let result = INPUT.parse::<Day>().unwrap().partOne();
-        assert_eq!(result, 437);
+        let result = INPUT.parse::<Day>().unwrap().tryPartOne();
+        assert_eq!(result, Ok(437));
     }
 
     #[test]
     fn eighteenPartOneExampleTest3() {
         const INPUT: &str = "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2";
-        let result = INPUT.parse::<Day>().unwrap().partOne();
-        assert_eq!(result, 13632);
+        let result = INPUT.parse::<Day>().unwrap().tryPartOne();
+        assert_eq!(result, Ok(13632));
     }
 
     #[test]
     fn eighteenPartTwoExampleTest() {
         const INPUT: &str = "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2";
-        let result = INPUT.parse::<Day>().unwrap().partTwo();
-        assert_eq!(result, 23340);
+        let result = INPUT.parse::<Day>().unwrap().tryPartTwo();
+        assert_eq!(result, Ok(23340));
     }
 
     #[test]
     fn eighteenPartTwoSimpleExampleTest() {
         const INPUT: &str = "1 + 2 * 3 + 4 * 5 + 6";
-        let result = INPUT.parse::<Day>().unwrap().partTwo();
-        assert_eq!(result, 231);
+        let result = INPUT.parse::<Day>().unwrap().tryPartTwo();
+        assert_eq!(result, Ok(231));
     }
 
     #[test]
     fn eighteenPartTwoParTestExampleTest() {
         const INPUT: &str = "1 + (2 * 3) + (4 * (5 + 6))";
-        let result = INPUT.parse::<Day>().unwrap().partTwo();
-        assert_eq!(result, 51);
+        let result = INPUT.parse::<Day>().unwrap().tryPartTwo();
+        assert_eq!(result, Ok(51));
     }
 
     #[test]
     fn eighteenPartTwoExampleTest2() {
         const INPUT: &str = "5 + (8 * 3 + 9 + 3 * 4 * 3)";
-        let result = INPUT.parse::<Day>().unwrap().partTwo();
-        assert_eq!(result, 1445);
+        let result = INPUT.parse::<Day>().unwrap().tryPartTwo();
+        assert_eq!(result, Ok(1445));
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn eighteenBadLineTest() {
+        const INPUT: &str = "1 + 2
+        3 * * 4";
+        let result = INPUT.parse::<Day>();
+        assert_eq!(result.err().map(|e| e.to_string()), Some("day 18, line 2: column 5: unexpected \"*\"".to_string()));
+    }
+
+    #[test]
+    fn eighteenUnknownVariableTest() {
+        const INPUT: &str = "1 + x";
+        let result = INPUT.parse::<Day>().unwrap().tryPartOne();
+        assert_eq!(result, Err(Error::invalid(18, 1, "unknown variable x")));
+    }
+}
@@ -7,6 +7,8 @@ pub struct Day {
     input: String
 }
 
+aoc_core::register!(solver(15, Day::init));
+
 impl AdventOfCodeSolver for Day {
     fn day(&self) -> &str {
         return "Fifteen";
@@ -82,15 +84,11 @@ fn diff(order: Option<&Vec<usize>>) -> usize {
 }
 
  impl Day {
-    fn init(input: &str) -> Day {
+    pub fn init(input: &str) -> Day {
         return Day {
-            input: input.to_string()
+            input: input.trim().to_string()
         };
     }
-
-    pub fn new() -> Day {
-        return Day::init("0,5,4,1,10,14,7");
-    }
 }
 
 #[cfg(test)]
@@ -44,6 +44,8 @@ impl DayFive {
     }
 }
 
+aoc_core::register!(solver(5, DayFive::new));
+
 impl AdventOfCodeSolver for DayFive {
     fn day(&self) -> &str {
         return "Five";
@@ -14,12 +14,6 @@ pub struct Day {
     input: Vec<String>
 }
 
-impl Day {
-    pub fn new() -> Day {
-        return loadInput("Four").parse().unwrap();
-    }
-}
-
 impl FromStr for Day {
     type Err = std::string::ParseError;
 
@@ -45,6 +39,8 @@ impl FromStr for Day {
     }
 }
 
+aoc_core::register!(solver(4, |input| input.parse::<Day>().unwrap()));
+
 impl AdventOfCodeSolver for Day {
     fn day(&self) -> &str {
         return "Four";
@@ -105,7 +101,7 @@ impl FromStr for Passport {
             hairColor: map.get("hcl").ok_or_else(|| "Required field 'hcl' is missing")?.to_string(),
             eyeColor: map.get("ecl").ok_or_else(|| "Required field 'ecl' is missing")?.to_string(),
             passportID: map.get("pid").ok_or_else(|| "Required field 'pid' is missing")?.to_string(),
-            countryID: map.get("cid").and_then(|s| Some(s.parse().unwrap()))
+            countryID: map.get("cid").map(|s| s.parse()).transpose()?
         });
     }
 }
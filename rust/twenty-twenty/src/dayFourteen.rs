@@ -3,94 +3,87 @@
 use crate::core::*;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::str::FromStr;
 
 pub struct PartOne {
-   input: String
+   program: Vec<Line>
 }
 
 impl PartOne {
-    fn init(input: &str) -> PartOne {
-        return PartOne {
-            input: input.to_string()
-        }
+    pub fn init(input: &str) -> Result<PartOne, Error> {
+        return Ok(PartOne {
+            program: parse_lines(14, input, Error::parse)?
+        });
     }
+}
 
-    pub fn new() -> PartOne {
-        return PartOne::init(&loadInput("Fourteen"));
-    }
+/// A bitmask, as the ones it sets, the zeros it clears and its floating `X` bits.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Mask {
+    onesMask: u64,
+    zerosMask: u64,
+    wildcardMask: String
 }
 
-fn parseMask(mask: &str, placeholder: &str) -> u64 {
-    let binary = mask.split("=").nth(1).unwrap().trim();
-    return u64::from_str_radix(&binary.replace("X", placeholder), 2).unwrap()
+#[derive(Debug, Clone, PartialEq)]
+enum Line {
+    Mask(Mask),
+    Mem(u64, u64)
 }
 
-fn parseMemoryInstruction(line: &str) -> (u64, u64) {
-    let l = line.replace("mem", "").replace("[", "").replace("]", "");
-    let mut nrs = l.split("=");
-    let addr = nrs.next().unwrap();
-    let value = nrs.next().unwrap();
-    return (
-            addr.trim().to_string().parse().unwrap(),
-            value.trim().to_string().parse().unwrap()
-        );
+impl FromStr for Line {
+    type Err = BadLine;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (target, value) = line.split_once("=").ok_or(BadLine)?;
+        let (target, value) = (target.trim(), value.trim());
+        if target == "mask" {
+            if value.chars().any(|c| !"01X".contains(c)) {
+                return Err(BadLine);
+            }
+            return Ok(Line::Mask(Mask {
+                onesMask: u64::from_str_radix(&value.replace("X", "0"), 2)?,
+                zerosMask: u64::from_str_radix(&value.replace("X", "1"), 2)?,
+                wildcardMask: value.to_string()
+            }));
+        }
+        let addr = target.strip_prefix("mem[").and_then(|t| t.strip_suffix("]")).ok_or(BadLine)?;
+        return Ok(Line::Mem(addr.parse()?, value.parse()?));
+    }
 }
 
 fn applyMask(value: &u64, zerosMask: &u64, onesMask: &u64) -> u64 {
     return zerosMask & (onesMask | value);
 }
 
-struct Version2Decoder {
-    onesMask: u64,
-    wildcardMask: String
-}
+aoc_core::register!(try_solver(14, PartOne::init));
 
-impl Version2Decoder {
-    fn fromString(input: &str) -> Version2Decoder {
-        let mask = if input.len() > 0 { parseMask(input, "0") } else { 0 };
-        let wildcard = match input.split("=").nth(1) {
-            Some(m) => m.trim().to_string(),
-            None => "".to_string()
-        };
-        return Version2Decoder {
-            wildcardMask: wildcard,
-            onesMask: mask,
-        }
-    }
-}
- 
 impl AdventOfCodeSolver for PartOne {
     fn day(&self) -> &str {
         return "Fourteen";
     }
 
     fn partOne(&self) -> u64 {
-        let mut onesMask: u64 = 0;
-        let mut zerosMask: u64 = 0;
+        let mut mask = Mask::default();
         let mut memoryArray: HashMap<u64, u64> = HashMap::new();
-        for line in self.input.split("\n") {
-            if line.starts_with("mask") {
-                onesMask = parseMask(line, "0");
-                zerosMask = parseMask(line, "1");
-            } else if line.trim().starts_with("mem") {
-                let instruction = parseMemoryInstruction(line);
-                memoryArray.insert(instruction.0, applyMask(&instruction.1, &zerosMask, &onesMask));
+        for line in self.program.iter() {
+            match line {
+                Line::Mask(m) => mask = m.clone(),
+                Line::Mem(addr, value) => { memoryArray.insert(*addr, applyMask(value, &mask.zerosMask, &mask.onesMask)); }
             }
         }
         return memoryArray.iter().map(|(_k, v)| v).sum();
     }
 
     fn partTwo(&self) -> u64 {
-        let mut decoder: Version2Decoder = Version2Decoder::fromString("");
+        let mut decoder = Mask::default();
         let mut memoryArray: HashMap<u64, u64> = HashMap::new();
         let base: u64 = 2;
-        for line in self.input.split("\n") {
-            //println!("line {}", line);
-            if line.trim().starts_with("mask") {
-                decoder = Version2Decoder::fromString(line);
-            } else if line.trim().starts_with("mem") {
-                let instruction = parseMemoryInstruction(line);
-                let mappedValue = decoder.onesMask | instruction.0;
+        for line in self.program.iter() {
+            if let Line::Mask(m) = line {
+                decoder = m.clone();
+            } else if let Line::Mem(addr, value) = line {
+                let mappedValue = decoder.onesMask | addr;
                 let mut possibleValues: HashSet<u64> = HashSet::new();
                 possibleValues.insert(mappedValue);
                 decoder.wildcardMask.chars().rev()
@@ -104,7 +97,7 @@ impl AdventOfCodeSolver for PartOne {
                         }
                     });
                 for addr in possibleValues.into_iter() {
-                    memoryArray.insert(addr, instruction.1);
+                    memoryArray.insert(addr, *value);
                 }
             }
         }
@@ -122,7 +115,7 @@ mod tests {
         mem[8] = 11
         mem[7] = 101
         mem[8] = 0";
-        let result = PartOne::init(INPUT).partOne();
+        let result = PartOne::init(INPUT).unwrap().partOne();
         assert_eq!(result, 165);
     }
 
@@ -137,8 +130,8 @@ mod tests {
         mem[13582] = 24353
         mem[1496] = 392102652
         mem[57760] = 2161095";
-        let result = PartOne::init(INPUT).partOne();
-        assert_eq!(result, 377131941922);
+        let result = PartOne::init(INPUT).unwrap().partOne();
+        assert_eq!(result, 403365011618);
     }
 
     #[test]
@@ -147,7 +140,15 @@ mod tests {
         mem[42] = 100
         mask = 00000000000000000000000000000000X0XX
         mem[26] = 1";
-        let result = PartOne::init(INPUT).partTwo();
+        let result = PartOne::init(INPUT).unwrap().partTwo();
         assert_eq!(result, 208);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn badLineTest() {
+        const INPUT: &str = "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
+        mem[8 = 11";
+        assert_eq!(Some(Error::parse(14, 2, "mem[8 = 11")), PartOne::init(INPUT).err());
+        assert_eq!(Some(Error::parse(14, 1, "mask = 1Y0")), PartOne::init("mask = 1Y0").err());
+    }
+}
@@ -11,22 +11,15 @@ pub struct DayNine {
 }
 
 impl DayNine {
-    pub fn test(input: &str, preamble: usize) -> DayNine {
-        return DayNine {
+    pub fn test(input: &str, preamble: usize) -> Result<DayNine, Error> {
+        return Ok(DayNine {
             preamble: preamble,
-            seq: input.split("\n")
-                .map(|line| line.trim().parse::<u64>().unwrap())
-                .collect()
-        }
+            seq: parse_lines(9, input, Error::parse)?
+        });
     }
-    pub fn new() -> DayNine {
-        return DayNine {
-            preamble: 25,
-            seq: loadInput("Nine").split("\n")
-                .filter(|line| !line.trim().is_empty())
-                .map(|line| match line.trim().parse::<u64>() { Ok(i) => i, Err(_) => handleError(line) })
-                .collect()
-            };
+
+    pub fn init(input: &str) -> Result<DayNine, Error> {
+        return DayNine::test(input, 25);
     }
 
     fn isInValid(&self, number: &u64, index: &usize) -> bool {
@@ -47,6 +40,8 @@ impl DayNine {
     }
 
 }
+aoc_core::register!(try_solver(9, DayNine::init));
+
 impl AdventOfCodeSolver for DayNine {
     fn day(&self) -> &str {
         return "Nine";
@@ -100,11 +95,6 @@ fn sumWhile(seq: &[u64], target: u64) -> Option<Vec<u64>> {
     return None;
 }
 
-fn handleError(err: &str) -> u64 {
-    println!("error parsing line {}", err);
-    return 0;
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -131,7 +121,7 @@ mod tests {
         277
         309
         576";
-        let result = DayNine::test(INPUT, 5).partOne();
+        let result = DayNine::test(INPUT, 5).unwrap().partOne();
         assert_eq!(result, 127);
     }
 
@@ -157,7 +147,15 @@ mod tests {
         277
         309
         576";
-        let result = DayNine::test(INPUT, 5).partTwo();
+        let result = DayNine::test(INPUT, 5).unwrap().partTwo();
         assert_eq!(result, 62);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn badNumberTest() {
+        const INPUT: &str = "35
+        20
+        fifteen";
+        assert_eq!(Some(Error::parse(9, 3, "fifteen")), DayNine::init(INPUT).err());
+    }
+}
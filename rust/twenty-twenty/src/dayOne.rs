@@ -39,6 +39,8 @@ fn sum_vector(n: &i64, numbers: &[i64]) -> Vec<i64> {
     return numbers.iter().map(|x| x + n).collect();
 }
 
+aoc_core::register!(aoc_core::Solution::functions(crate::YEAR, 1, day_one, dayOnePartTwo));
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,6 +2,7 @@
 extern crate regex;
 
 use crate::core::*;
+use aoc_utils::search;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use regex::Regex;
@@ -12,13 +13,7 @@ pub struct DaySeven {
 
 
 impl DaySeven {
-    pub fn new() -> DaySeven {
-        return DaySeven {
-            bagGraph: constructGraph(&loadInput("Seven"))
-        }
-    }
-
-    pub fn test(input: &str) -> DaySeven {
+    pub fn init(input: &str) -> DaySeven {
         return DaySeven {
             bagGraph: constructGraph(input)
         }
@@ -31,22 +26,16 @@ impl DaySeven {
             if target == node {
                 continue;
             }
-            let mut visited: HashSet<(u32, String)> = HashSet::new();
-            self.dfs(&(0, node.to_string()), &mut visited);
-            if visited.iter().any(|(_i, child)| child == target) {
+            let visited = search::dfs_all(node.as_str(), |bag| {
+                self.bagGraph.get(*bag).unwrap().iter().map(|(_qty, child)| child.as_str())
+            });
+            if visited.contains(&target) {
                 reachesTarget.insert(String::from(node));
             }
         }
         return reachesTarget;
     }
 
-    fn dfs(&self, (qty, source): &(u32, String), visited: &mut HashSet<(u32, String)>) {
-        let children = self.bagGraph.get(source).unwrap();
-        visited.insert((*qty, source.to_string()));
-        children.iter()
-            .for_each(|c| self.dfs(c, visited));
-    }
-
     fn dfsPartTwo(&self, (_qty, source): &(u32, String)) -> u32 {
         let children = self.bagGraph.get(source).unwrap();
         if children.len() < 1 {
@@ -60,6 +49,8 @@ impl DaySeven {
     }
 }
 
+aoc_core::register!(solver(7, DaySeven::init));
+
 impl AdventOfCodeSolver for DaySeven {
     fn day(&self) -> &str {
         return "Seven";
@@ -126,7 +117,7 @@ mod tests {
         vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
         faded blue bags contain no other bags.
         dotted black bags contain no other bags." ;
-        let result = DaySeven::test(INPUT).partOne();
+        let result = DaySeven::init(INPUT).partOne();
         assert_eq!(result, 4);
     }
 
@@ -141,7 +132,7 @@ mod tests {
         vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
         faded blue bags contain no other bags.
         dotted black bags contain no other bags." ;
-        let result = DaySeven::test(INPUT).partTwo();
+        let result = DaySeven::init(INPUT).partTwo();
         assert_eq!(result, 32);
     }
 
@@ -154,7 +145,7 @@ mod tests {
         dark green bags contain 2 dark blue bags.
         dark blue bags contain 2 dark violet bags.
         dark violet bags contain no other bags." ;
-        let result = DaySeven::test(INPUT).partTwo();
+        let result = DaySeven::init(INPUT).partTwo();
         assert_eq!(result, 126);
     }
 }
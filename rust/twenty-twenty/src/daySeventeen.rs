@@ -1,107 +1,23 @@
 #![allow(non_snake_case)]
 
 use crate::core::*;
+use aoc_utils::Point;
+use aoc_utils::automaton::Automaton;
 use std::str::FromStr;
 use std::collections::HashMap;
-use itertools::Itertools;
-use std::hash::Hash;
 
 pub struct Day {
-    cubeGrid: HashMap<[isize; 3], u8>,
+    cubeGrid: HashMap<Point<3>, u8>,
 }
 
-impl Day {
-    pub fn new() -> Day {
-        return loadInput("Seventeen").parse().unwrap();
-    }
-}
-
-trait GameOfLife<T> where T: Sized + Eq + Clone + Copy + Hash {
-    fn getGrid(&self) -> &HashMap<T, u8>;
-    fn getNeighbours(coordinate: &T) -> Vec<T>;
-    
-    fn nextState(&self) -> HashMap<T, u8> {
-        // loop over each point that is neighbour to an active cube + all active cubes
-        // loop over all active cubes
-        let neighbours: Vec<T> = self.getGrid().keys()
-            .flat_map(|coordinate| Self::getNeighbours(coordinate))
-            .unique()
-            .collect();
-        //println!("{:?} size {}", neighbours, neighbours.len());
-        let result: HashMap<T, u8> = neighbours.into_iter()
-        // for each such cube set new state according to rule
-            .map(|cube| (cube, self.rule(&cube)))
-            .filter(|(_coordinate, state)| *state > 0)
-            .collect();
-        //println!("--------------");
-        //println!("{:?}, grid size {}", result, result.len());
-        return result;
-    }
-
-    fn rule(&self, coordinate: &T) -> u8 {
-        let neighbours = Self::getNeighbours(coordinate);
-        let activeNeighbours: usize = neighbours.into_iter()
-            .filter(|cube| cube != coordinate)
-            .filter(|cube| self.getGrid().contains_key(cube))
-            .count();
-        if self.getGrid().contains_key(coordinate) {
-            return if activeNeighbours == 2 || activeNeighbours == 3 { 1 } else { 0 };
-        } else {
-            return if activeNeighbours == 3 { 1 } else { 0 };
-        }
-    }
-}
-
-struct PartOne {
-    cubeGrid: HashMap<[isize; 3], u8>
+/// Whether a cube is active in the next cycle: an active cube stays active with 2 or 3 active
+/// neighbours, an inactive one becomes active with exactly 3.
+fn rule(active: bool, activeNeighbours: usize) -> bool {
+    return activeNeighbours == 3 || active && activeNeighbours == 2;
 }
 
-impl GameOfLife<[isize; 3]> for PartOne {
-    fn getGrid(&self) -> &HashMap<[isize; 3], u8> {
-        return &self.cubeGrid;
-    }
-
-    fn getNeighbours(coordinate: &[isize; 3]) -> Vec<[isize; 3]> {
-        let [x,y,z] = coordinate;
-        let r = -1isize..2;
-        let mut result: Vec<[isize; 3]> = Vec::new();
-        for deltaX in r.clone() {
-            for deltaY in r.clone() {
-                for deltaZ in r.clone() {
-                    let n = [(x + deltaX), (y + deltaY), (z + deltaZ)];
-                    result.push(n);
-                }
-            }
-        }
-        return result;
-    }
-}
-
-struct PartTwo {
-    cubeGrid:  HashMap<[isize; 4], u8>
-}
-
-impl GameOfLife<[isize; 4]> for PartTwo {
-    fn getGrid(&self) -> &HashMap<[isize; 4], u8> {
-        return &self.cubeGrid;
-    }
-
-    fn getNeighbours(coordinate: &[isize; 4]) -> Vec<[isize; 4]> {
-        let [x,y,z,w] = coordinate;
-        let r = -1isize..2;
-        let mut result: Vec<[isize; 4]> = Vec::new();
-        for deltaX in r.clone() {
-            for deltaY in r.clone() {
-                for deltaZ in r.clone() {
-                    for deltaW in r.clone() {
-                        let n = [(x + deltaX), (y + deltaY), (z + deltaZ), (w + deltaW)];
-                        result.push(n);
-                    }
-                }
-            }
-        }
-        return result;
-    }
+fn getNeighbours<const N: usize>(coordinate: &Point<N>) -> Vec<Point<N>> {
+    return coordinate.neighbours().collect();
 }
 
 impl FromStr for Day {
@@ -111,14 +27,14 @@ impl FromStr for Day {
         let xSize = input.split("\n").count();
         let ySize = input.split("\n").next().unwrap().len();
         
-        let init: HashMap<[isize; 3], u8> = input.split("\n")
+        let init: HashMap<Point<3>, u8> = input.split("\n")
             .map(|line| line.trim())
             .enumerate()
             .filter(|(_i, line)| !line.is_empty())
             .flat_map(|(row, line)| line.chars()
                     .enumerate()
                     .filter(|(_col, c)| '#' == *c)
-                    .map(move |(col, _c)| ([row as isize, col  as isize, 0isize], 1u8))
+                    .map(move |(col, _c)| (Point([row as i64, col as i64, 0]), 1u8))
             )
             .collect();
         return Ok(Day {
@@ -127,6 +43,8 @@ impl FromStr for Day {
     }
 }
 
+aoc_core::register!(solver(17, |input| input.parse::<Day>().unwrap()));
+
 impl AdventOfCodeSolver for Day {
     fn day(&self) -> &str {
         return "Seventeen";
@@ -134,25 +52,14 @@ impl AdventOfCodeSolver for Day {
 
     fn partOne(&self) -> u64 {
         //println!("{:?}", self.cubeGrid);
-        let mut conwayGrid = PartOne {
-            cubeGrid: self.cubeGrid.clone()
-        };
-        for _i in 0..6 {
-            conwayGrid.cubeGrid = conwayGrid.nextState();
-        }
-        return conwayGrid.getGrid().values().map(|x| *x as u64).sum::<u64>();
+        let mut conwayGrid = Automaton::sparse(self.cubeGrid.keys().copied(), getNeighbours::<3>, rule);
+        return conwayGrid.run(6).len() as u64;
     }
 
     fn partTwo(&self) -> u64 {
-        let mut conwayCubes4d = PartTwo {
-            cubeGrid : self.cubeGrid.clone().iter()
-                .map(|([x,y,z], v)| ([*x,*y,*z,0isize], *v))
-                .collect()
-        };
-        for _i in 0..6 {
-            conwayCubes4d.cubeGrid = conwayCubes4d.nextState();
-        }
-        return conwayCubes4d.getGrid().values().map(|x| *x as u64).sum::<u64>();
+        let cubes = self.cubeGrid.keys().map(|cube| cube.resize::<4>());
+        let mut conwayCubes4d = Automaton::sparse(cubes, getNeighbours::<4>, rule);
+        return conwayCubes4d.run(6).len() as u64;
     }
 }
 
@@ -171,11 +78,11 @@ mod tests {
 
     #[test]
     fn getNeighboursTest() {
-        let result = PartOne::getNeighbours(&[1, 2, 3]);
-        assert_eq!(27, result.len());
-        let shouldContain222 = result.into_iter().any(|[x,y,z]| x == 2 && y == 2 && z == 2);
+        let result = getNeighbours(&Point([1, 2, 3]));
+        assert_eq!(26, result.len());
+        let shouldContain222 = result.into_iter().any(|Point([x,y,z])| x == 2 && y == 2 && z == 2);
         assert_eq!(true, shouldContain222);
-        let shouldContain023 = PartOne::getNeighbours(&[1, 2, 3]).into_iter().any(|[x,y,z]| x == 0 && y == 2 && z == 3);
+        let shouldContain023 = getNeighbours(&Point([1, 2, 3])).into_iter().any(|Point([x,y,z])| x == 0 && y == 2 && z == 3);
         assert_eq!(true, shouldContain023);
     }
 
@@ -9,13 +9,7 @@ pub struct DaySix {
 }
 
 impl DaySix {
-    pub fn new() -> DaySix {
-        return DaySix {
-            input: loadInput("Six")
-        }
-    }
-
-    pub fn test(input: &str) -> DaySix {
+    pub fn init(input: &str) -> DaySix {
         return DaySix {
             input: input.to_string()
         }
@@ -40,6 +34,8 @@ impl DaySix {
     }
 }
 
+aoc_core::register!(solver(6, DaySix::init));
+
 impl AdventOfCodeSolver for DaySix {
     fn partOne(&self) -> u64 {
         return self.getCustomDeclarations().iter()
@@ -103,7 +99,7 @@ a
 a
         
 b" ;
-        let result = DaySix::test(INPUT).partOne();
+        let result = DaySix::init(INPUT).partOne();
         assert_eq!(result, 11);
     }
 
@@ -124,7 +120,7 @@ b" ;
         a
 
         b" ;
-        let result = DaySix::test(INPUT).partTwo();
+        let result = DaySix::init(INPUT).partTwo();
         assert_eq!(result, 6);
     }
 }
\ No newline at end of file
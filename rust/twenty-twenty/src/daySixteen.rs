@@ -1,9 +1,10 @@
 #![allow(non_snake_case)]
 
 use crate::core::*;
+use aoc_utils::interval::IntervalSet;
+use aoc_utils::search;
 use regex::Regex;
 use std::collections::HashMap;
-use std::collections::HashSet;
 use std::str::FromStr;
 
 pub struct Day {
@@ -11,7 +12,8 @@ pub struct Day {
 }
 
 struct TicketInfo {
-    metadata: HashMap<String, Vec<(u64, u64)>>, // a list of ranges
+    metadata: HashMap<String, IntervalSet<u64>>,
+    valid: IntervalSet<u64>, // every number some field allows
     myTicket: Vec<u64>,
     nearbyTickets: Vec<Vec<u64>>
 }
@@ -23,7 +25,7 @@ impl FromStr for TicketInfo {
         let mut inputIter = input.split("\n")
             .map(|line| line.trim())
             .filter(|line| !line.is_empty());
-        let mut metadata: HashMap<String, Vec<(u64, u64)>> = HashMap::new();
+        let mut metadata: HashMap<String, IntervalSet<u64>> = HashMap::new();
         let mut ticketMetadata = true;
         while ticketMetadata {
             let line = inputIter.next();
@@ -46,8 +48,10 @@ impl FromStr for TicketInfo {
         let nearby: Vec<Vec<u64>> = inputIter
             .map(|line| parseTicket(line))
             .collect();
+        let valid = metadata.values().fold(IntervalSet::new(), |valid, ranges| valid.union(ranges));
         return Ok(TicketInfo{
             metadata: metadata,
+            valid: valid,
             myTicket: myTicket,
             nearbyTickets: nearby
          }); 
@@ -56,27 +60,20 @@ impl FromStr for TicketInfo {
 
 impl TicketInfo {
     fn isNumberValid(&self, nr: &u64) -> bool {
-        return self.metadata.values().into_iter()
-                    .flatten()
-                    .any(|(min, max)| {
-                        //println!("min {} max {}", min, max);
-                        nr <= max && nr >= min
-                    })
+        return self.valid.contains(*nr);
     }
 }
 
 impl Day {
-    fn init(input: &str) -> Day {
+    pub fn init(input: &str) -> Day {
         return Day {
             input: input.to_string()
         }
     }
-
-    pub fn new() -> Day {
-        return Day::init(&loadInput("Sixteen"));
-    }
 }
 
+aoc_core::register!(unfinished(16, Day::init));
+
 impl AdventOfCodeSolver for Day {
     fn day(&self) -> &str {
         return "Sixteen";
@@ -86,7 +83,7 @@ impl AdventOfCodeSolver for Day {
         let mut inputIter = self.input.split("\n")
             .map(|line| line.trim())
             .filter(|line| !line.is_empty());
-        let mut ticketRanges: Vec<Vec<(u64, u64)>> = Vec::new();
+        let mut ticketRanges: IntervalSet<u64> = IntervalSet::new();
         let mut ticketMetadata = true;
         while ticketMetadata {
             let line = inputIter.next();
@@ -98,7 +95,7 @@ impl AdventOfCodeSolver for Day {
             };
             if ticketMetadata {
                 let ranges = parseRanges(line.unwrap());
-                ticketRanges.push(ranges);
+                ticketRanges = ticketRanges.union(&ranges);
             }
         }
         //println!("{:?}", ticketRanges);
@@ -109,18 +106,8 @@ impl AdventOfCodeSolver for Day {
         let errorRate: u64 = inputIter
             .map(|ticket| parseTicket(ticket))
             .flatten()
-            .filter(|nr| {
-                //println!("{}", nr);
-                let res = !ticketRanges[..].into_iter()
-                        .flatten()
-                        .any(|(min, max)| {
-                            //println!("min {} max {}", min, max);
-                            nr <= max && nr >= min
-                        });
-                //println!("{}", res);
-                res
-            }
-            ).sum();
+            .filter(|nr| !ticketRanges.contains(*nr))
+            .sum();
         return errorRate;
     }
 
@@ -133,10 +120,8 @@ impl AdventOfCodeSolver for Day {
             .filter(|ticket| ticket.into_iter()
                 .all(|nr| ticketInfo.isNumberValid(nr))
             ).flatten().collect();
-        println!("{:?}", numbers);
 
-        let result = assign(0, &numbers, nrOfFields, &ticketInfo.metadata);
-        println!("{:?}", result);
+        let result = assign(&numbers, nrOfFields, &ticketInfo.metadata);
         let product: u64 = result.unwrap().iter()
             .filter(|(_idx, field)| field.starts_with("departure"))
             .map(|(idx, _f)| ticketInfo.myTicket[*idx])
@@ -168,7 +153,6 @@ impl AdventOfCodeSolver for Day {
             //println!("{:?}", matchingMetadata);
             mapPostionToMetadata.insert(pos, matchingMetadata);
         }
-        println!("{:?}", mapPostionToMetadata);
         //println!("{:?}", ticketInfo.metadata);
         let mut posMap: Vec<_> = mapPostionToMetadata.into_iter().collect();
         posMap.sort_by(|(_k1,v1),(_k2,v2)| v1.len().cmp(&v2.len()));
@@ -184,50 +168,18 @@ impl AdventOfCodeSolver for Day {
     }
 }
 
-fn assign(col: usize, columns: &Vec<&u64>, rowSize: usize, labels: &HashMap<String, Vec<(u64, u64)>>) -> Option<Vec<(usize, String)>> {
-    //println!("Recur {:?} {:?} {:?}", col, columns, labels);
-    for (label, constraints) in labels {
-        // check if label is valid for column col
-        let isLabelValid = columns[col..].into_iter().step_by(rowSize)
-            .all(|field| {
-                            let res = constraints[..].into_iter()
-                                    .any(|(min, max)| {
-                                    //println!("min {} max {}", min, max);
-                                    *field <= max && *field >= min
-                                });
-                                //println!("{} res {}", field, res);
-                                res
-                            });
-        //println!("{}, {} {}", isLabelValid, label, col);
-        if isLabelValid {
-            let mut availableLables = labels.clone();
-            availableLables.remove(label);
-            let mut candidate = vec![(col, label.to_string())];
-            if availableLables.is_empty() {
-                return Some(candidate);
-            }
-            let res = assign(col + 1, columns, rowSize, &availableLables);
-            //println!("result {:?}", res);
-            if res.is_some() {
-                candidate.extend_from_slice(&res.unwrap()[..]);
-                //println!("Exiting loop...{:?}", candidate);
-                return Some(candidate);
-            }
-        }
-    }
-    return None;
-}
-
-fn depthFirstSearch(posMap: &Vec<(usize, Vec<&String>)>) -> HashMap<usize, String> {
-    let mut taken: HashSet<String> = HashSet::new();
-    let mut finalMap: HashMap<usize, String> = HashMap::new();
-    for (k, v) in posMap {
-        let first = v.iter().cloned().find(|m| !taken.contains(*m));
-
-        finalMap.insert(*k, first.unwrap().to_string());
-        taken.insert(first.unwrap().to_string());
-    }
-    return finalMap;
+/// Gives every column a label that all of its fields fit, searching depth first through the
+/// labels given to the columns so far.
+fn assign(columns: &Vec<&u64>, rowSize: usize, labels: &HashMap<String, IntervalSet<u64>>) -> Option<Vec<(usize, String)>> {
+    let fits = |col: usize, constraints: &IntervalSet<u64>| columns.iter().skip(col).step_by(rowSize)
+        .all(|field| constraints.contains(**field));
+    let assignments = search::dfs(vec![], |assigned: &Vec<&String>| {
+        labels.iter()
+            .filter(|(label, constraints)| !assigned.contains(label) && fits(assigned.len(), constraints))
+            .map(|(label, _)| [assigned.clone(), vec![label]].concat())
+            .collect::<Vec<Vec<&String>>>()
+    }, |assigned| assigned.len() == labels.len())?;
+    return Some(assignments.last()?.iter().enumerate().map(|(col, label)| (col, label.to_string())).collect());
 }
 
 fn parseTicket(row: &str) -> Vec<u64> {
@@ -236,7 +188,7 @@ fn parseTicket(row: &str) -> Vec<u64> {
         .collect();
 }
 
-fn parseRanges(line: &str) -> Vec<(u64, u64)> {
+fn parseRanges(line: &str) -> IntervalSet<u64> {
     let rangePattern = Regex::new(r"([0-9]*-[0-9]*)").unwrap();
     //println!("line {:?}", line);
     let r = line.split(":").nth(1).unwrap();
@@ -248,8 +200,8 @@ fn parseRanges(line: &str) -> Vec<(u64, u64)> {
             .map(|r| r.parse::<u64>().unwrap())
             .collect::<Vec<u64>>()
         )
-        .map(|v| (v[0], v[1]))
-        .collect::<Vec<(u64, u64)>>();
+        .map(|v| v[0]..=v[1])
+        .collect::<IntervalSet<u64>>();
     //println!("{:?}", range);
     return range;
 }
@@ -8,14 +8,10 @@ pub struct Day {
 }
 
 impl Day {
-    pub fn new() -> Day {
-        return Day {
-            adapters: loadInput("Ten")
-                .split("\n")
-                .filter(|line| !line.trim().is_empty())
-                .map(|line| line.trim().parse().unwrap())
-                .collect()
-        };
+    pub fn init(input: &str) -> Result<Day, Error> {
+        return Ok(Day {
+            adapters: parse_lines(10, input, Error::parse)?
+        });
     }
 
     pub fn test(input: &str) -> Day {
@@ -27,6 +23,8 @@ impl Day {
     }
 }
 
+aoc_core::register!(try_solver(10, Day::init));
+
 impl AdventOfCodeSolver for Day {
     fn day(&self) -> &str {
         return "Ten";
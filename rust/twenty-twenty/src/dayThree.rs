@@ -1,43 +1,34 @@
 #![allow(non_snake_case)]
 
-use std::collections::HashMap;
+use aoc_utils::Grid;
 use crate::core::*;
 
 pub struct DayThree {
-    treeMap: Vec<HashMap<usize, u32>>,
-    length: usize
+    treeMap: Grid<u32>
 }
 
 impl DayThree {
     pub fn new(input: &str) -> DayThree {
         return DayThree {
-            treeMap: input.split("\n")
-                        .map(|line| line.trim())
-                        .map(|line| parseLine(line))
-                        .collect(),
-            length: input.split("\n").next().unwrap().chars().count()
+            treeMap: Grid::parse(input, |c| match c {
+                '#' => Some(1),
+                '.' => Some(0),
+                _ => None
+            }).unwrap()
         }
     }
 
+    /// The map repeats to the right, as far as the slope goes.
     fn countTrees(&self, right: usize, down: usize) -> u32 {
-        let mut pos: usize = 0;
-        let mut sum: u32 = 0;
-        for row in self.treeMap.iter().step_by(down) {
-            sum = sum + match row.get(&pos) {
-                Some(tree) => tree,
-                None => &0
-            };
-            if pos + right >= self.length {
-                pos = (pos + right) - self.length;
-            } else {
-                pos = pos + right;
-            }
-            //println!("pos {}", pos);
-        }
-        return sum;
+        return (0..self.treeMap.rows()).step_by(down)
+            .enumerate()
+            .map(|(step, row)| self.treeMap.get_wrapping((row, step * right)))
+            .sum();
     }
 }
 
+aoc_core::register!(solver(3, DayThree::new));
+
 impl AdventOfCodeSolver for DayThree {
     fn day(&self) -> &str {
         return "Three";
@@ -55,16 +46,6 @@ impl AdventOfCodeSolver for DayThree {
     }
 }
 
-fn parseLine(line: &str) -> HashMap<usize, u32> {
-    let mut trees = HashMap::new();
-    for (i, c) in line.chars().enumerate() {
-        if c == '#' {
-            trees.insert(i, 1);
-        }
-    }
-    return trees;
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,6 +1,7 @@
 #![allow(non_snake_case)]
 
 use crate::core::*;
+use aoc_utils::Point;
 use std::cell::Cell;
 use std::convert::TryInto;
 use std::str::FromStr;
@@ -35,29 +36,21 @@ pub struct Day {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
-struct Ship(pub Coordinate, pub Instruction);
-
-impl Day {
-
-    pub fn new() -> Day {
-        return Day {
-            instructions: parseInput(&loadInput("Twelve")),
-            ship: Cell::new(Ship(Coordinate(0, 0), Instruction::East(0))),
-        };
-    }
-}
+struct Ship(pub Point<2>, pub Instruction);
 
 impl FromStr for Day {
-    type Err = std::string::ParseError;
+    type Err = Error;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
         return Ok(Day {
-            instructions: parseInput(input),
-            ship: Cell::new(Ship(Coordinate(0, 0), Instruction::East(0)))
+            instructions: parse_lines(12, input, Error::parse)?,
+            ship: Cell::new(Ship(Point([0, 0]), Instruction::East(0)))
         });
     }
 }
 
+aoc_core::register!(try_solver(12, |input| input.parse::<Day>()));
+
 impl AdventOfCodeSolver for Day {
     fn day(&self) -> &str {
         return "Twelve";
@@ -77,8 +70,8 @@ impl AdventOfCodeSolver for Day {
         // loop over instructions, if F instruction update ship heading, then move ship.
         // if other instruction, move waypoint
         let waypointShip = Waypoint {
-            ship: Coordinate(0,0),
-            waypoint: Coordinate(10,1)
+            ship: Point([0,0]),
+            waypoint: Point([10,1])
         };
         let result = self.instructions.iter().fold(waypointShip, |mut acc, instruction| {
             //println!("{:?}, next instruction {:?}", acc, instruction);
@@ -86,7 +79,7 @@ impl AdventOfCodeSolver for Day {
             return acc;
         });
         //println!("{:?}", result);
-        return result.ship.manhattan(Coordinate(0,0)) as u64;
+        return result.ship.manhattan(&Point::ORIGIN);
     }
 }
 
@@ -101,12 +94,12 @@ impl Ship {
     }
 
     fn heading(&self, heading: Instruction) -> Ship {
-        let Coordinate(x,y) = self.0;
+        let Point([x,y]) = self.0;
         let newPosition = match heading {
-            Instruction::South(d) => Coordinate(x - (d as isize), y),
-            Instruction::North(d) => Coordinate(x + (d as isize), y),
-            Instruction::East(d) => Coordinate(x, y + (d as isize)),
-            Instruction::West(d) => Coordinate(x, y - (d as isize)),
+            Instruction::South(d) => Point([x - (d as i64), y]),
+            Instruction::North(d) => Point([x + (d as i64), y]),
+            Instruction::East(d) => Point([x, y + (d as i64)]),
+            Instruction::West(d) => Point([x, y - (d as i64)]),
             h => panic!("Unknown heading {:?}", h),
         };
         return Ship(newPosition, self.1);
@@ -130,26 +123,26 @@ impl Ship {
     }
 
     fn forward(&self, forward: usize) -> Ship {
-        let Coordinate(x,y) = self.0;
+        let Point([x,y]) = self.0;
         let newPosition = match self.1 {
-            Instruction::South(_) => Coordinate(x - (forward as isize), y),
-            Instruction::North(_) => Coordinate(x + (forward as isize), y),
-            Instruction::East(_) => Coordinate(x, y + (forward as isize)),
-            Instruction::West(_) => Coordinate(x, y - (forward as isize)),
+            Instruction::South(_) => Point([x - (forward as i64), y]),
+            Instruction::North(_) => Point([x + (forward as i64), y]),
+            Instruction::East(_) => Point([x, y + (forward as i64)]),
+            Instruction::West(_) => Point([x, y - (forward as i64)]),
             h => panic!("Unknown heading {:?}", h),
         };
         return Ship(newPosition, self.1);
     }
 
     fn manhattanDistance(&self) -> u64 {
-        return self.0.manhattan(Coordinate(0,0)) as u64;
+        return self.0.manhattan(&Point::ORIGIN);
     }
 }
 
 #[derive(Debug)]
 struct Waypoint {
-    ship: Coordinate,
-    waypoint: Coordinate
+    ship: Point<2>,
+    waypoint: Point<2>
 }
 
 impl Waypoint {
@@ -163,50 +156,48 @@ impl Waypoint {
     }
 
     fn translation(&mut self, heading: Instruction) {
-        let Coordinate(x,y) = self.waypoint;
+        let Point([x,y]) = self.waypoint;
         self.waypoint = match heading {
-            Instruction::West(d) => Coordinate(x - (d as isize), y),
-            Instruction::East(d) => Coordinate(x + (d as isize), y),
-            Instruction::North(d) => Coordinate(x, y + (d as isize)),
-            Instruction::South(d) => Coordinate(x, y - (d as isize)),
+            Instruction::West(d) => Point([x - (d as i64), y]),
+            Instruction::East(d) => Point([x + (d as i64), y]),
+            Instruction::North(d) => Point([x, y + (d as i64)]),
+            Instruction::South(d) => Point([x, y - (d as i64)]),
             h => panic!("Unknown heading {:?}", h),
         };
     }
 
     fn rotation(&mut self, rotation: isize) {
-        let Coordinate(x,y) = self.waypoint;
+        let Point([x,y]) = self.waypoint;
         self.waypoint = match rotation {
-            r if r == 270 || r == -90 => Coordinate(y, -x),
-            r if r == 90 || r == -270 => Coordinate(-y, x),
-            r if r == 180 || r == -180 => Coordinate(-x, -y),
-            r if r.abs() == 360 => Coordinate(x,y),
+            r if r == 270 || r == -90 => Point([y, -x]),
+            r if r == 90 || r == -270 => Point([-y, x]),
+            r if r == 180 || r == -180 => Point([-x, -y]),
+            r if r.abs() == 360 => Point([x,y]),
             r => panic!("Unsupported rotation {}", r)
         };
     }
 
     fn forward(&mut self, forward: usize) {
-        let Coordinate(x,y) = self.waypoint;
-        let Coordinate(x1, y1) = self.ship;
-        self.ship = Coordinate(x1 + (forward as isize * x), y1 + (forward as isize * y));
+        self.ship += self.waypoint * forward as i64;
     }
 }
 
-fn parseInput(input: &str) -> Vec<Instruction> {
-    return input.split("\n")
-    .map(|line| line.trim())
-    .filter(|line| !line.is_empty())
-    .filter_map(|line| match line.chars().nth(0) {
-        Some('S') => Some(Instruction::South(line[1..].parse::<usize>().unwrap())),
-        Some('N') => Some(Instruction::North(line[1..].parse::<usize>().unwrap())),
-        Some('E') => Some(Instruction::East(line[1..].parse::<usize>().unwrap())),
-        Some('W') => Some(Instruction::West(line[1..].parse::<usize>().unwrap())),
-        Some('F') => Some(Instruction::Forward(line[1..].parse::<usize>().unwrap())),
-        Some('L') => Some(Instruction::Left(line[1..].parse::<usize>().unwrap())),
-        Some('R') => Some(Instruction::Right(line[1..].parse::<usize>().unwrap())),
-        Some(_i) => panic!("Unexpected instruction: {}", line),
-        None => panic!("Unexpected instruction: {}", line)
-    })
-    .collect();
+impl FromStr for Instruction {
+    type Err = BadLine;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let value = line.get(1..).ok_or(BadLine)?.parse::<usize>()?;
+        return match line.chars().nth(0) {
+            Some('S') => Ok(Instruction::South(value)),
+            Some('N') => Ok(Instruction::North(value)),
+            Some('E') => Ok(Instruction::East(value)),
+            Some('W') => Ok(Instruction::West(value)),
+            Some('F') => Ok(Instruction::Forward(value)),
+            Some('L') => Ok(Instruction::Left(value)),
+            Some('R') => Ok(Instruction::Right(value)),
+            _ => Err(BadLine)
+        };
+    }
 }
 
 #[cfg(test)]
@@ -234,4 +225,13 @@ mod tests {
         let result = INPUT.parse::<Day>().unwrap().partTwo();
         assert_eq!(result, 286);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn badInstructionTest() {
+        const INPUT: &str = "F10
+            N3
+            T7";
+        assert_eq!(Error::parse(12, 3, "T7"), INPUT.parse::<Day>().unwrap_err());
+        assert_eq!(Error::parse(12, 1, "F"), "F".parse::<Day>().unwrap_err());
+    }
+}
@@ -71,6 +71,8 @@ impl Policy {
     }
 }
 
+aoc_core::register!(aoc_core::Solution::functions(crate::YEAR, 2, solve, partTwo));
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,5 +1,7 @@
 #![allow(non_snake_case)]
 
+use aoc_core::Solution;
+
 pub mod core;
 pub mod dayOne;
 pub mod dayTwo;
@@ -18,4 +20,12 @@ pub mod dayFourteen;
 pub mod dayFifteen;
 pub mod daySixteen;
 pub mod daySeventeen;
-pub mod dayEighteen;
\ No newline at end of file
+pub mod dayEighteen;
+
+pub const YEAR: u16 = 2020;
+
+aoc_core::register_year!(YEAR, core::inputs);
+
+pub fn solutions() -> Vec<Solution> {
+    aoc_core::registry::solutions(YEAR)
+}
@@ -1,36 +1,26 @@
 use std::iter::Map;
 use std::str::Split;
-use crate::core::{Day};
+use crate::core::AocDay;
 
+#[derive(AocDay)]
+#[aoc(day = 1, answer = u32)]
 pub struct DayOne {
     input: String
 }
 
-impl From<String> for DayOne {
-    fn from(input: String) -> Self {
-       DayOne { input }
-    }
-}
-
 struct Elf {
     calories: Vec<u32>
 }
 
-impl Day for DayOne {
-    type R = u32;
-
-    fn day() -> String where Self: Sized {
-        "1".to_string()
-    }
-
-    fn part_one(&self) -> Self::R {
+impl DayOne {
+    fn part_one(&self) -> u32 {
         self.elfs_iter()
             .map(|elf| elf.calories.iter().sum())
             .max()
             .unwrap_or(0u32)
     }
 
-    fn part_two(&self) -> Self::R {
+    fn part_two(&self) -> u32 {
        let mut elf_calories: Vec<u32> = self.elfs_iter()
            .map(|elf| elf.calories.iter().sum())
            .collect();
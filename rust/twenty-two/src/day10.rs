@@ -1,95 +1,95 @@
-use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
-use std::ops::Range;
-use crate::core::{Day};
+use std::str::FromStr;
+use aoc_vm::{Flow, Machine};
+use crate::core::{parse_lines, AocDay, BadLine, Error};
 use crate::day10::Instruction::{AddX, NoOp};
 
+#[derive(AocDay)]
+#[aoc(day = 10, answer = String, error = Error, parse = Self::parse)]
 pub struct DayTen {
-    input: String
+    program: Vec<Instruction>
 }
 
-impl From<String> for DayTen {
-    fn from(input: String) -> Self {
-       DayTen { input }
+impl DayTen {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(DayTen { program: parse_lines(10, input, Error::parse)? })
     }
 }
 
+aoc_vm::register!(crate::YEAR, 10, |input: &str| DayTen::parse(input).map(|day| day.cpu()));
+
+#[derive(Debug, Clone)]
 enum Instruction {
     NoOp,
     AddX(i32)
 }
 
-impl From<&str> for Instruction {
-    fn from(input: &str) -> Self {
-        match input {
-            "noop" => NoOp,
-            s if s.starts_with("addx") => AddX(s.split(" ").last()
-                .map_or(0, |x| x.parse().unwrap())),
-            s => panic!("Unknown instruction '{}'", s)
+impl FromStr for Instruction {
+    type Err = BadLine;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.split(" ").collect::<Vec<&str>>()[..] {
+            ["noop"] => Ok(NoOp),
+            ["addx", x] => Ok(AddX(x.parse()?)),
+            _ => Err(BadLine)
+        }
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NoOp => write!(f, "noop"),
+            AddX(x) => write!(f, "addx {}", x)
         }
     }
 }
 
-impl Day for DayTen {
-    type R = i32;
+/// The CPU of the handheld, whose only register is X.
+impl aoc_vm::Instruction for Instruction {
+    type Registers = i32;
 
-    fn day() -> String where Self: Sized {
-        String::from("10")
+    fn cycles(&self) -> usize {
+        match self {
+            NoOp => 1,
+            AddX(_) => 2
+        }
     }
 
-    fn part_one(&self) -> Self::R {
-        let operationHistory = self.executeInstructions();
-        let samplePoints = vec!(19, 59, 99, 139, 179, 219);
-        return samplePoints.iter().map(|cycle| operationHistory.iter()
-            .find(|(range, _x)| (*range).contains(cycle))
-            .map_or(0, |(_r, x)| {
-                (*cycle as i32 + 1) * *x
-            }))
-            .sum();
+    fn execute(&self, X: &mut i32) -> Flow {
+        if let AddX(x) = self {
+            *X += x;
+        }
+        Flow::Next
     }
+}
 
-    fn part_two(&self) -> Self::R {
-        let executionLog = self.executeInstructions();
-        let lastCycle = executionLog.keys().map(|k| k.end).max().unwrap();
+impl DayTen {
+    fn part_one(&self) -> Result<String, Error> {
+        let mut signalStrength = 0;
+        self.cpu().run_with(|state| if state.cycle % 40 == 20 && state.cycle <= 220 {
+            signalStrength += state.cycle as i32 * state.registers;
+        });
+        Ok(signalStrength.to_string())
+    }
+
+    /// The screen the CRT draws, a row of 40 pixels on each line.
+    fn part_two(&self) -> Result<String, Error> {
         let mut display = CrtDisplay::new();
-        for cycle in 0..lastCycle {
-            let col = cycle % 40;
-            let row = cycle / 40;
-            if executionLog.iter()
-                .find(|(k, _x)| k.contains(&cycle))
-                .map_or(false, |(_k, pos)| Range { start: pos-1, end: pos+2 }
-                    .contains(&(col as i32) )) {
+        self.cpu().run_with(|state| {
+            let (row, col) = ((state.cycle - 1) / 40, (state.cycle - 1) % 40);
+            if (state.registers - col as i32).abs() <= 1 {
                 display.drawAt(row, col);
             }
-        }
-        println!("{}", display);
-        return 0;
+        });
+        return Ok(display.to_string());
     }
 
 }
 
 impl DayTen {
-    fn executeInstructions(&self) -> HashMap<Range<usize>, i32> {
-        let mut cycle: usize = 0;
-        let mut X = 1;
-        let mut startCycle = cycle;
-        let mut operationHistory: HashMap<Range<usize>, i32> = HashMap::new();
-        for op in self.input.lines()
-            .map(|l| l.trim())
-            .map(|l| Instruction::from(l)) {
-            let executionTime = match op {
-                NoOp => 1,
-                AddX(x) => {
-                    operationHistory.insert(Range { start: startCycle, end: cycle + 2 }, X);
-                    startCycle = cycle + 2;
-                    X = X + x;
-                    2
-                }
-            };
-            cycle = cycle + executionTime;
-        }
-        operationHistory.insert(Range { start: startCycle, end: cycle }, X);
-        operationHistory
+    fn cpu(&self) -> Machine<Instruction> {
+        Machine::new(self.program.clone(), 1)
     }
 }
 
@@ -107,20 +107,18 @@ impl CrtDisplay {
         CrtDisplay { pixels: p }
     }
 
+    /// Lights a pixel, unless it is below the screen.
     fn drawAt(&mut self, row: usize, col: usize) {
-        self.pixels[row][col] = '#';
+        if let Some(pixels) = self.pixels.get_mut(row) {
+            pixels[col] = '#';
+        }
     }
 }
 
 impl Display for CrtDisplay {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-       for row in self.pixels.iter() {
-            for pixel in row {
-                write!(f, "{}", pixel).expect("Failed to render pixel");
-            }
-            writeln!(f, "").expect("Failed to render pixel");
-        };
-        return write!(f, "");
+        let rows: Vec<String> = self.pixels.iter().map(|row| row.iter().collect()).collect();
+        write!(f, "{}", rows.join("\n"))
     }
 }
 
@@ -276,8 +274,8 @@ mod tests {
                 noop
                 noop
                 noop";
-        let actual_res = DayTen::from(String::from(input)).part_one();
-        assert_eq!(13140, actual_res);
+        let actual_res = DayTen::try_from(String::from(input)).unwrap().part_one();
+        assert_eq!(Ok(String::from("13140")), actual_res);
     }
 
     #[test]
@@ -428,7 +426,22 @@ mod tests {
                 noop
                 noop
                 noop";
-        DayTen::from(input.to_string()).part_two();
+        let screen = "##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....";
+        assert_eq!(Ok(String::from(screen)), DayTen::try_from(input.to_string()).unwrap().part_two());
+    }
+
+    #[test]
+    fn badInstructionTest() {
+        let input = "noop
+                addx 3
+                addx
+                noop";
+        assert_eq!(Some(Error::parse(10, 3, "addx")), DayTen::try_from(input.to_string()).err());
     }
 }
 
@@ -1,15 +1,19 @@
 use std::ops::Mul;
-use crate::core::{Day};
+use std::str::FromStr;
+use aoc_utils::numtheory;
+use crate::core::{AocDay, BadLine, Error};
 use lazy_static::lazy_static;
 use regex::Regex;
 
+#[derive(AocDay)]
+#[aoc(day = 11, answer = u64, error = Error, parse = Self::parse)]
 pub struct DayEleven {
-   input: String
+   monkeys: Vec<Monkey>
 }
 
-impl From<String> for DayEleven {
-    fn from(input: String) -> Self {
-        DayEleven { input }
+impl DayEleven {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(DayEleven { monkeys: Self::parseMonkeys(input)? })
     }
 }
 
@@ -22,15 +26,9 @@ lazy_static! {
     static ref OPERATION_RE: Regex = Regex::new(r"Operation.+(old|\d+) ([\+\*-]) (old|\d+)").unwrap();
 }
 
-impl Day for DayEleven {
-    type R = u64;
-
-    fn day() -> String where Self: Sized {
-        "11".to_string()
-    }
-
-    fn part_one(&self) -> Self::R {
-        let mut monkeys: Vec<Monkey> = self.parseMonkeys();
+impl DayEleven {
+    fn part_one(&self) -> Result<u64, Error> {
+        let mut monkeys: Vec<Monkey> = self.monkeys.clone();
         let length = monkeys.len();
         //monkeys.iter().for_each(|monkey| println!("{:?}", monkey));
 
@@ -54,14 +52,16 @@ impl Day for DayEleven {
 
     }
 
-    fn part_two(&self) -> Self::R {
-        let mut monkeys: Vec<Monkey> = self.parseMonkeys();
+    fn part_two(&self) -> Result<u64, Error> {
+        let mut monkeys: Vec<Monkey> = self.monkeys.clone();
         let length = monkeys.len();
-        let gcd: u64 = monkeys.iter().map(|m| &(m.divisor)).product();
+        // worry levels only matter modulo every divisor, so modulo their least common multiple
+        let modulo = numtheory::lcm_all(monkeys.iter().map(|m| m.divisor))
+            .ok_or_else(|| Error::no_answer(11, "the divisors have no common multiple that fits a u64"))?;
 
         for _round in 0..10000 {
             for i in 0..length {
-                let inspected = monkeys[i].inspectPartTwo(&gcd);
+                let inspected = monkeys[i].inspectPartTwo(&modulo);
                 for item in inspected {
                     let receiver = monkeys[i].getReceiver(&item);
                     if i == receiver {
@@ -79,44 +79,63 @@ impl Day for DayEleven {
 }
 
 impl DayEleven {
-    fn parseMonkeys(&self) -> Vec<Monkey> {
-        MONKEY_ID_RE.split(&self.input)
-            .map(|l| l.trim())
-            .filter(|l| !l.is_empty())
-            .enumerate()
-            .map(|(_i, monkey)| Monkey::from(monkey))
-            .collect()
+    fn parseMonkeys(input: &str) -> Result<Vec<Monkey>, Error> {
+        let starts: Vec<usize> = MONKEY_ID_RE.find_iter(input).map(|m| m.start()).collect();
+        if starts.is_empty() {
+            return Err(Error::parse(11, 1, input.lines().next().unwrap_or_default()));
+        }
+        let monkeys: Vec<Monkey> = starts.iter().enumerate()
+            .map(|(i, start)| {
+                let end = starts.get(i + 1).cloned().unwrap_or(input.len());
+                let monkey = input[*start..end].trim();
+                monkey.parse().map_err(|_| Error::parse(11, input[..*start].lines().count() + 1, monkey))
+            })
+            .collect::<Result<_, _>>()?;
+        match monkeys.iter().flat_map(|m| m.friends.iter()).find(|friend| **friend >= monkeys.len()) {
+            Some(friend) => Err(Error::no_answer(11, format!("there is no monkey {} to throw to", friend))),
+            None => Ok(monkeys)
+        }
     }
 
-    fn amount_of_monkey_business(monkeys: &Vec<Monkey>) -> u64 {
+    fn amount_of_monkey_business(monkeys: &Vec<Monkey>) -> Result<u64, Error> {
         let mut monkey_business = monkeys.iter()
-            .map(|m| m.nr_of_inspections as <DayEleven as Day>::R)
-            .collect::<Vec<<DayEleven as Day>::R>>();
+            .map(|m| m.nr_of_inspections as u64)
+            .collect::<Vec<u64>>();
         monkey_business.sort();
         monkey_business.reverse();
-        monkey_business[0..2].iter().product()
+        match monkey_business[..] {
+            [first, second, ..] => Ok(first * second),
+            _ => Err(Error::no_answer(11, "there are fewer than two monkeys"))
+        }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Operation {
     op: char,
     lhs: String,
     rhs: String
 }
 
-impl From<&str> for Operation {
-    fn from(input: &str) -> Self {
-        let cap = OPERATION_RE.captures(input).unwrap();
-        Operation{
-            op: (&cap[2]).parse().unwrap(),
+impl FromStr for Operation {
+    type Err = BadLine;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let cap = OPERATION_RE.captures(input).ok_or(BadLine)?;
+        for operand in [&cap[1], &cap[3]] {
+            if operand != "old" {
+                operand.parse::<u64>()?;
+            }
+        }
+        Ok(Operation{
+            op: (&cap[2]).parse().map_err(|_| BadLine)?,
             lhs: (&cap[1]).to_string(),
             rhs: (&cap[3]).to_string()
-        }
+        })
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Monkey {
     items: Vec<u64>,
     op: Operation,
@@ -176,34 +195,40 @@ impl Monkey {
     }
 }
 
-impl From<&str> for Monkey {
-    fn from(input: &str) -> Self {
+impl FromStr for Monkey {
+    type Err = BadLine;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
 
         let startingItems: Vec<u64> = match STARTING_ITEMS.captures(input) {
              Some(caps) => (caps[1]).split(",").into_iter()
                  .map(|d| d.trim())
-                 .map(|s| s.parse().unwrap())
-                 .collect(),
+                 .map(|s| s.parse())
+                 .collect::<Result<_, _>>()?,
              None => vec![]
         };
         let friends: Vec<usize> = MONKEY_FRIENDS.captures_iter(input)
-            .map(|caps| caps[1].trim().parse().unwrap())
-            .collect();
+            .map(|caps| caps[1].trim().parse())
+            .collect::<Result<_, _>>()?;
+        if friends.len() != 2 {
+            return Err(BadLine);
+        }
         let divider = TEST_RE.captures_iter(input)
-            .map(|c| c[1].trim().parse().unwrap())
-            .collect::<Vec<u64>>();
+            .map(|c| c[1].trim().parse())
+            .collect::<Result<Vec<u64>, _>>()?;
         let divisor = match divider.first() {
+            Some(0) => return Err(BadLine),
             Some(d) => d.clone(),
             None => u64::from(1u8)
         };
-        let op = Operation::from(input);
-        Monkey {
+        let op = input.parse()?;
+        Ok(Monkey {
             items: startingItems,
             op,
             divisor,
             friends,
             nr_of_inspections: 0
-        }
+        })
     }
 }
 
@@ -240,8 +265,8 @@ mod tests {
               Test: divisible by 17
                 If true: throw to monkey 0
                 If false: throw to monkey 1";
-        let actual_res = DayEleven::from(input.to_string()).part_one();
-        assert_eq!(10605, actual_res);
+        let actual_res = DayEleven::try_from(input.to_string()).unwrap().part_one();
+        assert_eq!(Ok(10605), actual_res);
     }
 
     #[test]
@@ -273,7 +298,26 @@ mod tests {
               Test: divisible by 17
                 If true: throw to monkey 0
                 If false: throw to monkey 1";
-        let actual_res = DayEleven::from(input.to_string()).part_two();
-        assert_eq!(2713310158, actual_res);
+        let actual_res = DayEleven::try_from(input.to_string()).unwrap().part_two();
+        assert_eq!(Ok(2713310158), actual_res);
+    }
+
+    #[test]
+    fn badMonkeyTest() {
+        let input = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 1
+    If false: throw to monkey 1
+
+Monkey 1:
+  Starting items: 54, 65
+  Operation: new = old ^ 2
+  Test: divisible by 19
+    If true: throw to monkey 0
+    If false: throw to monkey 0";
+        let expected = Error::parse(11, 8, &input[input.find("Monkey 1").unwrap()..]);
+        assert_eq!(Some(expected), DayEleven::try_from(input.to_string()).err());
     }
 }
\ No newline at end of file
@@ -1,15 +1,23 @@
 use std::cmp::Ordering;
 use std::cmp::Ordering::Equal;
-use std::collections::VecDeque;
-use crate::core::{Day};
+use std::iter::Peekable;
+use std::str::{Chars, FromStr};
+use crate::core::{parse_lines, AocDay, BadLine, Error};
 
+#[derive(AocDay)]
+#[aoc(day = 13, answer = u32, error = Error, parse = Self::parse)]
 pub struct DayThirteen {
-    input: String
+    /// The packets in order, every two of them a pair.
+    packets: Vec<PacketData>
 }
 
-impl From<String> for DayThirteen {
-    fn from(input: String) -> Self {
-       DayThirteen { input }
+impl DayThirteen {
+    fn parse(input: &str) -> Result<Self, Error> {
+        let packets: Vec<PacketData> = parse_lines(13, input, Error::parse)?;
+        match packets.len() % 2 {
+            0 => Ok(DayThirteen { packets }),
+            _ => Err(Error::no_answer(13, "the last packet has no other to pair with"))
+        }
     }
 }
 
@@ -65,101 +73,62 @@ impl PacketData {
     }
 }
 
-struct Pair {
-    lhs: PacketData,
-    rhs: PacketData
-}
-
-impl Pair {
-    fn compare(&self) -> Ordering {
-        //println!("-------------");
-        //println!("Comparing Pair of {:?} and {:?}", self.lhs, self.rhs);
-        self.lhs.compare(&self.rhs)
-        //println!("Comparison result: {:?}", res);
-    }
-
-    fn toVec(&self) -> Vec<&PacketData> {
-        vec![&self.lhs, &self.rhs]
-    }
-
-    fn parse(packet_data: &mut VecDeque<char>) -> PacketData {
-        let mut element = Vec::new();
-        let mut child = None;
+impl PacketData {
+    /// Reads an integer, or a list of packet data, from the front of `chars`.
+    fn parse(chars: &mut Peekable<Chars>) -> Result<PacketData, BadLine> {
+        if chars.next_if_eq(&'[').is_none() {
+            let mut nr = String::new();
+            while let Some(digit) = chars.next_if(|c| c.is_ascii_digit()) {
+                nr.push(digit);
+            }
+            return Ok(PacketData::INT(nr.parse()?));
+        }
         let mut list = vec![];
-        while let Some(left) = packet_data.pop_front() {
-            if left.is_digit(10) {
-                element.push(left);
-            } else if '[' == left {
-                child = Some(Self::parse(packet_data));
-            } else if ',' == left {
-                let nr: String = element.iter().collect();
-                list.push(child.or(
-                    nr.parse().map_or(
-                        None,
-                        |n| Some(PacketData::INT(n))
-                    )
-                ));
-                element.clear();
-                child = None;
-            } else if ']' == left {
-                let nr: String = element.iter().collect();
-                list.push(child.or(
-                    nr.parse().map_or(
-                        None,
-                        |n| Some(PacketData::INT(n))
-                    )
-                ));
-                return PacketData::LIST(list.into_iter()
-                    .filter(|c| c.is_some())
-                    .map(|c| c.unwrap())
-                    .collect::<Vec<PacketData>>().clone()
-                );
+        if chars.next_if_eq(&']').is_some() {
+            return Ok(PacketData::LIST(list));
+        }
+        loop {
+            list.push(Self::parse(chars)?);
+            match chars.next() {
+                Some(',') => continue,
+                Some(']') => return Ok(PacketData::LIST(list)),
+                _ => return Err(BadLine)
             }
         }
-       child.expect("Not a Packet!")
-    }
-}
-
-impl From<&str> for Pair {
-    fn from(input: &str) -> Self {
-        let lines: Vec<&str> = input.lines().map(|l| l.trim())
-            .collect();
-        if lines.len() == 2 {
-            let lhs = Pair::parse(&mut lines[0].chars().collect());
-            let rhs = Pair::parse(&mut lines[1].chars().collect());
-            Pair { lhs, rhs }
-        } else { panic!("A Pair must have exactly 2 sides, {}", input) }
     }
 }
 
-impl Day for DayThirteen {
-    type R = u32;
+/// Reads a packet, which is a list.
+impl FromStr for PacketData {
+    type Err = BadLine;
 
-    fn day() -> String where Self: Sized {
-        "13".to_string()
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        if !input.starts_with('[') {
+            return Err(BadLine);
+        }
+        let mut chars = input.chars().peekable();
+        let packet = Self::parse(&mut chars)?;
+        match chars.next() {
+            None => Ok(packet),
+            Some(_) => Err(BadLine)
+        }
     }
+}
 
-    fn part_one(&self) -> Self::R {
-        let pairs: Vec<Pair> = self.input.split("\n\n")
-            .map(|pair| Pair::from(pair))
-            .collect();
+impl DayThirteen {
+    fn part_one(&self) -> Result<u32, Error> {
         let mut right: Vec<usize> = Vec::new();
-        for (i, pair) in pairs.iter().enumerate() {
-            if pair.compare() == Ordering::Less {
+        for (i, pair) in self.packets.chunks(2).enumerate() {
+            if pair[0].compare(&pair[1]) == Ordering::Less {
                 right.push(i);
             }
         }
 
-        right.iter().map(|i| (i+1) as u32).sum()
+        Ok(right.iter().map(|i| (i+1) as u32).sum())
     }
 
-    fn part_two(&self) -> Self::R {
-
-        let pairs: Vec<Pair> = self.input.split("\n\n")
-            .map(|pair| Pair::from(pair))
-            .collect();
-        let mut packets: Vec<&PacketData> = pairs.iter()
-            .flat_map(|p| p.toVec()).collect();
+    fn part_two(&self) -> Result<u32, Error> {
+        let mut packets: Vec<&PacketData> = self.packets.iter().collect();
         let cp1 = PacketData::LIST(vec![PacketData::INT(2)]);
         packets.push(&cp1);
 
@@ -179,7 +148,7 @@ impl Day for DayThirteen {
                 _ => false
             });
         return match (controlPacketIndex1, controlPacketIndex2) {
-            (Some(x), Some(y)) => ((x + 1) * (y + 1)) as u32,
+            (Some(x), Some(y)) => Ok(((x + 1) * (y + 1)) as u32),
             (_, _) => panic!("Could not find controlpackets!!")
         };
     }
@@ -214,8 +183,8 @@ mod tests {
 
                 [1,[2,[3,[4,[5,6,7]]]],8,9]
                 [1,[2,[3,[4,[5,6,0]]]],8,9]";
-        let actual_res = DayThirteen::from(input.to_string()).part_one();
-        assert_eq!(13, actual_res);
+        let actual_res = DayThirteen::try_from(input.to_string()).unwrap().part_one();
+        assert_eq!(Ok(13), actual_res);
     }
 
     #[test]
@@ -243,7 +212,20 @@ mod tests {
 
                 [1,[2,[3,[4,[5,6,7]]]],8,9]
                 [1,[2,[3,[4,[5,6,0]]]],8,9]";
-        let actual_res = DayThirteen::from(input.to_string()).part_two();
-        assert_eq!(140, actual_res);
+        let actual_res = DayThirteen::try_from(input.to_string()).unwrap().part_two();
+        assert_eq!(Ok(140), actual_res);
+    }
+
+    #[test]
+    fn badPacketTest() {
+        let input = "[1,1,3,1,1]
+                [1,1,5,1,1]
+
+                [[1],[2,3,4]
+                [[1],4]";
+        assert_eq!(Some(Error::parse(13, 4, "[[1],[2,3,4]")), DayThirteen::try_from(input.to_string()).err());
+        assert_eq!(Some(Error::parse(13, 1, "1,2")), DayThirteen::try_from(String::from("1,2\n[3]")).err());
+        assert_eq!(Some(Error::parse(13, 2, "[3]]")), DayThirteen::try_from(String::from("[1]\n[3]]")).err());
+        assert!(matches!(DayThirteen::try_from(String::from("[1]\n[2]\n\n[3]")), Err(Error::NoAnswer { day: 13, .. })));
     }
 }
@@ -1,182 +1,156 @@
 use std::collections::HashSet;
-use crate::core::{Day};
-use lazy_static::lazy_static;
-use regex::Regex;
+use std::str::FromStr;
+use crate::core::{parse_lines, AocDay, BadLine, Error};
+use aoc_utils::Point;
 
+#[derive(AocDay)]
+#[aoc(day = 14, answer = u32, error = Error, parse = Self::parse)]
 pub struct DayFourteen {
-    input: String
+    cave: SandCave
 }
 
-impl From<String> for DayFourteen {
-    fn from(input: String) -> Self {
-       DayFourteen { input }
+impl DayFourteen {
+    fn parse(input: &str) -> Result<Self, Error> {
+        let paths: Vec<RockPath> = parse_lines(14, input, Error::parse)?;
+        let cave = SandCave::new(&paths).ok_or_else(|| Error::no_answer(14, "the scan shows no rock"))?;
+        Ok(DayFourteen { cave })
     }
 }
 
-lazy_static! {
-    static ref COORDINATE_RE: Regex = Regex::new(r"([0-9]+,[0-9]+)").unwrap();
+/// The corners of a path of rock, each one straight across or down from the one before.
+struct RockPath(Vec<Point<2>>);
+
+impl FromStr for RockPath {
+    type Err = BadLine;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let corners = input.split(" -> ")
+            .map(|corner| corner.parse().map_err(|_| BadLine))
+            .collect::<Result<Vec<Point<2>>, _>>()?;
+        match corners.windows(2).all(|pair| pair[0].x() == pair[1].x() || pair[0].y() == pair[1].y()) {
+            true => Ok(RockPath(corners)),
+            false => Err(BadLine)
+        }
+    }
 }
 
 struct SandCave {
-    rock: HashSet<Coordinate>,
-    left_boundary: i32,
-    right_boundary: i32,
-    bottom_boundary: i32,
+    rock: HashSet<Point<2>>,
+    left_boundary: i64,
+    right_boundary: i64,
+    bottom_boundary: i64,
 }
 
-impl From<&str> for SandCave {
-    fn from(input: &str) -> Self {
-        let rocks: HashSet<Coordinate> = input.lines()
-              .map(|l| DayFourteen::parsePoint(l))
-              .flat_map(|p| p.windows(2)
-                  .flat_map(|pair| pair[0].on_path(&pair[1]))
-                  .collect::<HashSet<Coordinate>>()
+impl SandCave {
+    /// The cave with rock along the `paths`, none when there is no rock at all.
+    fn new(paths: &[RockPath]) -> Option<Self> {
+        let rocks: HashSet<Point<2>> = paths.iter()
+              .flat_map(|p| p.0.windows(2)
+                  .flat_map(|pair| on_path(&pair[0], &pair[1]))
+                  .collect::<HashSet<Point<2>>>()
               ).collect();
 
-        let right_boundary = rocks.iter().map(|pos| pos.0).max().unwrap();
-        let left_boundary = rocks.iter().map(|pos| pos.0).min().unwrap();
-        let bottom_boundary = rocks.iter().map(|pos| pos.1).max().unwrap();
-        SandCave { rock: rocks, left_boundary, right_boundary, bottom_boundary }
+        let right_boundary = rocks.iter().map(|pos| pos.x()).max()?;
+        let left_boundary = rocks.iter().map(|pos| pos.x()).min()?;
+        let bottom_boundary = rocks.iter().map(|pos| pos.y()).max()?;
+        Some(SandCave { rock: rocks, left_boundary, right_boundary, bottom_boundary })
     }
 }
 
-impl Day for DayFourteen {
-    type R = u32;
-
-    fn day() -> String where Self: Sized {
-        "14".to_string()
-    }
-
-    fn part_one(&self) -> Self::R {
-        let cave = SandCave::from(self.input.as_str());
-        let mut occupied = cave.rock;
+impl DayFourteen {
+    fn part_one(&self) -> Result<u32, Error> {
+        let cave = &self.cave;
+        let mut occupied = cave.rock.clone();
         let mut grain_count= 0;
         let mut sand_resting = true;
         while sand_resting {
-            let mut grain_pos =Coordinate(500, 0);
+            let mut grain_pos =Point([500, 0]);
             // depth first search
             let mut grain_falling = true;
             while grain_falling {
-                if grain_pos.0 < cave.left_boundary || grain_pos.0 > cave.right_boundary || grain_pos.1 > cave.bottom_boundary {
+                if grain_pos.x() < cave.left_boundary || grain_pos.x() > cave.right_boundary || grain_pos.y() > cave.bottom_boundary {
                     grain_falling = false;
                     sand_resting = false;
                     break
                 }
                 grain_pos = match grain_pos {
-                    Coordinate(x, y) if !occupied.contains(&Coordinate(x, y + 1)) => {
-                        let p = Coordinate(x, y + 1);
+                    Point([x, y]) if !occupied.contains(&Point([x, y + 1])) => {
+                        let p = Point([x, y + 1]);
                         p
                     },
-                    Coordinate(x, y) if !occupied.contains(&Coordinate(x - 1, y + 1)) => {
-                        let p = Coordinate(x - 1, y + 1);
+                    Point([x, y]) if !occupied.contains(&Point([x - 1, y + 1])) => {
+                        let p = Point([x - 1, y + 1]);
                         p
                     },
-                    Coordinate(x, y) if !occupied.contains(&Coordinate(x + 1, y + 1)) => {
-                        let p = Coordinate(x + 1, y + 1);
+                    Point([x, y]) if !occupied.contains(&Point([x + 1, y + 1])) => {
+                        let p = Point([x + 1, y + 1]);
                         p
                     },
-                    Coordinate(x,y) => {
+                    Point([x,y]) => {
                         grain_count = grain_count + 1;
                         grain_falling = false;
-                        occupied.insert(Coordinate(x,y));
+                        occupied.insert(Point([x,y]));
                         grain_pos
                     }
                 };
             };
         }
-        grain_count
+        Ok(grain_count)
     }
 
-    fn part_two(&self) -> Self::R {
-        let cave = SandCave::from(self.input.as_str());
-        let mut occupied = cave.rock;
+    fn part_two(&self) -> Result<u32, Error> {
+        let cave = &self.cave;
+        let mut occupied = cave.rock.clone();
         let mut grain_count= 0;
         let mut sand_resting = true;
         while sand_resting {
-            let mut grain_pos = Coordinate(500, 0);
+            let mut grain_pos = Point([500, 0]);
             // depth first search
             if occupied.contains(&grain_pos) {
                 break;
             }
             let mut grain_falling = true;
             while grain_falling {
-                if grain_pos.1 == cave.bottom_boundary+1 {
+                if grain_pos.y() == cave.bottom_boundary+1 {
                     occupied.insert(grain_pos);
                     grain_count = grain_count + 1;
                     grain_falling = false;
                     break;
                 }
                 grain_pos = match grain_pos {
-                    Coordinate(x, y) if !occupied.contains(&Coordinate(x, y + 1)) => {
-                        let p = Coordinate(x, y + 1);
+                    Point([x, y]) if !occupied.contains(&Point([x, y + 1])) => {
+                        let p = Point([x, y + 1]);
                         //println!("pos {:?} straight below is available", p);
                         p
                     },
-                    Coordinate(x, y) if !occupied.contains(&Coordinate(x - 1, y + 1)) => {
-                        let p = Coordinate(x - 1, y + 1);
+                    Point([x, y]) if !occupied.contains(&Point([x - 1, y + 1])) => {
+                        let p = Point([x - 1, y + 1]);
                         //println!("pos {:?} below to the left is available", p);
                         p
                     },
-                    Coordinate(x, y) if !occupied.contains(&Coordinate(x + 1, y + 1)) => {
-                        let p = Coordinate(x + 1, y + 1);
+                    Point([x, y]) if !occupied.contains(&Point([x + 1, y + 1])) => {
+                        let p = Point([x + 1, y + 1]);
                         //println!("pos {:?} below to the right is available", p);
                         p
                     },
-                    Coordinate(x,y) => {
+                    Point([x,y]) => {
                         //println!("all options occupied, grain rests at {:?}", grain_pos);
                         grain_count = grain_count + 1;
                         grain_falling = false;
-                        occupied.insert(Coordinate(x,y));
+                        occupied.insert(Point([x,y]));
                         break;
                     }
                 };
             };
         }
-        grain_count
+        Ok(grain_count)
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialOrd, PartialEq, Eq, Hash)]
-struct Coordinate(i32,i32);
-
-impl Coordinate {
-    fn on_path(&self, end: &Coordinate) -> Vec<Coordinate> {
-        let mut res = vec![];
-
-        let horizontal = end.0 - self.0;
-        let vertical = end.1 - self.1;
-
-        if horizontal == 0 {
-            let start = if vertical > 0 {self.1} else {self.1+vertical};
-            let end = if vertical > 0 {self.1+vertical} else {self.1};
-            for y in start..=end {
-                res.push(Coordinate(self.0, y));
-            }
-        }
-        else if vertical == 0 {
-            let start = if horizontal > 0 {self.0} else {self.0+horizontal};
-            let end = if horizontal > 0 {self.0+horizontal} else {self.0};
-            for x in start..=end {
-                res.push(Coordinate(x, self.1));
-            }
-        }
-        else {
-            panic!("Path is neither horizontal nor vertical! start {:?} end {:?}", self, end);
-        }
-        res
-    }
-}
-
-impl DayFourteen {
-    fn parsePoint(l: &str) -> Vec<Coordinate> {
-        COORDINATE_RE.captures_iter(l).map(|point| {
-            let coord: Vec<i32> = point[0].split(",")
-                .map(|a| a.parse().unwrap())
-                .collect();
-            Coordinate{ 0: coord[0], 1: coord[1] }
-        }
-        ).collect()
-    }
+/// The points from `start` to `end`, both included, on a horizontal or vertical path.
+fn on_path(start: &Point<2>, end: &Point<2>) -> Vec<Point<2>> {
+    let delta = *end - *start;
+    (0..=delta.chebyshev(&Point::ORIGIN) as i64).map(|i| *start + delta.signum() * i).collect()
 }
 
 #[cfg(test)]
@@ -187,15 +161,24 @@ mod tests {
     fn partOneExampleTest() {
         let input = "498,4 -> 498,6 -> 496,6
 503,4 -> 502,4 -> 502,9 -> 494,9 ";
-        let actual_res = DayFourteen::from(input.to_string()).part_one();
-        assert_eq!(24, actual_res);
+        let actual_res = DayFourteen::try_from(input.to_string()).unwrap().part_one();
+        assert_eq!(Ok(24), actual_res);
     }
 
     #[test]
     fn partTwoExampleTest() {
         let input = "498,4 -> 498,6 -> 496,6
 503,4 -> 502,4 -> 502,9 -> 494,9 ";
-        let actual_res = DayFourteen::from(input.to_string()).part_two();
-        assert_eq!(93, actual_res);
+        let actual_res = DayFourteen::try_from(input.to_string()).unwrap().part_two();
+        assert_eq!(Ok(93), actual_res);
+    }
+
+    #[test]
+    fn badPathTest() {
+        let input = "498,4 -> 498,6 -> 496,6
+503,4 -> 502,5";
+        assert_eq!(Some(Error::parse(14, 2, "503,4 -> 502,5")), DayFourteen::try_from(input.to_string()).err());
+        assert_eq!(Some(Error::parse(14, 1, "498,4 -> 498")), DayFourteen::try_from(String::from("498,4 -> 498")).err());
+        assert!(matches!(DayFourteen::try_from(String::new()), Err(Error::NoAnswer { day: 14, .. })));
     }
 }
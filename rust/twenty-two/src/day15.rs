@@ -1,35 +1,39 @@
 use std::collections::HashSet;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use aoc_utils::interval::IntervalSet;
 use lazy_static::lazy_static;
 use regex::Regex;
-use crate::core::{Day};
+use crate::core::{parse_lines, AocDay, BadLine, Error};
 
+#[derive(AocDay)]
+#[aoc(day = 15, answer = i64, error = Error, parse = Self::parse)]
 pub struct DayFifteen {
-    input: String
+    sensor_map: SensorMap
 }
 
-impl From<String> for DayFifteen {
-    fn from(input: String) -> Self {
-       DayFifteen { input }
+impl DayFifteen {
+    fn parse(input: &str) -> Result<Self, Error> {
+        let sensor_map: SensorMap = input.parse()?;
+        if sensor_map.sensors.is_empty() {
+            return Err(Error::no_answer(15, "there are no sensors"));
+        }
+        Ok(DayFifteen { sensor_map })
     }
 }
 
-impl Day for DayFifteen {
-    type R = i64;
-
-    fn day() -> String where Self: Sized {
-        "15".to_string()
-    }
-
-    fn part_one(&self) -> Self::R {
-        let sensorMap = SensorMap::from(self.input.as_str());
+impl DayFifteen {
+    fn part_one(&self) -> Result<i64, Error> {
+        let sensorMap = &self.sensor_map;
         let min = sensorMap.sensors.iter().map(|s| s.pos.0 - s.range as i32).min().unwrap();
         let max = sensorMap.sensors.iter().map(|s| s.pos.0 + s.range as i32).max().unwrap();
-        self.count_coverage(&sensorMap, 2000000, min, max).count as i64
+        Ok(self.count_coverage(sensorMap, 2000000, min, max).count as i64)
     }
 
-    fn part_two(&self) -> Self::R {
-        let distress_pos = self.find_distress_beacon(0, 4000000);
-        DayFifteen::tuning_frequency(distress_pos) as i64
+    fn part_two(&self) -> Result<i64, Error> {
+        let distress_pos = self.find_distress_beacon(0, 4000000)
+            .ok_or_else(|| Error::no_answer(15, "there is no room for the distress beacon"))?;
+        Ok(DayFifteen::tuning_frequency(distress_pos) as i64)
     }
 
 }
@@ -42,29 +46,13 @@ struct Sensor {
 }
 
 impl Sensor {
-    fn in_range(&self, pos: (i32, i32)) -> bool {
-        let dist = ((pos.0 - self.pos.0).abs() + (pos.1 - self.pos.1).abs()) as u32;
-        //println!("pos: {:?} is in range from {:?}: {}", pos, self, dist <= self.range);
-        dist <= self.range
-    }
-
-    fn to_row_sensor(&self, row: i32) -> Option<(i32, i32)> {
-        // if row is out of range, return None
-
-        let max_row_reached = self.pos.1 + self.range as i32;
-        let min_row_reached = self.pos.1 - self.range as i32;
-        //println!("max row: {}, min row: {}, target row {}", max_row_reached, min_row_reached, row);
-        if row > max_row_reached || row <= min_row_reached {
-            //println!("sensor: {:?} does not cover row: {}", self, row);
+    /// The columns of `row` this sensor covers, if it reaches that row at all.
+    fn to_row_sensor(&self, row: i32) -> Option<RangeInclusive<i32>> {
+        let r = self.range as i32 - (row - self.pos.1).abs();
+        if r < 0 {
             return None;
         }
-        //println!("sensor: {:?} covers row: {}", self, row);
-        // else, calculate this sensors coverage of the row
-        // row sensor start x and row sensor stop x
-        let r = self.range as i32 - (row - self.pos.1).abs();
-        let start_x = self.pos.0 - r;
-        let end_x = self.pos.0 + r;
-        Some((start_x, end_x))
+        Some(self.pos.0 - r..=self.pos.0 + r)
     }
 }
 
@@ -72,17 +60,22 @@ lazy_static! {
     static ref COORDINATE_RE: Regex = Regex::new(r"(-?[0-9]+)").unwrap();
 }
 
-impl From<&str> for Sensor {
-    fn from(input: &str) -> Self {
+impl FromStr for Sensor {
+    type Err = BadLine;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
           let nrs: Vec<i32> = COORDINATE_RE.captures_iter(input)
               .filter_map(|cap| cap.get(0).map(|m| m.as_str()))
-              .map(|x| x.parse().unwrap())
-              .collect();
-        Sensor {
+              .map(|x| x.parse())
+              .collect::<Result<_, _>>()?;
+        if nrs.len() != 4 {
+            return Err(BadLine);
+        }
+        Ok(Sensor {
             pos: (nrs[0],nrs[1]),
             beacon: (nrs[2],nrs[3]),
             range: ((nrs[2] - nrs[0]).abs() + (nrs[3] - nrs[1]).abs()) as u32
-        }
+        })
     }
 }
 
@@ -91,16 +84,23 @@ struct SensorMap {
     beacons: HashSet<(i32, i32)>
 }
 
-impl From<&str> for SensorMap {
-    fn from(input: &str) -> Self {
-        let sensors: Vec<Sensor> = input.lines()
-            .map(|l| l.trim())
-            .map(|l| Sensor::from(l))
-            .collect();
+impl FromStr for SensorMap {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let sensors: Vec<Sensor> = parse_lines(15, input, Error::parse)?;
         let beacons: HashSet<(i32, i32)> = sensors.iter()
             .map(|s| s.beacon.clone())
             .collect();
-        SensorMap { sensors, beacons }
+        Ok(SensorMap { sensors, beacons })
+    }
+}
+
+impl SensorMap {
+    fn row_coverage(&self, row: i32) -> IntervalSet<i32> {
+        self.sensors.iter()
+            .filter_map(|sensor| sensor.to_row_sensor(row))
+            .collect()
     }
 }
 
@@ -111,64 +111,24 @@ struct CoverageResult {
 
 impl DayFifteen {
     fn count_coverage(&self, sensorMap: &SensorMap, row: i32, min: i32, max: i32) -> CoverageResult {
-        //println!("Sensors: {:?}", sensors);
-        //println!("Min y: {:?}, max y {:?}", min, max);
-        let mut count = 0;
-        let mut pos = None;
-        for x in min..=max {
-            let c = (x, row);
-            let covered = sensorMap.sensors.iter()
-                .find(|s| s.in_range(c));
-            match covered {
-                Some(_s) => {
-                    count = count + 1;
-                    //println!("pos: {:?} is covered by sensor: {:?}", c, _s);
-                },
-                None => {
-                    pos = Some(c.clone())
-                }
-            };
-        }
-        let coverage_count = count - (sensorMap.beacons.iter()
-            .filter(|b| (*b).1 == row).count() as u32);
-        return CoverageResult { count:coverage_count, pos };
+        let searched = IntervalSet::from(min..=max);
+        let coverage = sensorMap.row_coverage(row).intersection(&searched);
+        let beacons = sensorMap.beacons.iter()
+            .filter(|b| b.1 == row && coverage.contains(b.0))
+            .count() as u32;
+        let pos = searched.difference(&coverage).iter().last().map(|gap| (*gap.end(), row));
+        return CoverageResult { count: coverage.total_len() as u32 - beacons, pos };
     }
 
-    fn find_distress_beacon(&self, min: i32, max: i32) -> (i32, i32) {
-        // start at row min
-        let sensorMap = SensorMap::from(self.input.as_str());
-        let mut distress_beacon_row = None;
-        let mut current_x_pos = min;
-        for row in min..=max {
-            if distress_beacon_row.is_some() {
-                break;
+    fn find_distress_beacon(&self, min: i32, max: i32) -> Option<(i32, i32)> {
+        let searched = IntervalSet::from(min..=max);
+        (min..=max).find_map(|row| {
+            let coverage = self.sensor_map.row_coverage(row);
+            if coverage.contains_range(&(min..=max)) {
+                return None;
             }
-            let mut rowSensors: Vec<(i32, i32)> = sensorMap.sensors.iter()
-                .filter_map(|sensor| sensor.to_row_sensor(row))
-                .map(|s| match s {
-                    (start,end) if start < min => (min,end),
-                    (start,end) if end > max => (start,max),
-                    (start,end) => (start,end)
-                })
-                .collect();
-            rowSensors.sort_by(|a,b| a.0.cmp(&b.0));
-            // do DFS to find if there is a path from min to max
-            current_x_pos = min;
-            while !rowSensors.is_empty() && current_x_pos < max {
-                // find next sensor
-                let nextSensor = rowSensors.iter()
-                    .position(|s| current_x_pos >= s.0 && current_x_pos <= s.1);
-                if nextSensor.is_none() {
-                    //println!("Distress beacon is at row: {} and col: {}", row, current_x_pos);
-                    distress_beacon_row = Some(row);
-                    break;
-                } else {
-                    let rowSensor = rowSensors.swap_remove(nextSensor.unwrap());
-                    current_x_pos = rowSensor.1 + 1;
-                }
-            }
-        }
-        return (current_x_pos, distress_beacon_row.unwrap());
+            searched.difference(&coverage).iter().next().map(|gap| (*gap.start(), row))
+        })
     }
 
     fn tuning_frequency(pos: (i32, i32)) -> i64 {
@@ -197,10 +157,10 @@ Sensor at x=16, y=7: closest beacon is at x=15, y=3
 Sensor at x=14, y=3: closest beacon is at x=15, y=3
 Sensor at x=20, y=1: closest beacon is at x=15, y=3";
         let searchAtRow = 10;
-        let sensor_map = SensorMap::from(input);
+        let sensor_map: SensorMap = input.parse().unwrap();
         let min = sensor_map.sensors.iter().map(|s| s.pos.0 - s.range as i32).min().unwrap();
         let max = sensor_map.sensors.iter().map(|s| s.pos.0 + s.range as i32).max().unwrap();
-        let actual_res = DayFifteen::from(input.to_string())
+        let actual_res = DayFifteen::try_from(input.to_string()).unwrap()
             .count_coverage(&sensor_map, searchAtRow, min, max).count;
         assert_eq!(26, actual_res);
     }
@@ -221,9 +181,17 @@ Sensor at x=17, y=20: closest beacon is at x=21, y=22
 Sensor at x=16, y=7: closest beacon is at x=15, y=3
 Sensor at x=14, y=3: closest beacon is at x=15, y=3
 Sensor at x=20, y=1: closest beacon is at x=15, y=3";
-        let actual_pos = DayFifteen::from(input.to_string())
+        let actual_pos = DayFifteen::try_from(input.to_string()).unwrap()
             .find_distress_beacon(0, 20);
-        assert_eq!((14, 11), actual_pos);
-        assert_eq!(56000011, DayFifteen::tuning_frequency(actual_pos));
+        assert_eq!(Some((14, 11)), actual_pos);
+        assert_eq!(56000011, DayFifteen::tuning_frequency(actual_pos.unwrap()));
+    }
+
+    #[test]
+    fn badSensorTest() {
+        let input = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10";
+        assert_eq!(Some(Error::parse(15, 2, "Sensor at x=9, y=16: closest beacon is at x=10")),
+                   DayFifteen::try_from(input.to_string()).err());
     }
 }
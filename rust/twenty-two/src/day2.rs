@@ -1,28 +1,34 @@
-use crate::core::{Day};
+use std::str::FromStr;
+use crate::core::{parse_lines, AocDay, BadLine, Error};
 
+#[derive(AocDay)]
+#[aoc(day = 2, answer = u32, error = Error, parse = Self::parse)]
 pub struct DayTwo {
-    input: String
+    guide: Vec<Guide>
 }
 
-impl From<String> for DayTwo {
-    fn from(input: String) -> Self {
-        DayTwo { input }
+impl DayTwo {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(DayTwo { guide: parse_lines(2, input, Error::parse)? })
     }
 }
 
+#[derive(Clone, Copy)]
 enum Opponent {
     ROCK,
     PAPER,
     SCISSOR
 }
 
-impl From<&str> for Opponent {
-    fn from(input: &str) -> Self {
+impl FromStr for Opponent {
+    type Err = BadLine;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
         match input {
-            "A" => Opponent::ROCK,
-            "B" => Opponent::PAPER,
-            "C" => Opponent::SCISSOR,
-            _ => panic!("Unknown Rock-Paper-Scissors move {}", input)
+            "A" => Ok(Opponent::ROCK),
+            "B" => Ok(Opponent::PAPER),
+            "C" => Ok(Opponent::SCISSOR),
+            _ => Err(BadLine)
         }
     }
 }
@@ -33,29 +39,39 @@ enum YourMove {
     SCISSOR(u8)
 }
 
-impl From<&str> for YourMove {
-    fn from(input: &str) -> Self {
+impl FromStr for YourMove {
+    type Err = BadLine;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
         match input {
-            "X" => YourMove::ROCK(1),
-            "Y" => YourMove::PAPER(2),
-            "Z" => YourMove::SCISSOR(3),
-            _ => panic!("Unknown Rock-Paper-Scissors move {}", input)
+            "X" => Ok(YourMove::ROCK(1)),
+            "Y" => Ok(YourMove::PAPER(2)),
+            "Z" => Ok(YourMove::SCISSOR(3)),
+            _ => Err(BadLine)
         }
     }
 }
 
 struct Round(Opponent, YourMove);
 
-impl From<&str> for Round {
-    fn from(input: &str) -> Self {
-        let mut i = input.split(" ");
-        let o = Opponent::from(
-            i.next().expect("There should be a move for your opponent")
-        );
-        let y = YourMove::from(
-            i.next().expect("There should be a move for you")
-        );
-        return Round(o, y);
+/// A line of the strategy guide, whose second column is your move in part one and the
+/// outcome in part two.
+struct Guide {
+    round: Round,
+    outcome: DesiredOutcome
+}
+
+impl FromStr for Guide {
+    type Err = BadLine;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.split(" ").collect::<Vec<&str>>()[..] {
+            [opponent, second] => Ok(Guide {
+                round: Round(opponent.parse()?, second.parse()?),
+                outcome: second.parse()?
+            }),
+            _ => Err(BadLine)
+        }
     }
 }
 
@@ -81,58 +97,49 @@ enum DesiredOutcome {
     DRAW
 }
 
-impl From<&str> for DesiredOutcome {
-    fn from(input: &str) -> Self {
+impl FromStr for DesiredOutcome {
+    type Err = BadLine;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
         match input {
-            "X" => DesiredOutcome::LOSE,
-            "Y" => DesiredOutcome::DRAW,
-            "Z" => DesiredOutcome::WIN,
-            _ => panic!("Unknown Outcome {}", input)
+            "X" => Ok(DesiredOutcome::LOSE),
+            "Y" => Ok(DesiredOutcome::DRAW),
+            "Z" => Ok(DesiredOutcome::WIN),
+            _ => Err(BadLine)
         }
     }
 }
 impl DesiredOutcome {
     fn your_move(&self, opponent: &Opponent) -> YourMove {
-        let your_move = match (self, opponent) {
-            (DesiredOutcome::WIN, Opponent::ROCK) => "Y",
-            (DesiredOutcome::LOSE, Opponent::ROCK) => "Z",
-            (DesiredOutcome::DRAW, Opponent::ROCK) => "X",
-            (DesiredOutcome::WIN, Opponent::PAPER) => "Z",
-            (DesiredOutcome::DRAW, Opponent::PAPER) => "Y",
-            (DesiredOutcome::LOSE, Opponent::PAPER) => "X",
-            (DesiredOutcome::WIN, Opponent::SCISSOR) => "X",
-            (DesiredOutcome::LOSE, Opponent::SCISSOR) => "Y",
-            (DesiredOutcome::DRAW, Opponent::SCISSOR) => "Z",
-        };
-        return YourMove::from(your_move);
+        match (self, opponent) {
+            (DesiredOutcome::WIN, Opponent::ROCK) => YourMove::PAPER(2),
+            (DesiredOutcome::LOSE, Opponent::ROCK) => YourMove::SCISSOR(3),
+            (DesiredOutcome::DRAW, Opponent::ROCK) => YourMove::ROCK(1),
+            (DesiredOutcome::WIN, Opponent::PAPER) => YourMove::SCISSOR(3),
+            (DesiredOutcome::DRAW, Opponent::PAPER) => YourMove::PAPER(2),
+            (DesiredOutcome::LOSE, Opponent::PAPER) => YourMove::ROCK(1),
+            (DesiredOutcome::WIN, Opponent::SCISSOR) => YourMove::ROCK(1),
+            (DesiredOutcome::LOSE, Opponent::SCISSOR) => YourMove::PAPER(2),
+            (DesiredOutcome::DRAW, Opponent::SCISSOR) => YourMove::SCISSOR(3),
+        }
     }
 }
-impl Day for DayTwo {
-    type R = u32;
-
-    fn day() -> String where Self: Sized {
-        "2".to_string()
+impl DayTwo {
+    fn part_one(&self) -> Result<u32, Error> {
+        Ok(self.guide.iter()
+            .map(|guide| guide.round.score() as u32)
+            .sum())
     }
 
-    fn part_one(&self) -> Self::R {
-        self.input.lines()
-            .map(|l| l.trim())
-            .map(|l| Round::from(l))
-            .map(|round| round.score() as u32)
-            .sum()
-    }
-
-    fn part_two(&self) -> Self::R {
-        self.input.lines()
-            .map(|l| l.trim())
-            .map(|l| {
-                let s: Vec<&str> = l.split(" ").collect();
-                let o = Opponent::from(s[0]);
-                let d = DesiredOutcome::from(s[1]).your_move(&o);
+    fn part_two(&self) -> Result<u32, Error> {
+        Ok(self.guide.iter()
+            .map(|guide| {
+                let o = guide.round.0;
+                let d = guide.outcome.your_move(&o);
                 return Round(o, d);
             })
             .map(|round| round.score() as u32)
-            .sum()
+            .sum())
     }
 }
 
@@ -145,9 +152,9 @@ mod tests {
         let input = "A Y
                            B X
                            C Z";
-        let actual_res = DayTwo::from(input.to_string())
+        let actual_res = DayTwo::try_from(input.to_string()).unwrap()
             .part_one();
-        assert_eq!(15, actual_res);
+        assert_eq!(Ok(15), actual_res);
     }
 
     #[test]
@@ -155,9 +162,17 @@ mod tests {
         let input = "A Y
                            B X
                            C Z";
-        let actual_res = DayTwo::from(input.to_string())
+        let actual_res = DayTwo::try_from(input.to_string()).unwrap()
             .part_two();
-        assert_eq!(12, actual_res);
+        assert_eq!(Ok(12), actual_res);
+    }
+
+    #[test]
+    fn badRoundTest() {
+        let input = "A Y
+                           B W
+                           C Z";
+        assert_eq!(Some(Error::parse(2, 2, "B W")), DayTwo::try_from(input.to_string()).err());
     }
 }
 
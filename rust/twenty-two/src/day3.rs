@@ -1,32 +1,43 @@
 use std::collections::HashSet;
-use crate::core::{Day};
+use std::str::FromStr;
+use crate::core::{parse_lines, AocDay, BadLine, Error};
 
+#[derive(AocDay)]
+#[aoc(day = 3, answer = u32, error = Error, parse = Self::parse)]
 pub struct DayThree {
-    input: String
+    rucksacks: Vec<Rucksack>
 }
 
-impl From<String> for DayThree {
-    fn from(input: String) -> Self {
-        DayThree { input }
+impl DayThree {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(DayThree { rucksacks: parse_lines(3, input, Error::parse)? })
     }
 }
 
 #[derive(Debug)]
 struct Rucksack(Vec<char>, Vec<char>);
 
-impl From<&str> for Rucksack {
-    fn from(input: &str) -> Self {
-        let half = input.len() / 2;
-        let halves = input.split_at(half);
-        assert_eq!(halves.0.len(), halves.1.len(), "Rucksack item lists must be equal");
-        return Rucksack(
+/// Reads the items of a rucksack, letters split evenly between its two compartments.
+impl FromStr for Rucksack {
+    type Err = BadLine;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        if input.len() % 2 != 0 || !input.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(BadLine);
+        }
+        let halves = input.split_at(input.len() / 2);
+        return Ok(Rucksack(
             halves.0.chars().collect(),
             halves.1.chars().collect()
-        );
+        ));
     }
 }
 
 impl Rucksack {
+    fn items(&self) -> HashSet<char> {
+        self.0.iter().chain(self.1.iter()).copied().collect()
+    }
+
     fn find_shared_items(&self) -> HashSet<char> {
         self.0.iter()
             .filter(|c| self.1.contains(c))
@@ -35,16 +46,9 @@ impl Rucksack {
     }
 }
 
-impl Day for DayThree {
-    type R = u32;
-
-    fn day() -> String where Self: Sized {
-        "3".to_string()
-    }
-
-    fn part_one(&self) -> Self::R {
-        self.input.lines()
-            .map(|l| Rucksack::from(l))
+impl DayThree {
+    fn part_one(&self) -> Result<u32, Error> {
+        Ok(self.rucksacks.iter()
             .map(|rucksack| {
                 rucksack.find_shared_items()
                     .iter()
@@ -53,29 +57,27 @@ impl Day for DayThree {
                     )
                     .sum::<u32>()
             })
-            .sum()
+            .sum())
     }
 
-    fn part_two(&self) -> Self::R {
-        let v: Vec<&str> = self.input.lines().collect();
-        return v.chunks(3)
+    fn part_two(&self) -> Result<u32, Error> {
+        return self.rucksacks.chunks(3)
             .filter_map(|g|
                 g.iter()
-                    .map(|elf| elf.chars().collect::<HashSet<char>>())
-                    .into_iter()
+                    .map(|elf| elf.items())
                     .reduce(|shared_items, elf| shared_items.intersection(&elf).copied().collect())
             )
-            .map(|item| item.into_iter().next().expect("There should be a common item!"))
-            .map(|item| Self::get_priority(&item))
+            .map(|item| item.into_iter().next()
+                .ok_or_else(|| Error::no_answer(3, "a group of elves has no item in common")))
+            .map(|item| item.map(|item| Self::get_priority(&item)))
             .sum();
     }
 }
 
 impl DayThree {
+    /// The priority of an item, which is a letter since the rucksacks only hold those.
     fn get_priority(item: &char) -> u32 {
-        if item.is_ascii_lowercase() { *item as u32 - 96 } else if item.is_ascii_uppercase() { *item as u32 - 38 } else {
-            panic!("Unsupported char: {}", item);
-        }
+        if item.is_ascii_lowercase() { *item as u32 - 96 } else { *item as u32 - 38 }
     }
 }
 
@@ -91,9 +93,9 @@ PmmdzqPrVvPwwTWBwg
 wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
 ttgJtRGJQctTZtZT
 CrZsJsPPZsGzwwsLwLmpwMDw";
-        let actual_res = DayThree::from(input.to_string())
+        let actual_res = DayThree::try_from(input.to_string()).unwrap()
             .part_one();
-        assert_eq!(157, actual_res);
+        assert_eq!(Ok(157), actual_res);
     }
 
     #[test]
@@ -104,8 +106,17 @@ PmmdzqPrVvPwwTWBwg
 wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
 ttgJtRGJQctTZtZT
 CrZsJsPPZsGzwwsLwLmpwMDw";
-        let actual_res = DayThree::from(input.to_string())
+        let actual_res = DayThree::try_from(input.to_string()).unwrap()
             .part_two();
-        assert_eq!(70, actual_res);
+        assert_eq!(Ok(70), actual_res);
+    }
+
+    #[test]
+    fn badRucksackTest() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsS
+PmmdzqPrVvPwwTWBwg";
+        assert_eq!(Some(Error::parse(3, 2, "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsS")), DayThree::try_from(input.to_string()).err());
+        assert_eq!(Some(Error::parse(3, 1, "ab-d")), DayThree::try_from(String::from("ab-d")).err());
     }
 }
\ No newline at end of file
@@ -1,63 +1,50 @@
-use std::iter::Map;
-use std::ops::RangeInclusive;
-use std::str::Lines;
-use crate::core::{Day};
+use std::str::FromStr;
+use aoc_utils::interval::IntervalSet;
+use crate::core::{parse_lines, AocDay, BadLine, Error};
 
+#[derive(AocDay)]
+#[aoc(day = 4, answer = usize, error = Error, parse = Self::parse)]
 pub struct DayFour {
-    input: String
+    assignments: Vec<ElfPair>
 }
 
-impl From<String> for DayFour {
-    fn from(input: String) -> Self {
-        DayFour { input }
+impl DayFour {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(DayFour { assignments: parse_lines(4, input, Error::parse)? })
     }
 }
 
-impl Day for DayFour {
-    type R = usize;
+/// The sections assigned to each elf of a pair.
+struct ElfPair(IntervalSet<u32>, IntervalSet<u32>);
+
+impl FromStr for ElfPair {
+    type Err = BadLine;
 
-    fn day() -> String where Self: Sized {
-        "4".to_string()
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let (elf1, elf2) = input.split_once(",").ok_or(BadLine)?;
+        Ok(ElfPair(DayFour::create_range(elf1)?, DayFour::create_range(elf2)?))
     }
+}
 
-    fn part_one(&self) -> Self::R {
-        self.create_elf_assignments()
-            .filter(|(e1, e2)|
-                              (e1.contains(&e2.start()) && e1.contains(&e2.end()))
-                                  || (e2.contains(&e1.start()) && e2.contains(&e1.end())))
-            .count()
+impl DayFour {
+    fn part_one(&self) -> Result<usize, Error> {
+        Ok(self.assignments.iter()
+            .filter(|ElfPair(e1, e2)| e1.is_subset(e2) || e2.is_subset(e1))
+            .count())
     }
 
-    fn part_two(&self) -> Self::R {
-        self.create_elf_assignments()
-            .filter(|(e1, e2)|
-                e1.contains(e2.start())
-                || e1.contains(e2.end())
-                || e2.contains(e1.start())
-                || e2.contains(e1.end())
-            )
-            .count()
+    fn part_two(&self) -> Result<usize, Error> {
+        Ok(self.assignments.iter()
+            .filter(|ElfPair(e1, e2)| !e1.is_disjoint(e2))
+            .count())
     }
 }
 
 impl DayFour {
 
-    fn create_range(input: &str) -> RangeInclusive<u32> {
-        let mut s = input.split("-");
-        let start = s.next().map(|v| v.parse().ok()).unwrap().expect("A range needs a start value!");
-        let end = s.next().map(|v| v.parse().ok()).unwrap().expect("A range needs a start value!");
-        start..=end
-    }
-
-    fn create_elf_assignments(&self) -> Map<Lines, fn(&str) -> (RangeInclusive<u32>, RangeInclusive<u32>)> {
-        self.input.lines().map(|l| {
-            let mut s = l.split(",");
-            let elf1 = s.next()
-                .map(|r| DayFour::create_range(r)).expect("Failed to construct elf 1");
-            let elf2 = s.next()
-                .map(|r| DayFour::create_range(r)).expect("Failed to construct elf 2");
-            (elf1, elf2)
-        })
+    fn create_range(input: &str) -> Result<IntervalSet<u32>, BadLine> {
+        let (start, end) = input.split_once("-").ok_or(BadLine)?;
+        Ok(IntervalSet::from(start.parse()?..=end.parse()?))
     }
 }
 
@@ -73,9 +60,9 @@ mod tests {
 2-8,3-7
 6-6,4-6
 2-6,4-8";
-        let actual_res = DayFour::from(input.to_string())
+        let actual_res = DayFour::try_from(input.to_string()).unwrap()
             .part_one();
-        assert_eq!(2, actual_res);
+        assert_eq!(Ok(2), actual_res);
     }
 
     #[test]
@@ -86,8 +73,17 @@ mod tests {
 2-8,3-7
 6-6,4-6
 2-6,4-8";
-        let actual_res = DayFour::from(input.to_string())
+        let actual_res = DayFour::try_from(input.to_string()).unwrap()
             .part_two();
-        assert_eq!(4, actual_res);
+        assert_eq!(Ok(4), actual_res);
+    }
+
+    #[test]
+    fn badPairTest() {
+        let input = "2-4,6-8
+2-3
+5-7,7-9";
+        assert_eq!(Some(Error::parse(4, 2, "2-3")), DayFour::try_from(input.to_string()).err());
+        assert_eq!(Some(Error::parse(4, 1, "2-x,6-8")), DayFour::try_from(String::from("2-x,6-8")).err());
     }
 }
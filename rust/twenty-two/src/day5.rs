@@ -1,15 +1,20 @@
 use std::collections::{HashMap, VecDeque};
-use crate::core::{Day};
+use std::str::FromStr;
+use crate::core::{AocDay, BadLine, Error};
 use regex::Regex;
 use lazy_static::lazy_static;
 
+#[derive(AocDay)]
+#[aoc(day = 5, answer = String, error = Error, parse = Self::parse)]
 pub struct DayFive {
-    input: String
+    stacks: HashMap<usize, VecDeque<char>>,
+    moves: Vec<Move>
 }
 
-impl From<String> for DayFive {
-    fn from(input: String) -> Self {
-       DayFive { input }
+impl DayFive {
+    fn parse(input: &str) -> Result<Self, Error> {
+        let (stacks, moves) = Self::parse_stacks_and_moves(input)?;
+        Ok(DayFive { stacks, moves })
     }
 }
 
@@ -26,28 +31,26 @@ struct Move {
     qty: usize
 }
 
-impl From<&str> for Move {
-    fn from(input: &str) -> Self {
+impl FromStr for Move {
+    type Err = BadLine;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
         let c: Vec<usize> = MOVE_RE.captures_iter(input)
-            .filter_map(|c| (&c[0]).parse().ok())
-            .collect();
-        Move { from: c[1], to: c[2], qty: c[0] }
+            .map(|c| (&c[0]).parse())
+            .collect::<Result<_, _>>()?;
+        match c[..] {
+            [qty, from, to] => Ok(Move { from, to, qty }),
+            _ => Err(BadLine)
+        }
     }
 }
 
-impl Day for DayFive {
-    type R = String;
-
-    fn day() -> String where Self: Sized {
-       "5".to_string()
-    }
-
-    fn part_one(&self) -> Self::R {
-        let (mut stacks, moves) = self.parse_stacks_and_moves();
+impl DayFive {
+    fn part_one(&self) -> Result<String, Error> {
+        let mut stacks = self.stacks.clone();
 
-        for m in moves.iter() {
-            let from = stacks.get_mut(&m.from).unwrap();
-            let moved: Vec<char> = from.drain(0..m.qty).collect();
+        for m in self.moves.iter() {
+            let moved: Vec<char> = Self::take_crates(&mut stacks, m)?;
             let to = stacks.get_mut(&m.to).unwrap();
             //println!("move {:?} to {:?}", moved, to);
             for ccrate in moved.iter() {
@@ -55,16 +58,15 @@ impl Day for DayFive {
             }
         }
         //println!("Stacks: {:?}", stacks);
-        Self::get_top_crates(&mut stacks)
+        Ok(Self::get_top_crates(&mut stacks))
     }
 
-    fn part_two(&self) -> Self::R {
-        let (mut stacks, moves) = self.parse_stacks_and_moves();
+    fn part_two(&self) -> Result<String, Error> {
+        let mut stacks = self.stacks.clone();
 
-        for m in moves.iter() {
+        for m in self.moves.iter() {
             //println!("Stacks: {:?}", stacks);
-            let from = stacks.get_mut(&m.from).unwrap();
-            let mut moved: Vec<char> = from.drain(0..m.qty).collect();
+            let mut moved: Vec<char> = Self::take_crates(&mut stacks, m)?;
             moved.reverse();
             let to = stacks.get_mut(&m.to).unwrap();
             //println!("move {:?} to {:?}", moved, to);
@@ -73,28 +75,31 @@ impl Day for DayFive {
             }
         }
         //println!("Stacks: {:?}", stacks);
-        Self::get_top_crates(&mut stacks)
+        Ok(Self::get_top_crates(&mut stacks))
     }
 }
 
 impl DayFive {
-    fn parse_stacks_and_moves(&self) -> (HashMap<usize, VecDeque<char>>, Vec<Move>) {
-        let mut s = BLOCK_SPLIT_RE.split(&self.input);
+    fn parse_stacks_and_moves(input: &str) -> Result<(HashMap<usize, VecDeque<char>>, Vec<Move>), Error> {
+        let mut s = BLOCK_SPLIT_RE.split(input);
 
-        let initial_config = s.next().unwrap();
-        let footer = initial_config.lines().last().unwrap();
+        let initial_config = s.next().unwrap_or_default();
+        let config_lines = initial_config.lines().count();
+        let footer = initial_config.lines().last().unwrap_or_default();
         let stack_idx: HashMap<usize, usize> = footer.chars().enumerate()
-            .filter(|(_i, n)| n.is_numeric())
-            .map(|(i, n)| (i, n.to_digit(10).unwrap() as usize)).collect();
-        let mut stacks: HashMap<usize, VecDeque<char>> = footer.chars().enumerate()
-            .filter(|(_i, n)| n.is_numeric())
-            .map(|(i, _n)| (*stack_idx.get(&i).expect("Every index should have a stack id mapping"), VecDeque::new()))
+            .filter_map(|(i, n)| n.to_digit(10).map(|n| (i, n as usize)))
+            .collect();
+        if stack_idx.is_empty() || !footer.chars().all(|c| c.is_ascii_digit() || c == ' ') {
+            return Err(Error::parse(5, config_lines.max(1), footer));
+        }
+        let mut stacks: HashMap<usize, VecDeque<char>> = stack_idx.values()
+            .map(|idx| (*idx, VecDeque::new()))
             .collect();
 
-        for l in initial_config.lines() {
+        for (i, l) in initial_config.lines().enumerate() {
             for (j, c) in l.chars().enumerate() {
                 if c.is_ascii_uppercase() {
-                    let idx = stack_idx.get(&j).unwrap();
+                    let idx = stack_idx.get(&j).ok_or_else(|| Error::parse(5, i + 1, l))?;
                     let stack = stacks.get_mut(idx).unwrap();
                     stack.push_back(c);
                 }
@@ -102,10 +107,23 @@ impl DayFive {
         }
         //println!("Stacks: {:?}", stacks);
 
-        let moves: Vec<Move> = s.next().map(|m|
-            m.lines().map(|l| Move::from(l)).collect()
-        ).unwrap();
-        (stacks, moves)
+        let moves: Vec<Move> = input.lines().enumerate()
+            .skip(config_lines + 1)
+            .filter(|(_i, l)| !l.trim().is_empty())
+            .map(|(i, l)| match l.parse::<Move>() {
+                Ok(m) if stacks.contains_key(&m.from) && stacks.contains_key(&m.to) => Ok(m),
+                _ => Err(Error::parse(5, i + 1, l))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok((stacks, moves))
+    }
+
+    fn take_crates(stacks: &mut HashMap<usize, VecDeque<char>>, m: &Move) -> Result<Vec<char>, Error> {
+        let from = stacks.get_mut(&m.from).unwrap();
+        if from.len() < m.qty {
+            return Err(Error::no_answer(5, format!("cannot move {} crates from stack {} holding {}", m.qty, m.from, from.len())));
+        }
+        Ok(from.drain(0..m.qty).collect())
     }
 
     fn get_top_crates(stacks: &mut HashMap<usize, VecDeque<char>>) -> String {
@@ -119,35 +137,21 @@ impl DayFive {
 mod tests {
     use super::*;
 
-    #[test]
-    fn partOneExampleTest() {
-        let input = "    [D]
-[N] [C]
-[Z] [M] [P]
- 1   2   3
-
-move 1 from 2 to 1
-move 3 from 1 to 3
-move 2 from 2 to 1
-move 1 from 1 to 2";
-        let actual_res = DayFive::from(input.to_string())
-            .part_one();
-        assert_eq!("CMZ", actual_res);
-    }
+    aoc_core::aoc_examples!(DayFive, part_one: [(example(1), "CMZ")], part_two: [(example(1), "MCD")]);
 
     #[test]
-    fn partTwoExampleTest() {
-        let input = "    [D]
-[N] [C]
-[Z] [M] [P]
- 1   2   3
+    fn badMoveTest() {
+        let input = "[N] [C]
+ 1   2
 
 move 1 from 2 to 1
-move 3 from 1 to 3
-move 2 from 2 to 1
-move 1 from 1 to 2";
-        let actual_res = DayFive::from(input.to_string())
-            .part_two();
-        assert_eq!("MCD", actual_res);
+move one from 1 to 2
+move 1 from 1 to 3";
+        assert_eq!(Some(Error::parse(5, 5, "move one from 1 to 2")), DayFive::try_from(input.to_string()).err());
+        let input = "[N] [C]
+ 1   2
+
+move 1 from 1 to 3";
+        assert_eq!(Some(Error::parse(5, 4, "move 1 from 1 to 3")), DayFive::try_from(input.to_string()).err());
     }
 }
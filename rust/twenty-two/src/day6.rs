@@ -1,28 +1,18 @@
 use std::collections::{HashSet, VecDeque};
-use crate::core::{Day};
+use crate::core::AocDay;
 
+#[derive(AocDay)]
+#[aoc(day = 6, answer = u32)]
 pub struct DaySix {
     input: String
 }
 
-impl From<String> for DaySix {
-    fn from(input: String) -> Self {
-       DaySix { input }
-    }
-}
-
-impl Day for DaySix {
-    type R = u32;
-
-    fn day() -> String where Self: Sized {
-        "6".to_string()
-    }
-
-    fn part_one(&self) -> Self::R {
+impl DaySix {
+    fn part_one(&self) -> u32 {
         self.find_marker_of_length(4)
     }
 
-    fn part_two(&self) -> Self::R {
+    fn part_two(&self) -> u32 {
         self.find_marker_of_length(14)
     }
 }
@@ -1,32 +1,21 @@
-use crate::core::{Day};
+use crate::core::AocDay;
 use std::collections::HashMap;
 use regex::Regex;
 use lazy_static::lazy_static;
 
+#[derive(AocDay)]
+#[aoc(day = 7, answer = u64)]
 pub struct DaySeven {
     input: String
 }
 
-// this implementation should/could be a derive-macro
-impl From<String> for DaySeven {
-    fn from(input: String) -> Self {
-        DaySeven { input }
-    }
-}
-
 lazy_static! {
     static ref CD_DOWN: Regex = Regex::new(r"(\$ cd )([\w/]+)").unwrap();
     static ref CD_UP: Regex = Regex::new(r"(\$ cd )([.]{2})").unwrap();
     static ref FILE: Regex = Regex::new(r"(\d+)[ \w\.]+").unwrap();
 }
 
-impl Day for DaySeven {
-    type R = u64;
-
-    fn day() -> String where Self: Sized {
-        String::from("7")
-    }
-
+impl DaySeven {
     /*
           keep map of dir names to size.
           $ cd {dir_name} => set current dir to dir_name, => push {dir_name}
@@ -37,14 +26,14 @@ impl Day for DaySeven {
 
          when done, filter map on size and sum
     */
-    fn part_one(&self) -> Self::R {
+    fn part_one(&self) -> u64 {
         let dir_size = self.get_directory_size();
         return dir_size.values()
             .filter(|size| *size <= &100000)
             .sum();
     }
 
-    fn part_two(&self) -> Self::R {
+    fn part_two(&self) -> u64 {
         let dir_size = self.get_directory_size();
         let total_disk_space = 70000000;
         let minimum_disk_space_required = 30000000;
@@ -1,36 +1,32 @@
 use std::collections::HashSet;
-use crate::core::{Day};
+use std::str::FromStr;
+use crate::core::{parse_lines, AocDay, BadLine, Error};
 use crate::day9::RopeMove::{DOWN, LEFT, RIGHT, UP};
 
+#[derive(AocDay)]
+#[aoc(day = 9, answer = usize, error = Error, parse = Self::parse)]
 pub struct DayNine {
-    input: String
+    moves: Vec<RopeMove>
 }
 
-impl From<String> for DayNine {
-    fn from(input: String) -> Self {
-        DayNine { input }
+impl DayNine {
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(DayNine { moves: parse_lines(9, input, Error::parse)? })
     }
 }
 
-impl Day for DayNine {
-    type R = usize;
-
-    fn day() -> String where Self: Sized {
-        "9".to_string()
-    }
-
-    fn part_one(&self) -> Self::R {
+impl DayNine {
+    fn part_one(&self) -> Result<usize, Error> {
         let mut visited: HashSet<(i32, i32)> = HashSet::new();
         let mut state = State { head_pos: (0,0), tail_pos: (0,0) };
-        for rope_move in self.input.lines().map(|l| l.trim()) {
-            let m = RopeMove::from(rope_move);
+        for m in self.moves.iter() {
             state.nextState(&mut visited, m);
 
         }
-        visited.len()
+        Ok(visited.len())
     }
 
-    fn part_two(&self) -> Self::R {
+    fn part_two(&self) -> Result<usize, Error> {
         todo!()
     }
 }
@@ -42,7 +38,7 @@ struct State {
 
 impl State {
 
-    fn nextState(&mut self, visited: &mut HashSet<(i32,i32)>, m: RopeMove) {
+    fn nextState(&mut self, visited: &mut HashSet<(i32,i32)>, m: &RopeMove) {
         for _step in 0..m.getMoves() {
             let head_delta = match m {
                 UP(_) => (0, 1),
@@ -89,15 +85,19 @@ impl RopeMove {
     }
 }
 
-impl From<&str> for RopeMove {
-    fn from(input: &str) -> Self {
+impl FromStr for RopeMove {
+    type Err = BadLine;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
        let mut split = input.split(" ");
-        match split.next() {
-            Some("U") => UP(split.next().map_or(0, |d| d.parse().unwrap())),
-            Some("D") => DOWN(split.next().map_or(0, |d| d.parse().unwrap())),
-            Some("L") => LEFT(split.next().map_or(0, |d| d.parse().unwrap())),
-            Some("R") => RIGHT(split.next().map_or(0, |d| d.parse().unwrap())),
-            _ => panic!("Unable to parse &str {} into RopeMove", input)
+        let direction = split.next();
+        let steps = split.next().ok_or(BadLine)?.parse()?;
+        match direction {
+            Some("U") => Ok(UP(steps)),
+            Some("D") => Ok(DOWN(steps)),
+            Some("L") => Ok(LEFT(steps)),
+            Some("R") => Ok(RIGHT(steps)),
+            _ => Err(BadLine)
         }
     }
 }
@@ -117,7 +117,15 @@ mod tests {
             D 1
             L 5
             R 2";
-        let actual_res = DayNine::from(input.to_string()).part_one();
-        assert_eq!(13, actual_res);
+        let actual_res = DayNine::try_from(input.to_string()).unwrap().part_one();
+        assert_eq!(Ok(13), actual_res);
+    }
+
+    #[test]
+    fn badMoveTest() {
+        let input = "R 4
+            U 4
+            X 3";
+        assert_eq!(Some(Error::parse(9, 3, "X 3")), DayNine::try_from(input.to_string()).err());
     }
 }
\ No newline at end of file
@@ -1,5 +1,7 @@
 #![allow(non_snake_case)]
 
+use aoc_core::Solution;
+
 pub mod core;
 pub mod day1;
 pub mod day2;
@@ -14,3 +16,11 @@ pub mod day11;
 pub mod day13;
 pub mod day14;
 pub mod day15;
+
+pub const YEAR: u16 = 2022;
+
+aoc_core::register_year!(YEAR, core::inputs);
+
+pub fn solutions() -> Vec<Solution> {
+    aoc_core::registry::solutions(YEAR)
+}
@@ -1,31 +1,11 @@
-use twentytwolib::day7::DaySeven;
-use twentytwolib::solve;
-use twentytwolib::core::Day;
-use twentytwolib::day9::DayNine;
-use twentytwolib::day10::DayTen;
-use twentytwolib::day11::DayEleven;
-use twentytwolib::day13::DayThirteen;
-use twentytwolib::day14::DayFourteen;
-use twentytwolib::day15::DayFifteen;
-use twentytwolib::day1::DayOne;
-use twentytwolib::day2::DayTwo;
-use twentytwolib::day3::DayThree;
-use twentytwolib::day4::DayFour;
-use twentytwolib::day5::DayFive;
-use twentytwolib::day6::DaySix;
+use std::process::ExitCode;
+use twentytwolib::core::inputs;
+use twentytwolib::solutions;
 
-fn main() {
-    solve!(DayOne);
-    solve!(DayTwo);
-    solve!(DayThree);
-    solve!(DayFour);
-    solve!(DayFive);
-    solve!(DaySix);
-    solve!(DaySeven);
-    solve!(DayNine);
-    solve!(DayTen);
-    solve!(DayEleven);
-    solve!(DayThirteen);
-    solve!(DayFourteen);
-    solve!(DayFifteen);
+fn main() -> ExitCode {
+    if aoc_core::run(&solutions(), &inputs()) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
 }